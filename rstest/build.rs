@@ -0,0 +1,9 @@
+use rustc_version::{version, Version};
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(diagnostic_namespace)");
+    // `#[diagnostic::on_unimplemented]` is stable since 1.78
+    if version().unwrap() >= Version::new(1, 78, 0) {
+        println!("cargo:rustc-cfg=diagnostic_namespace");
+    }
+}
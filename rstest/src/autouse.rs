@@ -0,0 +1,96 @@
+use std::{
+    cell::Cell,
+    future::{ready, Future, Ready},
+};
+
+/// The value of an `#[autouse]` fixture. The traits below select how to resolve it by
+/// autoref: futures first, then any other value. Async tests await both, sync tests
+/// cannot take a future.
+pub struct Autoused<T>(Cell<Option<T>>);
+
+impl<T> Autoused<T> {
+    pub fn new(value: T) -> Self {
+        Self(Cell::new(Some(value)))
+    }
+
+    fn take(&self) -> T {
+        self.0
+            .take()
+            .expect("an autouse fixture is resolved just once")
+    }
+}
+
+/// The value of an `#[autouse]` fixture resolved by a sync test.
+pub struct Resolved<T>(pub T);
+
+/// A sync test cannot await an async `#[autouse]` fixture: this type doesn't match
+/// `Resolved`, so the test doesn't compile.
+pub struct AsyncFixtureInSyncTest<F>(pub F);
+
+pub trait ViaFuture<F> {
+    fn resolve(&self) -> F;
+
+    fn resolve_sync(&self) -> AsyncFixtureInSyncTest<F>;
+}
+
+impl<F: Future> ViaFuture<F> for &Autoused<F> {
+    fn resolve(&self) -> F {
+        self.take()
+    }
+
+    fn resolve_sync(&self) -> AsyncFixtureInSyncTest<F> {
+        AsyncFixtureInSyncTest(self.take())
+    }
+}
+
+pub trait ViaValue<T> {
+    fn resolve(&self) -> Ready<T>;
+
+    fn resolve_sync(&self) -> Resolved<T>;
+}
+
+impl<T> ViaValue<T> for Autoused<T> {
+    fn resolve(&self) -> Ready<T> {
+        ready(self.take())
+    }
+
+    fn resolve_sync(&self) -> Resolved<T> {
+        Resolved(self.take())
+    }
+}
+
+#[cfg(test)]
+// The borrows select the resolution by autoref
+#[allow(clippy::needless_borrow)]
+mod test {
+    use super::*;
+    use async_std::task::block_on;
+
+    #[test]
+    fn await_futures() {
+        let value = block_on((&&Autoused::new(async { 42 })).resolve());
+
+        assert_eq!(42, value);
+    }
+
+    #[test]
+    fn await_values() {
+        let value = block_on((&&Autoused::new(42)).resolve());
+
+        assert_eq!(42, value);
+    }
+
+    #[test]
+    fn take_values_in_sync_tests() {
+        let Resolved(value) = (&&Autoused::new(42)).resolve_sync();
+
+        assert_eq!(42, value);
+    }
+
+    #[test]
+    fn mark_futures_in_sync_tests() {
+        let AsyncFixtureInSyncTest(future) = (&&Autoused::new(async { 42 })).resolve_sync();
+
+        assert_eq!(42, block_on(future));
+    }
+}
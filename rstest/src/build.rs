@@ -0,0 +1,270 @@
+//! Helpers for your crate's build script.
+//!
+//! `#[files(...)]` globs are expanded when the tests are compiled, so cargo doesn't know
+//! that a test should be compiled again when a file is added to (or removed from) the
+//! folders that the globs match. The macro cannot tell cargo by itself: on stable Rust a
+//! procedural macro can make the compiler track the files it reads (the matched files are
+//! included, so changing their contents builds the test again) but not a folder, because
+//! `proc_macro::tracked_path` is still unstable. Call [`track_files()`] from your
+//! `build.rs` to tell it:
+//!
+//! ```toml
+//! [build-dependencies]
+//! rstest = { version = "*", default-features = false, features = ["build"] }
+//! ```
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     rstest::build::track_files();
+//! }
+//! ```
+
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::{LitStr, MetaList};
+
+/// The folders where the `#[files(...)]` attributes can live.
+const SOURCES: &[&str] = &["src", "tests", "benches", "examples"];
+
+/// Print the `cargo:rerun-if-changed` directives that make cargo build the crate again when
+/// the folders matched by its `#[files(...)]` globs or its sources change.
+///
+/// The globs are read from the crate's `src`, `tests`, `benches` and `examples` folders
+/// by tokenizing the sources, so commented out attributes are ignored.
+/// Globs that start with a pattern (i.e. `*.txt`) cannot be tracked because cargo would
+/// watch the whole crate's folder, `target` included: a warning is printed for them.
+pub fn track_files() {
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .expect("rstest::build::track_files() should be called from a build script");
+    for directive in directives(&root) {
+        println!("{directive}");
+    }
+}
+
+fn directives(root: &Path) -> Vec<String> {
+    let sources = SOURCES
+        .iter()
+        .filter(|s| root.join(s).is_dir())
+        .collect::<Vec<_>>();
+    let mut globs = BTreeSet::new();
+    for source in &sources {
+        for file in rust_files(&root.join(source)) {
+            if let Ok(code) = std::fs::read_to_string(file) {
+                globs.extend(files_globs(&code));
+            }
+        }
+    }
+    let mut folders = BTreeSet::new();
+    let mut directives = vec!["cargo:rerun-if-changed=build.rs".to_owned()];
+    directives.extend(
+        sources
+            .iter()
+            .map(|source| format!("cargo:rerun-if-changed={source}")),
+    );
+    for glob in globs {
+        match glob_base_dir(&glob) {
+            Some(folder) => {
+                folders.insert(folder);
+            }
+            None => directives.push(format!(
+                "cargo:warning=rstest cannot track the files matched by `{glob}`: \
+                the glob should start with a folder"
+            )),
+        }
+    }
+    directives.extend(
+        folders
+            .into_iter()
+            .map(|folder| format!("cargo:rerun-if-changed={folder}")),
+    );
+    directives
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            files.extend(rust_files(&path));
+        } else if path.extension().map(|e| e == "rs").unwrap_or(false) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// The globs of all the `#[files("glob")]` attributes in `code`: the code is tokenized, so
+/// comments and string literals are skipped and the attribute can be written in any form
+/// (i.e. `# [files ("glob")]`, split on more lines or as `#[rstest::files("glob")]`).
+fn files_globs(code: &str) -> Vec<String> {
+    let mut globs = vec![];
+    if let Ok(tokens) = code.parse() {
+        collect_files_globs(tokens, &mut globs);
+    }
+    globs
+}
+
+fn collect_files_globs(tokens: TokenStream, globs: &mut Vec<String>) {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                if let Some(TokenTree::Group(attr)) = tokens.peek() {
+                    if attr.delimiter() == Delimiter::Bracket {
+                        globs.extend(files_glob(attr.stream()));
+                    }
+                }
+            }
+            TokenTree::Group(group) => collect_files_globs(group.stream(), globs),
+            _ => {}
+        }
+    }
+}
+
+/// The glob of the attribute's content, if it's a `files("glob")` attribute.
+fn files_glob(attr: TokenStream) -> Option<String> {
+    let attr = syn::parse2::<MetaList>(attr).ok()?;
+    if attr.path.segments.last()?.ident != "files" {
+        return None;
+    }
+    syn::parse2::<LitStr>(attr.tokens)
+        .ok()
+        .map(|glob| glob.value())
+}
+
+/// The folder where the glob's patterns start or, if it's a plain path, the file's folder.
+fn glob_base_dir(glob: &str) -> Option<String> {
+    let components = glob.split('/').collect::<Vec<_>>();
+    let base = match components
+        .iter()
+        .position(|c| c.contains(['*', '?', '[', '{']))
+    {
+        Some(pattern) => &components[..pattern],
+        None => &components[..components.len() - 1],
+    };
+    let base = base
+        .iter()
+        .filter(|&&c| !c.is_empty() && c != ".")
+        .copied()
+        .collect::<Vec<_>>();
+    (!base.is_empty()).then(|| base.join("/"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use temp_testdir::TempDir;
+
+    #[test]
+    fn find_the_files_globs() {
+        let code = r####"
+            #[rstest]
+            fn test(#[files("tests/cases/*.yaml")] #[exclude("skip")] path: PathBuf) {}
+
+            #[rstest]
+            fn other(#[files( "data/\"quoted\"/*" )] #[files(r#"raw/**/*.txt"#)] path: PathBuf) {}
+
+            #[rstest]
+            fn spaced(# [files ("spaced/*")] a: PathBuf, #[rstest::files("path/*")] b: PathBuf) {}
+
+            #[rstest]
+            fn split(
+                #[files(
+                    "split/*"
+                )]
+                path: PathBuf,
+            ) {}
+
+            mod nested {
+                #[rstest]
+                fn test(#[files("nested/*")] path: PathBuf) {}
+            }
+        "####;
+
+        assert_eq!(
+            vec![
+                "tests/cases/*.yaml",
+                r#"data/"quoted"/*"#,
+                "raw/**/*.txt",
+                "spaced/*",
+                "path/*",
+                "split/*",
+                "nested/*"
+            ],
+            files_globs(code)
+        );
+    }
+
+    #[test]
+    fn ignore_comments_strings_and_other_attributes() {
+        let code = r####"
+            // #[files("line_comment/*")]
+            /* #[files("block_comment/*")] */
+            /// #[files("doc_comment/*")]
+            const CODE: &str = r#"#[files("string/*")]"#;
+            #[rstest]
+            fn test(#[exclude("exclude/*")] #[other::files_dir("other/*")] path: PathBuf) {}
+        "####;
+
+        assert!(files_globs(code).is_empty(), "{:?}", files_globs(code));
+    }
+
+    #[test]
+    fn ignore_the_files_that_cannot_be_tokenized() {
+        assert!(files_globs(r#"fn f(#[files("a/*")] p: PathBuf) { "#).is_empty());
+    }
+
+    #[test]
+    fn compute_the_glob_base_dir() {
+        let cases = [
+            ("tests/cases/*.yaml", Some("tests/cases")),
+            ("tests/**/*.yaml", Some("tests")),
+            ("./tests/c?ses/a.yaml", Some("tests")),
+            ("../shared/[ab].txt", Some("../shared")),
+            ("tests/data/file.txt", Some("tests/data")),
+            ("*.txt", None),
+            ("file.txt", None),
+        ];
+
+        for (glob, expected) in cases {
+            assert_eq!(expected.map(str::to_owned), glob_base_dir(glob), "{glob}");
+        }
+    }
+
+    #[test]
+    fn track_the_sources_and_the_globs_folders() {
+        let root = TempDir::default();
+        std::fs::create_dir_all(root.join("tests/nested")).unwrap();
+        std::fs::write(
+            root.join("tests/nested/test.rs"),
+            r#"fn a(#[files("tests/cases/*.yaml")] p: PathBuf, #[files("*.md")] d: PathBuf) {}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("tests/other.rs"),
+            r#"fn b(#[files("tests/cases/**/*.json")] p: PathBuf) {}"#,
+        )
+        .unwrap();
+
+        let directives = directives(&root);
+
+        assert_eq!(
+            vec![
+                "cargo:rerun-if-changed=build.rs",
+                "cargo:rerun-if-changed=tests",
+                "cargo:warning=rstest cannot track the files matched by `*.md`: \
+                the glob should start with a folder",
+                "cargo:rerun-if-changed=tests/cases",
+            ],
+            directives
+        );
+    }
+}
@@ -0,0 +1,49 @@
+/// An argument of a fixture builder: its value is either given by the user or
+/// resolved lazily by the fixture's default.
+pub trait Arg {
+    type Value;
+
+    fn value(self) -> Self::Value;
+}
+
+/// An argument value given by the user.
+pub struct Given<V>(pub V);
+
+impl<V> Arg for Given<V> {
+    type Value = V;
+
+    fn value(self) -> V {
+        self.0
+    }
+}
+
+/// An argument that is resolved by calling `F` just if the user doesn't give it.
+pub struct Lazy<F>(pub F);
+
+impl<V, F: FnOnce() -> V> Arg for Lazy<F> {
+    type Value = V;
+
+    fn value(self) -> V {
+        (self.0)()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_lazy_arguments_just_when_needed() {
+        let mut called = false;
+
+        let given = Given(42).value();
+        let lazy = Lazy(|| {
+            called = true;
+            0
+        });
+
+        assert_eq!(42, given);
+        assert_eq!(0, lazy.value());
+        assert!(called);
+    }
+}
@@ -0,0 +1,30 @@
+/// Poll the futures together till they are all done and give their outputs:
+/// `#[concurrent]` tests and fixtures await their awaited arguments by it.
+pub use futures::join;
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    async fn delayed(value: u32, delay: u64) -> u32 {
+        async_std::task::sleep(Duration::from_millis(delay)).await;
+        value
+    }
+
+    #[async_std::test]
+    async fn await_the_futures_concurrently() {
+        let start = Instant::now();
+        let (a, b, c) =
+            crate::concurrent::join!(delayed(1, 200), delayed(2, 200), async { "ready" });
+
+        assert!(start.elapsed() < Duration::from_millis(380));
+        assert_eq!((1, 2, "ready"), (a, b, c));
+    }
+
+    #[test]
+    fn keep_send_futures_send() {
+        fn assert_send<T: Send>(_: T) {}
+
+        assert_send(async { crate::concurrent::join!(delayed(1, 10), delayed(2, 10)) });
+    }
+}
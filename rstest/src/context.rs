@@ -0,0 +1,224 @@
+//! The running test's context: see [`Context`] and the `#[context]` attribute.
+
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    task::{self, Poll},
+    time::Instant,
+};
+
+thread_local! {
+    static TESTS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+#[doc(hidden)]
+/// A test of `rstest`: its path and when it started.
+#[derive(Debug, Clone, Copy)]
+pub struct Test {
+    path: &'static str,
+    start: Instant,
+}
+
+#[doc(hidden)]
+/// A new test. `path` is the test's full path (as given by `module_path!()`): the crate
+/// name is removed to get the same name that the test harness uses.
+pub fn test(path: &'static str) -> Test {
+    Test {
+        path: path.split_once("::").map(|(_, t)| t).unwrap_or(path),
+        start: Instant::now(),
+    }
+}
+
+impl Test {
+    /// Mark the test as running in the current thread till the returned guard is dropped.
+    pub fn enter(self) -> TestGuard {
+        resume(self.path)
+    }
+
+    /// Mark the test as running in the thread that polls the async test's `future`, just
+    /// while it's polled: it can be polled by a different thread every time.
+    pub fn run<F: Future>(self, future: F) -> Running<F> {
+        Running {
+            test: self,
+            future: Box::pin(future),
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Track the running test till it's dropped.
+pub struct TestGuard(&'static str);
+
+impl Drop for TestGuard {
+    fn drop(&mut self) {
+        // Remove just this guard's entry, even if it's not the innermost one
+        TESTS.with(|t| {
+            let mut tests = t.borrow_mut();
+            if let Some(position) = tests.iter().rposition(|&test| test == self.0) {
+                tests.remove(position);
+            }
+        });
+    }
+}
+
+#[doc(hidden)]
+/// The future returned by [`Test::run()`].
+pub struct Running<F: Future> {
+    test: Test,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Running<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let _test = self.test.enter();
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// The `rstest` test running in the current thread, if any.
+pub(crate) fn running() -> Option<&'static str> {
+    TESTS.with(|t| t.borrow().last().copied())
+}
+
+/// Mark the `running()` test of another thread as running in the current one.
+pub(crate) fn resume(test: &'static str) -> TestGuard {
+    TESTS.with(|t| t.borrow_mut().push(test));
+    TestGuard(test)
+}
+
+/// The name of the running test. If the current thread is not running an `rstest`
+/// test (i.e. the fixture was resolved in an async runtime's worker thread) fall back
+/// to the thread's name.
+pub(crate) fn current_test() -> String {
+    running()
+        .map(str::to_owned)
+        .or_else(|| std::thread::current().name().map(str::to_owned))
+        .unwrap_or_else(|| "<unknown>".to_owned())
+}
+
+/// The running test as seen by a `#[context]` argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Context {
+    /// The test's full path without the crate name, as the test harness shows it
+    /// (i.e. `module::the_test::case_1`).
+    pub path: &'static str,
+    /// The name of the `#[rstest]` function.
+    pub name: &'static str,
+    /// The case number, starting from 1, if the test is a `#[case]`.
+    pub case: Option<usize>,
+    /// The description of the case, like `description` in `#[case::description(...)]`.
+    pub description: Option<&'static str>,
+    /// The labels of the values lists' values in the test path (i.e. `x_1_42`).
+    pub values: &'static [&'static str],
+    /// When the test started, before resolving its arguments.
+    pub start: Instant,
+}
+
+impl Context {
+    pub fn new(
+        test: &Test,
+        name: &'static str,
+        case: Option<usize>,
+        description: Option<&'static str>,
+        values: &'static [&'static str],
+    ) -> Self {
+        Self {
+            path: test.path,
+            name,
+            case,
+            description,
+            values,
+            start: test.start,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::teardown::test::{NoopWaker, YieldOnce};
+    use std::sync::Arc;
+
+    #[test]
+    fn remove_crate_name_from_the_test_path() {
+        let _guard = test("my_crate::module::case_1").enter();
+
+        assert_eq!("module::case_1", current_test());
+    }
+
+    #[test]
+    fn restore_the_previous_test_when_dropped() {
+        let _outer = test("my_crate::outer").enter();
+        {
+            let _inner = test("my_crate::inner").enter();
+            assert_eq!("inner", current_test());
+        }
+
+        assert_eq!("outer", current_test());
+    }
+
+    #[test]
+    fn remove_just_its_own_test_when_dropped_out_of_order() {
+        let outer = test("my_crate::outer").enter();
+        let inner = test("my_crate::inner").enter();
+
+        drop(outer);
+        assert_eq!("inner", current_test());
+        drop(inner);
+        assert_eq!(None, running());
+    }
+
+    #[test]
+    fn mark_async_tests_as_running_just_while_polled() {
+        let mut future = test("my_crate::async_test").run(async {
+            let before = current_test();
+            YieldOnce(false).await;
+            (before, current_test())
+        });
+        let waker = std::task::Waker::from(Arc::new(NoopWaker));
+        let mut cx = task::Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        assert_eq!(None, running());
+        {
+            // Another test is running on this thread
+            let _other = test("my_crate::other").enter();
+            let Poll::Ready(seen) = Pin::new(&mut future).poll(&mut cx) else {
+                panic!("Should be ready")
+            };
+
+            assert_eq!(("async_test".to_owned(), "async_test".to_owned()), seen);
+            assert_eq!("other", current_test());
+        }
+        assert_eq!(None, running());
+    }
+
+    #[test]
+    fn build_the_context_of_the_running_test() {
+        let test = test("my_crate::module::the_test::case_2_two::x_1_42");
+
+        let context = Context::new(&test, "the_test", Some(2), Some("two"), &["x_1_42"]);
+
+        assert_eq!("module::the_test::case_2_two::x_1_42", context.path);
+        assert_eq!("the_test", context.name);
+        assert_eq!(Some(2), context.case);
+        assert_eq!(Some("two"), context.description);
+        assert_eq!(&["x_1_42"], context.values);
+        assert_eq!(test.start, context.start);
+    }
+
+    #[test]
+    fn use_the_thread_name_if_no_test_is_running() {
+        let name = std::thread::Builder::new()
+            .name("some_thread".to_owned())
+            .spawn(current_test)
+            .unwrap()
+            .join()
+            .unwrap();
+
+        assert_eq!("some_thread", name);
+    }
+}
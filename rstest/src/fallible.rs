@@ -0,0 +1,115 @@
+use std::{cell::Cell, error::Error, fmt::Debug};
+
+/// The error returned by a `#[fallible]` fixture. The traits below select how to
+/// report it by autoref: errors with a source chain first, then anything
+/// that implements `Debug`.
+pub struct Failure<E>(Cell<Option<E>>);
+
+impl<E> Failure<E> {
+    pub fn new(error: E) -> Self {
+        Self(Cell::new(Some(error)))
+    }
+
+    fn take(&self) -> E {
+        self.0
+            .take()
+            .expect("a fixture setup failure is reported just once")
+    }
+}
+
+fn chain(fixture: &str, error: &dyn Error) -> String {
+    let mut message = format!("setup of fixture `{fixture}` failed: {error}");
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!("\ncaused by: {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+pub trait ViaSendError {
+    fn message(&self, fixture: &str) -> String;
+}
+
+impl<E: Into<Box<dyn Error + Send + Sync>>> ViaSendError for &&Failure<E> {
+    fn message(&self, fixture: &str) -> String {
+        chain(fixture, self.take().into().as_ref())
+    }
+}
+
+pub trait ViaError {
+    fn message(&self, fixture: &str) -> String;
+}
+
+impl<E: Into<Box<dyn Error>>> ViaError for &Failure<E> {
+    fn message(&self, fixture: &str) -> String {
+        chain(fixture, self.take().into().as_ref())
+    }
+}
+
+pub trait ViaDebug {
+    fn message(&self, fixture: &str) -> String;
+}
+
+impl<E: Debug> ViaDebug for Failure<E> {
+    fn message(&self, fixture: &str) -> String {
+        format!("setup of fixture `{fixture}` failed: {:?}", self.take())
+    }
+}
+
+#[cfg(test)]
+// The borrows select the report by autoref
+#[allow(clippy::needless_borrow)]
+mod test {
+    use super::*;
+    use std::fmt::Display;
+
+    #[derive(Debug)]
+    struct Wrapped(&'static str, Option<Box<Wrapped>>);
+
+    impl Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.1.as_deref().map(|e| e as &dyn Error)
+        }
+    }
+
+    #[test]
+    fn report_the_whole_error_chain() {
+        let error = Wrapped(
+            "cannot connect",
+            Some(Box::new(Wrapped("connection refused", None))),
+        );
+
+        assert_eq!(
+            (&&&Failure::new(error)).message("db"),
+            "setup of fixture `db` failed: cannot connect\ncaused by: connection refused"
+        );
+    }
+
+    #[test]
+    fn report_not_send_boxed_errors() {
+        let error: Box<dyn Error> = "cannot connect".into();
+
+        assert_eq!(
+            (&&&Failure::new(error)).message("db"),
+            "setup of fixture `db` failed: cannot connect"
+        );
+    }
+
+    #[test]
+    fn report_debug_errors() {
+        #[derive(Debug)]
+        struct NotAnError;
+
+        assert_eq!(
+            (&&&Failure::new(NotAnError)).message("db"),
+            "setup of fixture `db` failed: NotAnError"
+        );
+    }
+}
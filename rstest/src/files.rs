@@ -0,0 +1,68 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// Set this environment variable to the crate root where the tests run to relocate the
+/// `#[files(...)]` paths injected by `#[mode = relocatable]`.
+pub const FILES_BASE_ENV: &str = "RSTEST_FILES_BASE";
+
+/// Join the crate relative path of a file to the crate root where the test runs:
+/// `RSTEST_FILES_BASE` if it's defined, the `CARGO_MANIFEST_DIR` that cargo (or
+/// nextest) defines when runs the test or, finally, the crate root where the test was
+/// built (`compile_time_base`).
+pub fn relocatable_path(relative: &str, compile_time_base: &str) -> PathBuf {
+    base_dir(
+        std::env::var_os(FILES_BASE_ENV),
+        std::env::var_os("CARGO_MANIFEST_DIR"),
+        compile_time_base,
+    )
+    .join(relative)
+}
+
+fn base_dir(
+    files_base: Option<OsString>,
+    manifest_dir: Option<OsString>,
+    compile_time_base: &str,
+) -> PathBuf {
+    files_base
+        .into_iter()
+        .chain(manifest_dir)
+        .find(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(compile_time_base).to_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn os(s: &str) -> Option<OsString> {
+        Some(s.into())
+    }
+
+    #[test]
+    fn prefer_the_files_base_env() {
+        assert_eq!(
+            PathBuf::from("/files_base"),
+            base_dir(os("/files_base"), os("/manifest"), "/build")
+        );
+    }
+
+    #[test]
+    fn use_the_runtime_manifest_dir() {
+        assert_eq!(
+            PathBuf::from("/manifest"),
+            base_dir(None, os("/manifest"), "/build")
+        );
+        assert_eq!(
+            PathBuf::from("/manifest"),
+            base_dir(os(""), os("/manifest"), "/build")
+        );
+    }
+
+    #[test]
+    fn fallback_to_the_compile_time_base() {
+        assert_eq!(PathBuf::from("/build"), base_dir(None, None, "/build"));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    ops::Deref,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{mpsc, Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{context, teardown};
+
+/// Ask the owner thread to tear down its fixture and wait for the failures.
+pub(crate) type Release = mpsc::Sender<Vec<String>>;
+
+struct State<T> {
+    value: Option<Arc<T>>,
+    owner: Option<mpsc::Sender<Release>>,
+    finished: usize,
+}
+
+/// Share a fixture's value among the `size` tests of a group: the value is initialized
+/// by the first test that needs it and torn down when the last test of the group ends.
+/// If some tests of the group never run (i.e. filtered out) the value is torn down
+/// when the process exits.
+///
+/// The fixture is resolved by an owner thread that waits for the end of the group to
+/// execute its teardowns: they are executed by the thread that registered them, so
+/// they don't need to be `Send`.
+pub struct Group<T> {
+    size: usize,
+    state: Mutex<State<T>>,
+}
+
+impl<T: Send + Sync + 'static> Group<T> {
+    pub const fn new(size: usize) -> Self {
+        Self {
+            size,
+            state: Mutex::new(State {
+                value: None,
+                owner: None,
+                finished: 0,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the group's value, or initialize it with `init`. The returned `Member` should
+    /// live till the end of the test.
+    pub fn get(
+        &'static self,
+        fixture: &'static str,
+        init: impl FnOnce() -> T + Send + 'static,
+    ) -> Member<T> {
+        let mut member = Member {
+            group: self,
+            fixture,
+            value: None,
+        };
+        let mut state = self.lock();
+        let value = match &state.value {
+            Some(value) => value.clone(),
+            None => {
+                let (value, owner) =
+                    spawn_owner(format!("rstest group {fixture}"), move || Arc::new(init()));
+                let value = value.unwrap_or_else(|payload| resume_unwind(payload));
+                state.value = Some(value.clone());
+                state.owner = Some(owner);
+                teardown::register_at_exit(fixture, move || {
+                    for failure in self.release() {
+                        eprintln!("{failure}");
+                    }
+                });
+                value
+            }
+        };
+        member.value = Some(value);
+        member
+    }
+
+    fn finish(&self) -> Vec<String> {
+        let mut state = self.lock();
+        state.finished += 1;
+        if state.finished < self.size {
+            return Vec::new();
+        }
+        state.finished = 0;
+        drop(state);
+        self.release()
+    }
+
+    fn release(&self) -> Vec<String> {
+        let mut state = self.lock();
+        let value = state.value.take();
+        let owner = state.owner.take();
+        drop(state);
+        let failures = owner.map(release).unwrap_or_default();
+        drop(value);
+        failures
+    }
+}
+
+/// Resolve a fixture by `init` in a new thread, called `name`, that keeps its teardowns
+/// till it receives a `Release`. If `init` panics, its teardowns are executed immediately.
+pub(crate) fn spawn_owner<T: Send + 'static>(
+    name: String,
+    init: impl FnOnce() -> T + Send + 'static,
+) -> (std::thread::Result<T>, mpsc::Sender<Release>) {
+    let test = context::running();
+    let (value_sender, value) = mpsc::channel();
+    let (owner, release) = mpsc::channel::<Release>();
+    std::thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            let _test = test.map(context::resume);
+            match catch_unwind(AssertUnwindSafe(|| teardown::collect(init))) {
+                Ok((value, guards)) => {
+                    let _ = value_sender.send(Ok(value));
+                    if guards.is_empty() {
+                        return;
+                    }
+                    if let Ok(release) = release.recv() {
+                        let _ = release.send(teardown::tear_down_all(guards));
+                    }
+                }
+                Err(payload) => {
+                    let _ = value_sender.send(Err(payload));
+                }
+            }
+        })
+        .expect("Cannot spawn the fixture's owner thread");
+    let value = value
+        .recv()
+        .expect("The owner thread should send the fixture's value");
+    (value, owner)
+}
+
+/// Ask the `owner` thread to execute its teardowns and return their failures.
+pub(crate) fn release(owner: mpsc::Sender<Release>) -> Vec<String> {
+    let (release, failures) = mpsc::channel();
+    if owner.send(release).is_err() {
+        // The owner had nothing to tear down
+        return Vec::new();
+    }
+    failures.recv().unwrap_or_default()
+}
+
+/// The value shared in a `Group` as seen by one of its tests.
+pub struct Member<T: Send + Sync + 'static> {
+    group: &'static Group<T>,
+    fixture: &'static str,
+    value: Option<Arc<T>>,
+}
+
+impl<T: Send + Sync + 'static> Deref for Member<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_deref()
+            .unwrap_or_else(|| panic!("fixture `{}` is not initialized", self.fixture))
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for Member<T> {
+    fn drop(&mut self) {
+        // Release our reference before: the last test tears down the only one left
+        drop(self.value.take());
+        teardown::report(self.group.finish())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::teardown::{register, Scope};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn share_the_value_among_the_tests_of_the_group() {
+        static GROUP: Group<u32> = Group::new(3);
+        static INIT: AtomicUsize = AtomicUsize::new(0);
+        let init = || {
+            INIT.fetch_add(1, Ordering::SeqCst);
+            42
+        };
+
+        let first = GROUP.get("answer", init);
+        let second = std::thread::spawn(move || *GROUP.get("answer", init))
+            .join()
+            .unwrap();
+
+        assert_eq!(42, *first);
+        assert_eq!(42, second);
+        assert_eq!(1, INIT.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn tear_down_when_the_last_test_of_the_group_ends() {
+        static GROUP: Group<u32> = Group::new(2);
+        static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+        let init = || {
+            register("answer", || {
+                TORN_DOWN.fetch_add(1, Ordering::SeqCst);
+            });
+            42
+        };
+
+        let first = GROUP.get("answer", init);
+        drop(GROUP.get("answer", init));
+        assert_eq!(0, TORN_DOWN.load(Ordering::SeqCst));
+
+        drop(first);
+        assert_eq!(1, TORN_DOWN.load(Ordering::SeqCst));
+        assert!(GROUP.lock().value.is_none());
+    }
+
+    #[test]
+    fn not_tear_down_the_group_value_with_the_test_that_initialized_it() {
+        static GROUP: Group<u32> = Group::new(2);
+        static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+
+        {
+            let _scope = Scope::new();
+            let _member = GROUP.get("answer", || {
+                register("answer", || {
+                    TORN_DOWN.fetch_add(1, Ordering::SeqCst);
+                });
+                42
+            });
+        }
+
+        assert_eq!(0, TORN_DOWN.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn tear_down_not_send_guards_in_the_thread_that_registered_them() {
+        static GROUP: Group<u32> = Group::new(2);
+        static TORN_DOWN: Mutex<Option<std::thread::ThreadId>> = Mutex::new(None);
+        let init = || {
+            let not_send = std::rc::Rc::new(std::thread::current().id());
+            register("answer", move || {
+                *TORN_DOWN.lock().unwrap() = Some(*not_send);
+                assert_eq!(*not_send, std::thread::current().id());
+            });
+            42
+        };
+
+        let first = GROUP.get("answer", init);
+        std::thread::spawn(move || drop(GROUP.get("answer", init)))
+            .join()
+            .unwrap();
+        drop(first);
+
+        assert!(TORN_DOWN.lock().unwrap().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "teardown of fixture `answer` failed: cannot close")]
+    fn report_the_failures_of_the_group_teardowns() {
+        static GROUP: Group<u32> = Group::new(1);
+
+        drop(GROUP.get("answer", || {
+            register("answer", || panic!("cannot close"));
+            42
+        }));
+    }
+}
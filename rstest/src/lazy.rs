@@ -0,0 +1,182 @@
+//! Fixtures resolved on first use: see [`Lazy`], [`AsyncLazy`] and the `#[lazy]`
+//! attribute.
+
+use std::{
+    cell::{Cell, OnceCell},
+    fmt,
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+};
+
+type Init<'a, T> = Box<dyn FnOnce() -> T + 'a>;
+
+/// A fixture's value that is resolved just the first time it's dereferenced. Use
+/// it with the `#[lazy]` attribute. It's resolved by the test's thread, so the closure
+/// that resolves it can borrow the test's values and needn't be `Send`.
+pub struct Lazy<'a, T> {
+    value: OnceCell<T>,
+    init: Cell<Option<Init<'a, T>>>,
+}
+
+impl<'a, T> Lazy<'a, T> {
+    pub fn new(init: impl FnOnce() -> T + 'a) -> Self {
+        Self {
+            value: OnceCell::new(),
+            init: Cell::new(Some(Box::new(init))),
+        }
+    }
+
+    /// Whether the value is already resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<T> Deref for Lazy<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.get_or_init(|| {
+            let init = self.init.take().expect("Lazy fixture already resolving");
+            init()
+        })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<unresolved>)"),
+        }
+    }
+}
+
+type AsyncInit<'a, T> = Init<'a, Pin<Box<dyn Future<Output = T> + 'a>>>;
+
+/// Like [`Lazy`] but for async fixtures: the fixture is resolved and awaited just
+/// the first time [`AsyncLazy::get`] is awaited.
+pub struct AsyncLazy<'a, T> {
+    value: OnceCell<T>,
+    init: Cell<Option<AsyncInit<'a, T>>>,
+}
+
+impl<'a, T> AsyncLazy<'a, T> {
+    pub fn new<Fut: Future<Output = T> + 'a>(init: impl FnOnce() -> Fut + 'a) -> Self {
+        Self {
+            value: OnceCell::new(),
+            init: Cell::new(Some(Box::new(move || {
+                Box::pin(init()) as Pin<Box<dyn Future<Output = T> + 'a>>
+            }))),
+        }
+    }
+
+    /// Whether the value is already resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Resolve the fixture, if not already done, and get its value.
+    pub async fn get(&self) -> &T {
+        if let Some(value) = self.value.get() {
+            return value;
+        }
+        let init = self.init.take().expect("Lazy fixture already resolving");
+        let value = init().await;
+        self.value.get_or_init(|| value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AsyncLazy<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("AsyncLazy").field(value).finish(),
+            None => f.write_str("AsyncLazy(<unresolved>)"),
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Build a lazy value from the closure that resolves the fixture: `#[lazy]` arguments
+/// choose between [`Lazy`] and [`AsyncLazy`] by their type.
+pub trait FromInit<F> {
+    fn from_init(init: F) -> Self;
+}
+
+impl<'a, T, F: FnOnce() -> T + 'a> FromInit<F> for Lazy<'a, T> {
+    fn from_init(init: F) -> Self {
+        Self::new(init)
+    }
+}
+
+impl<'a, T, Fut, F> FromInit<F> for AsyncLazy<'a, T>
+where
+    Fut: Future<Output = T> + 'a,
+    F: FnOnce() -> Fut + 'a,
+{
+    fn from_init(init: F) -> Self {
+        Self::new(init)
+    }
+}
+
+#[doc(hidden)]
+pub fn lazy<L: FromInit<F>, F>(init: F) -> L {
+    L::from_init(init)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn resolve_the_value_just_once_when_used() {
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+        let value: Lazy<u32> = lazy(|| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            42
+        });
+
+        assert!(!value.is_resolved());
+        assert_eq!(0, CALLS.load(Ordering::SeqCst));
+
+        assert_eq!(42, *value);
+        assert_eq!(42, *value);
+        assert!(value.is_resolved());
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn never_resolve_the_value_if_not_used() {
+        let value: Lazy<u32> = lazy(|| panic!("should not be resolved"));
+
+        assert_eq!("Lazy(<unresolved>)", format!("{value:?}"));
+    }
+
+    #[test]
+    fn capture_values_that_are_not_send_nor_static() {
+        let shared = std::rc::Rc::new(40);
+        let borrowed = &shared;
+        let value: Lazy<u32> = lazy(move || **borrowed + 2);
+
+        assert_eq!(42, *value);
+    }
+
+    #[async_std::test]
+    async fn resolve_the_async_value_just_once_when_awaited() {
+        static CALLS: AtomicU32 = AtomicU32::new(0);
+        let value: AsyncLazy<u32> = lazy(|| async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            42
+        });
+
+        assert!(!value.is_resolved());
+
+        assert_eq!(42, *value.get().await);
+        assert_eq!(42, *value.get().await);
+        assert_eq!("AsyncLazy(42)", format!("{value:?}"));
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+}
@@ -234,10 +234,39 @@
 //!
 //! - **`async-timeout`** *(enabled by default)* — Implement timeout for async
 //! tests.
+//! - **`cases-csv`**, **`cases-json`** and **`cases-toml`** — Load `#[cases_from(...)]`
+//!   tables from CSV, JSON and TOML files.
+//! - **`build`** — The `rstest::build` helpers for your build script.
 
+#[doc(hidden)]
+pub mod autouse;
+#[cfg(feature = "build")]
+pub mod build;
+#[doc(hidden)]
+pub mod builder;
+#[doc(hidden)]
+pub mod concurrent;
+pub mod context;
+#[doc(hidden)]
+pub mod fallible;
+#[doc(hidden)]
+pub mod files;
+#[doc(hidden)]
+pub mod group;
+pub mod lazy;
 #[doc(hidden)]
 pub mod magic_conversion;
 #[doc(hidden)]
+pub mod once;
+#[doc(hidden)]
+pub mod profile;
+pub mod provide;
+pub mod teardown;
+#[doc(hidden)]
 pub mod timeout;
 
-pub use rstest_macros::{fixture, rstest};
+pub use context::Context;
+pub use lazy::{AsyncLazy, Lazy};
+pub use provide::Provide;
+pub use rstest_macros::{autouse, fixture, rstest, Fixtures};
+pub use teardown::TearDown;
@@ -0,0 +1,756 @@
+use std::{
+    any::{Any, TypeId},
+    future::Future,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    context::current_test,
+    group::{release, spawn_owner},
+    profile::once_hit,
+    teardown::{collect_async, panic_message, register_at_exit, tear_down_all, TearDown},
+};
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn leak<T>(value: T) -> &'static T {
+    Box::leak(Box::new(value))
+}
+
+/// Register the teardown of a `#[once]` fixture `value`: `teardown`'s guard is executed when
+/// the process exits. The value itself is never dropped because some threads (i.e. the ones
+/// of the tests that timed out) can still use it.
+fn tear_down_at_exit<T: Send + Sync + 'static, G: TearDown + Send + 'static>(
+    fixture: &'static str,
+    value: &'static T,
+    teardown: impl FnOnce(&'static T) -> G,
+) {
+    register_at_exit(fixture, teardown(value));
+}
+
+/// Resolve a `#[once]` fixture and its dependencies by `resolve` in an owner thread that
+/// keeps the teardowns of the dependencies till the process exits: the value can use them
+/// in every test. They are executed by the thread that registered them, so they don't
+/// need to be `Send`.
+fn resolve_by_owner<T: Sync + 'static>(
+    fixture: &'static str,
+    resolve: impl FnOnce() -> T + Send + 'static,
+) -> &'static T {
+    let (value, owner) = spawn_owner(format!("rstest once {fixture}"), move || leak(resolve()));
+    let value = value.unwrap_or_else(|payload| resume_unwind(payload));
+    register_at_exit(fixture, move || {
+        for failure in release(owner) {
+            eprintln!("{failure}");
+        }
+    });
+    value
+}
+
+/// Resolve an async `#[once]` fixture and its dependencies by `resolve`: their teardowns
+/// cannot outlive the test that resolves them because they are not `Send`, so the
+/// dependencies of an async `#[once]` fixture cannot have teardowns.
+async fn resolve_without_teardowns<T>(
+    fixture: &'static str,
+    resolve: impl Future<Output = T>,
+) -> T {
+    let (value, registered) = collect_async(resolve).await;
+    if registered.is_empty() {
+        return value;
+    }
+    let dependencies = registered
+        .iter()
+        .map(|r| format!("`{}`", r.fixture))
+        .collect::<Vec<_>>()
+        .join(", ");
+    for failure in tear_down_all(registered) {
+        eprintln!("{failure}");
+    }
+    panic!(
+        "async #[once] fixture `{fixture}` cannot keep the teardowns of its dependencies \
+        till the process exits: remove the teardowns of {dependencies} or make `{fixture}` sync"
+    )
+}
+
+/// The panic of a `#[once]` fixture's initialization: the test that triggered it
+/// fails with the original panic and all the others that use the fixture report it.
+#[derive(Clone)]
+struct Failure {
+    test: String,
+    message: String,
+}
+
+impl Failure {
+    fn new(payload: &(dyn Any + Send)) -> Self {
+        Self {
+            test: current_test(),
+            message: panic_message(payload).to_owned(),
+        }
+    }
+
+    fn raise(self, fixture: &str) -> ! {
+        panic!(
+            "fixture `{fixture}` failed to initialize in test `{}`: {}",
+            self.test, self.message
+        )
+    }
+}
+
+enum Slot<T: 'static> {
+    Empty,
+    Ready(&'static T),
+    Failed(Failure),
+}
+
+/// The cell behind sync `#[once]` fixtures.
+pub struct SyncOnce<T: 'static> {
+    slot: Mutex<Slot<T>>,
+}
+
+impl<T: 'static> Default for SyncOnce<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> SyncOnce<T> {
+    pub const fn new() -> Self {
+        Self {
+            slot: Mutex::new(Slot::Empty),
+        }
+    }
+
+    pub fn get_or_init<F: FnOnce() -> T>(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+    ) -> &'static T {
+        self.get_or_init_and_then(fixture, || leak(init()), |_| ())
+    }
+
+    fn get_or_init_and_then(
+        &'static self,
+        fixture: &'static str,
+        init: impl FnOnce() -> &'static T,
+        on_init: impl FnOnce(&'static T),
+    ) -> &'static T {
+        let mut slot = lock(&self.slot);
+        match &*slot {
+            Slot::Ready(value) => {
+                once_hit(fixture);
+                return value;
+            }
+            Slot::Failed(failure) => {
+                let failure = failure.clone();
+                drop(slot);
+                failure.raise(fixture)
+            }
+            Slot::Empty => {}
+        }
+        match catch_unwind(AssertUnwindSafe(init)) {
+            Ok(value) => {
+                *slot = Slot::Ready(value);
+                on_init(value);
+                value
+            }
+            Err(payload) => {
+                *slot = Slot::Failed(Failure::new(payload.as_ref()));
+                drop(slot);
+                resume_unwind(payload)
+            }
+        }
+    }
+}
+
+impl<T: Sync + 'static> SyncOnce<T> {
+    /// Like `get_or_init()` but `resolve` resolves the fixture's dependencies too: see
+    /// `resolve_by_owner()`.
+    pub fn get_or_resolve<F: FnOnce() -> T + Send + 'static>(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+    ) -> &'static T {
+        self.get_or_init_and_then(fixture, || resolve_by_owner(fixture, resolve), |_| ())
+    }
+}
+
+impl<T: Send + Sync + 'static> SyncOnce<T> {
+    /// Like `get_or_init()` but the guard returned by `teardown` is executed when the
+    /// process exits.
+    pub fn get_or_init_with_teardown<
+        F: FnOnce() -> T,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+        teardown: D,
+    ) -> &'static T {
+        self.get_or_init_and_then(
+            fixture,
+            || leak(init()),
+            |value| tear_down_at_exit(fixture, value, teardown),
+        )
+    }
+
+    /// Like `get_or_resolve()` but the guard returned by `teardown` is executed when the
+    /// process exits, before the teardowns of the dependencies.
+    pub fn get_or_resolve_with_teardown<
+        F: FnOnce() -> T + Send + 'static,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+        teardown: D,
+    ) -> &'static T {
+        self.get_or_init_and_then(
+            fixture,
+            || resolve_by_owner(fixture, resolve),
+            |value| tear_down_at_exit(fixture, value, teardown),
+        )
+    }
+}
+
+enum State<T: 'static> {
+    Empty,
+    Initializing(Vec<Waker>),
+    Ready(&'static T),
+    Failed(Failure),
+}
+
+/// The cell behind async `#[once]` fixtures: the first task that asks for the value
+/// runs the init future and all the others wait for it without relying on any
+/// specific runtime, so tests that use different executors share the same value.
+///
+/// If the init future is dropped before completing (the task was cancelled) the next
+/// waiting task will try to initialize the value again.
+pub struct AsyncOnce<T: 'static> {
+    state: Mutex<State<T>>,
+}
+
+impl<T: 'static> Default for AsyncOnce<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> AsyncOnce<T> {
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(State::Empty),
+        }
+    }
+
+    pub async fn get_or_init<F: Future<Output = T>>(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+    ) -> &'static T {
+        self.get_or_init_and_then(fixture, init, |_| ()).await
+    }
+
+    /// Like `get_or_init()` but `resolve` resolves the fixture's dependencies too: see
+    /// `resolve_without_teardowns()`.
+    pub async fn get_or_resolve<F: Future<Output = T>>(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+    ) -> &'static T {
+        self.get_or_init(fixture, resolve_without_teardowns(fixture, resolve))
+            .await
+    }
+
+    async fn get_or_init_and_then(
+        &'static self,
+        fixture: &'static str,
+        init: impl Future<Output = T>,
+        on_init: impl FnOnce(&'static T),
+    ) -> &'static T {
+        match (Acquire { once: self }).await {
+            Acquired::Ready(value) => {
+                once_hit(fixture);
+                value
+            }
+            Acquired::Failed(failure) => failure.raise(fixture),
+            Acquired::Init(guard) => match (CatchUnwind(Box::pin(init))).await {
+                Ok(value) => {
+                    let value = guard.complete(value);
+                    on_init(value);
+                    value
+                }
+                Err(payload) => {
+                    guard.fail(Failure::new(payload.as_ref()));
+                    resume_unwind(payload)
+                }
+            },
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        lock(&self.state)
+    }
+}
+
+impl<T: Send + Sync + 'static> AsyncOnce<T> {
+    /// Like `get_or_init()` but the guard returned by `teardown` is executed when the
+    /// process exits.
+    pub async fn get_or_init_with_teardown<
+        F: Future<Output = T>,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+        teardown: D,
+    ) -> &'static T {
+        self.get_or_init_and_then(fixture, init, |value| {
+            tear_down_at_exit(fixture, value, teardown)
+        })
+        .await
+    }
+
+    /// Like `get_or_resolve()` but the guard returned by `teardown` is executed when the
+    /// process exits.
+    pub async fn get_or_resolve_with_teardown<
+        F: Future<Output = T>,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+        teardown: D,
+    ) -> &'static T {
+        self.get_or_init_with_teardown(
+            fixture,
+            resolve_without_teardowns(fixture, resolve),
+            teardown,
+        )
+        .await
+    }
+}
+
+enum Acquired<T: 'static> {
+    Ready(&'static T),
+    Init(InitGuard<T>),
+    Failed(Failure),
+}
+
+struct Acquire<T: 'static> {
+    once: &'static AsyncOnce<T>,
+}
+
+impl<T: 'static> Future for Acquire<T> {
+    type Output = Acquired<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.once.lock();
+        match &mut *state {
+            State::Ready(value) => Poll::Ready(Acquired::Ready(*value)),
+            State::Failed(failure) => Poll::Ready(Acquired::Failed(failure.clone())),
+            State::Empty => {
+                *state = State::Initializing(Vec::new());
+                Poll::Ready(Acquired::Init(InitGuard { once: self.once }))
+            }
+            State::Initializing(wakers) => {
+                if !wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct InitGuard<T: 'static> {
+    once: &'static AsyncOnce<T>,
+}
+
+impl<T: 'static> InitGuard<T> {
+    fn complete(self, value: T) -> &'static T {
+        let value = leak(value);
+        self.release(State::Ready(value));
+        std::mem::forget(self);
+        value
+    }
+
+    fn fail(self, failure: Failure) {
+        self.release(State::Failed(failure));
+        std::mem::forget(self);
+    }
+
+    fn release(&self, new_state: State<T>) {
+        let old = std::mem::replace(&mut *self.once.lock(), new_state);
+        if let State::Initializing(wakers) = old {
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+}
+
+impl<T: 'static> Drop for InitGuard<T> {
+    fn drop(&mut self) {
+        self.release(State::Empty)
+    }
+}
+
+struct CatchUnwind<F: Future>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = self.0.as_mut();
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(value)) => Poll::Ready(Ok(value)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+/// Generic `#[once]` fixtures can't store their value in a `static` because statics
+/// cannot depend on the function's generic types. `OnceMap` stores a cell for every
+/// `K` key (the tuple of the fixture's generic types) so every concrete type gets
+/// its own value.
+pub struct OnceMap {
+    cells: Mutex<Vec<(TypeId, &'static (dyn Any + Send + Sync))>>,
+}
+
+impl Default for OnceMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnceMap {
+    pub const fn new() -> Self {
+        Self {
+            cells: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn get_or_init<K: 'static, T: Send + Sync + 'static, F: FnOnce() -> T>(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+    ) -> &'static T {
+        self.cell::<K, SyncOnce<T>>().get_or_init(fixture, init)
+    }
+
+    pub fn get_or_init_with_teardown<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+        teardown: D,
+    ) -> &'static T {
+        self.cell::<K, SyncOnce<T>>()
+            .get_or_init_with_teardown(fixture, init, teardown)
+    }
+
+    pub fn get_or_resolve<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+    ) -> &'static T {
+        self.cell::<K, SyncOnce<T>>()
+            .get_or_resolve(fixture, resolve)
+    }
+
+    pub fn get_or_resolve_with_teardown<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T + Send + 'static,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+        teardown: D,
+    ) -> &'static T {
+        self.cell::<K, SyncOnce<T>>()
+            .get_or_resolve_with_teardown(fixture, resolve, teardown)
+    }
+
+    pub async fn get_or_init_async<K: 'static, T: Send + Sync + 'static, F: Future<Output = T>>(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+    ) -> &'static T {
+        self.cell::<K, AsyncOnce<T>>()
+            .get_or_init(fixture, init)
+            .await
+    }
+
+    pub async fn get_or_init_async_with_teardown<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: Future<Output = T>,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        init: F,
+        teardown: D,
+    ) -> &'static T {
+        self.cell::<K, AsyncOnce<T>>()
+            .get_or_init_with_teardown(fixture, init, teardown)
+            .await
+    }
+
+    pub async fn get_or_resolve_async<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: Future<Output = T>,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+    ) -> &'static T {
+        self.cell::<K, AsyncOnce<T>>()
+            .get_or_resolve(fixture, resolve)
+            .await
+    }
+
+    pub async fn get_or_resolve_async_with_teardown<
+        K: 'static,
+        T: Send + Sync + 'static,
+        F: Future<Output = T>,
+        D: FnOnce(&'static T) -> G,
+        G: TearDown + Send + 'static,
+    >(
+        &'static self,
+        fixture: &'static str,
+        resolve: F,
+        teardown: D,
+    ) -> &'static T {
+        self.cell::<K, AsyncOnce<T>>()
+            .get_or_resolve_with_teardown(fixture, resolve, teardown)
+            .await
+    }
+
+    fn cell<K: 'static, C: Default + Send + Sync + 'static>(&'static self) -> &'static C {
+        let key = TypeId::of::<K>();
+        let mut cells = lock(&self.cells);
+        let cell = match cells.iter().find(|(k, _)| *k == key) {
+            Some((_, cell)) => *cell,
+            None => {
+                let cell: &'static C = Box::leak(Box::default());
+                cells.push((key, cell));
+                cell
+            }
+        };
+        cell.downcast_ref()
+            .expect("Once map cells should be indexed by their types")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::teardown::{register, test::registered_at_exit, Scope};
+    use async_std::task::block_on;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn initialize_the_value_just_once() {
+        static ONCE: AsyncOnce<u32> = AsyncOnce::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let init = || async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            42
+        };
+
+        let first = block_on(ONCE.get_or_init("fixture", init()));
+        let second = block_on(ONCE.get_or_init("fixture", init()));
+
+        assert_eq!(42, *first);
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn share_the_value_between_threads_and_runtimes() {
+        static ONCE: AsyncOnce<u32> = AsyncOnce::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let init = || async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            async_std::task::sleep(std::time::Duration::from_millis(50)).await;
+            42
+        };
+
+        let threads = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || match i % 2 {
+                    0 => block_on(ONCE.get_or_init("fixture", init())),
+                    _ => tokio::runtime::Builder::new_current_thread()
+                        .build()
+                        .unwrap()
+                        .block_on(ONCE.get_or_init("fixture", init())),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for t in threads {
+            assert_eq!(42, *t.join().unwrap());
+        }
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    fn panic_message_of<R>(f: impl FnOnce() -> R) -> String {
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(f))
+            .err()
+            .expect("Should panic");
+        panic_message(payload.as_ref()).to_owned()
+    }
+
+    #[test]
+    fn sync_once_should_report_the_first_init_failure() {
+        static ONCE: SyncOnce<u32> = SyncOnce::new();
+        let _test = crate::context::test("my_crate::module::case_1").enter();
+
+        let first = panic_message_of(|| ONCE.get_or_init("db", || panic!("cannot connect")));
+        let second = panic_message_of(|| ONCE.get_or_init("db", || 42));
+
+        assert_eq!("cannot connect", first);
+        assert_eq!(
+            "fixture `db` failed to initialize in test `module::case_1`: cannot connect",
+            second
+        );
+    }
+
+    #[test]
+    fn async_once_should_report_the_first_init_failure() {
+        static ONCE: AsyncOnce<u32> = AsyncOnce::new();
+        let _test = crate::context::test("my_crate::module::case_1").enter();
+
+        let first = panic_message_of(|| {
+            block_on(ONCE.get_or_init("db", async { panic!("cannot connect") }))
+        });
+        let second = panic_message_of(|| block_on(ONCE.get_or_init("db", async { 42 })));
+
+        assert_eq!("cannot connect", first);
+        assert_eq!(
+            "fixture `db` failed to initialize in test `module::case_1`: cannot connect",
+            second
+        );
+    }
+
+    #[test]
+    fn async_once_should_initialize_again_if_init_is_cancelled() {
+        static ONCE: AsyncOnce<u32> = AsyncOnce::new();
+
+        let cancelled = async_std::future::timeout(
+            std::time::Duration::from_millis(10),
+            ONCE.get_or_init("fixture", async_std::future::pending()),
+        );
+
+        assert!(block_on(cancelled).is_err());
+        assert_eq!(42, *block_on(ONCE.get_or_init("fixture", async { 42 })));
+    }
+
+    #[test]
+    fn register_teardown_at_exit_just_once() {
+        static SYNC: SyncOnce<u32> = SyncOnce::new();
+        static ASYNC: AsyncOnce<u32> = AsyncOnce::new();
+
+        for _ in 0..3 {
+            SYNC.get_or_init_with_teardown("sync_once_fixture", || 42, |_| || ());
+            block_on(
+                ASYNC.get_or_init_with_teardown("async_once_fixture", async { 42 }, |_| || ()),
+            );
+        }
+
+        assert_eq!(1, registered_at_exit("sync_once_fixture"));
+        assert_eq!(1, registered_at_exit("async_once_fixture"));
+    }
+
+    #[test]
+    fn keep_the_teardowns_of_the_dependencies_till_exit() {
+        static ONCE: SyncOnce<u32> = SyncOnce::new();
+        static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+        let resolve = || {
+            let not_send = std::rc::Rc::new(());
+            register("dependency", move || {
+                drop(not_send);
+                TORN_DOWN.fetch_add(1, Ordering::SeqCst);
+            });
+            42
+        };
+
+        {
+            let _scope = Scope::new();
+            assert_eq!(42, *ONCE.get_or_resolve("resolved_once_fixture", resolve));
+            assert_eq!(42, *ONCE.get_or_resolve("resolved_once_fixture", resolve));
+        }
+
+        assert_eq!(0, TORN_DOWN.load(Ordering::SeqCst));
+        assert_eq!(1, registered_at_exit("resolved_once_fixture"));
+    }
+
+    #[test]
+    fn async_once_should_reject_dependencies_with_teardowns() {
+        static ONCE: AsyncOnce<u32> = AsyncOnce::new();
+        static TORN_DOWN: AtomicUsize = AtomicUsize::new(0);
+
+        let message = panic_message_of(|| {
+            block_on(ONCE.get_or_resolve("server", async {
+                register("dir", || {
+                    TORN_DOWN.fetch_add(1, Ordering::SeqCst);
+                });
+                42
+            }))
+        });
+
+        assert_eq!(
+            "async #[once] fixture `server` cannot keep the teardowns of its dependencies \
+            till the process exits: remove the teardowns of `dir` or make `server` sync",
+            message
+        );
+        assert_eq!(1, TORN_DOWN.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn once_map_should_store_a_value_for_each_type() {
+        static MAP: OnceMap = OnceMap::new();
+
+        let a = MAP.get_or_init::<(u32,), _, _>("fixture", || "u32".to_string());
+        let b = MAP.get_or_init::<(i32,), _, _>("fixture", || "i32".to_string());
+        let c = MAP.get_or_init::<(u32,), _, _>("fixture", || "again".to_string());
+
+        assert_eq!("u32", a);
+        assert_eq!("i32", b);
+        assert!(std::ptr::eq(a, c));
+    }
+
+    #[test]
+    fn once_map_should_resolve_async_values_for_each_type() {
+        static MAP: OnceMap = OnceMap::new();
+
+        let a = block_on(MAP.get_or_init_async::<(u32,), _, _>("fixture", async { 1 }));
+        let b = block_on(MAP.get_or_init_async::<(i32,), _, _>("fixture", async { 2 }));
+        let c = block_on(MAP.get_or_init_async::<(u32,), _, _>("fixture", async { 3 }));
+
+        assert_eq!((1, 2), (*a, *b));
+        assert!(std::ptr::eq(a, c));
+    }
+}
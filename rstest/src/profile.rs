@@ -0,0 +1,312 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Mutex, MutexGuard, Once, OnceLock, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use crate::{context::current_test, teardown::at_exit};
+
+/// Set this environment variable (to anything but `0`) to profile the fixtures' setup.
+pub const PROFILE_ENV: &str = "RSTEST_PROFILE";
+
+fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        std::env::var(PROFILE_ENV)
+            .map(|v| !v.is_empty() && v != "0")
+            .unwrap_or(false)
+    })
+}
+
+thread_local! {
+    /// The time spent resolving the dependencies of each fixture that is being resolved in
+    /// the current thread, the innermost last.
+    static DEPENDENCIES: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The resolution of a fixture in the current thread: the fixtures resolved till it's
+/// exited are its dependencies.
+struct Frame(());
+
+impl Frame {
+    fn enter() -> Self {
+        DEPENDENCIES.with(|d| d.borrow_mut().push(Duration::ZERO));
+        Self(())
+    }
+
+    /// The time spent resolving the dependencies.
+    fn exit(self) -> Duration {
+        std::mem::forget(self);
+        Self::pop()
+    }
+
+    fn pop() -> Duration {
+        DEPENDENCIES
+            .with(|d| d.borrow_mut().pop())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        Self::pop();
+    }
+}
+
+/// The fixture's self time: its `elapsed` time is added to the fixture that depends on
+/// it, if any, and the time spent by its `dependencies` is subtracted.
+fn self_time(elapsed: Duration, dependencies: Duration) -> Duration {
+    DEPENDENCIES.with(|d| {
+        if let Some(parent) = d.borrow_mut().last_mut() {
+            *parent += elapsed;
+        }
+    });
+    elapsed.saturating_sub(dependencies)
+}
+
+fn self_timed<T>(resolve: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let frame = Frame::enter();
+    let value = resolve();
+    let dependencies = frame.exit();
+    (value, self_time(start.elapsed(), dependencies))
+}
+
+async fn self_timed_async<F: Future>(resolve: F) -> (F::Output, Duration) {
+    let start = Instant::now();
+    let (value, dependencies) = Resolving {
+        future: Box::pin(resolve),
+        dependencies: Duration::ZERO,
+    }
+    .await;
+    (value, self_time(start.elapsed(), dependencies))
+}
+
+/// An async fixture's resolution: every poll is a `Frame`, whatever thread polls it, and
+/// the time spent by the dependencies in each of them is summed up.
+struct Resolving<F: Future> {
+    future: Pin<Box<F>>,
+    dependencies: Duration,
+}
+
+impl<F: Future> Future for Resolving<F> {
+    type Output = (F::Output, Duration);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let frame = Frame::enter();
+        let poll = self.future.as_mut().poll(cx);
+        self.dependencies += frame.exit();
+        poll.map(|value| (value, self.dependencies))
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+struct Stats {
+    calls: u32,
+    once_hits: u32,
+    total: Duration,
+    max: Duration,
+    slowest_test: String,
+}
+
+impl Stats {
+    fn mean(&self) -> Duration {
+        self.total.checked_div(self.calls).unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct Profile {
+    fixtures: BTreeMap<&'static str, Stats>,
+}
+
+impl Profile {
+    const fn new() -> Self {
+        Self {
+            fixtures: BTreeMap::new(),
+        }
+    }
+
+    fn record(&mut self, fixture: &'static str, test: String, elapsed: Duration) {
+        let stats = self.fixtures.entry(fixture).or_default();
+        stats.calls += 1;
+        stats.total += elapsed;
+        if stats.slowest_test.is_empty() || elapsed > stats.max {
+            stats.max = elapsed;
+            stats.slowest_test = test;
+        }
+    }
+
+    fn once_hit(&mut self, fixture: &'static str) {
+        self.fixtures.entry(fixture).or_default().once_hits += 1;
+    }
+
+    /// The fixtures' stats sorted by the total setup time, the slowest first.
+    fn report(&self) -> String {
+        let mut fixtures = self.fixtures.iter().collect::<Vec<_>>();
+        fixtures.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.total));
+        let mut report = format!(
+            "{:<30} {:>8} {:>10} {:>14} {:>14}  slowest test\n",
+            "fixture", "calls", "once hits", "self total", "self mean"
+        );
+        for (fixture, stats) in fixtures {
+            report.push_str(&format!(
+                "{:<30} {:>8} {:>10} {:>14} {:>14}  {}\n",
+                fixture,
+                stats.calls,
+                stats.once_hits,
+                format!("{:?}", stats.total),
+                format!("{:?}", stats.mean()),
+                stats.slowest_test
+            ));
+        }
+        report
+    }
+}
+
+static PROFILE: Mutex<Profile> = Mutex::new(Profile::new());
+
+fn profile() -> MutexGuard<'static, Profile> {
+    static INSTALL: Once = Once::new();
+    // `write_report` catches all panics
+    INSTALL.call_once(|| at_exit(write_report));
+    PROFILE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The report is written in `rstest-profile/<test binary name>.txt` in the target's profile
+/// directory (i.e. `target/debug`): test binaries live in its `deps` subdirectory.
+fn report_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let dir = exe.parent()?.parent()?.join("rstest-profile");
+    Some(dir.join(exe.file_stem()?).with_extension("txt"))
+}
+
+extern "C" fn write_report() {
+    let _ = std::panic::catch_unwind(|| {
+        let report = PROFILE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .report();
+        let written = report_path().and_then(|path| {
+            std::fs::create_dir_all(path.parent()?).ok()?;
+            std::fs::write(&path, report).ok()?;
+            Some(path)
+        });
+        match written {
+            Some(path) => eprintln!("rstest profile report written to {}", path.display()),
+            None => eprintln!("rstest cannot write the profile report"),
+        }
+    });
+}
+
+/// Resolve the given `fixture` by `resolve` and, if `RSTEST_PROFILE` is set, record
+/// how long it took: the time spent resolving its dependencies is not counted.
+pub fn fixture<T>(fixture: &'static str, resolve: impl FnOnce() -> T) -> T {
+    if !enabled() {
+        return resolve();
+    }
+    let (value, elapsed) = self_timed(resolve);
+    profile().record(fixture, current_test(), elapsed);
+    value
+}
+
+/// Like `fixture()` but for async fixtures. The dependencies awaited concurrently
+/// (`#[concurrent]`) overlap: their time can exceed the fixture's one and is not
+/// counted anyway.
+pub async fn fixture_async<F: Future>(fixture: &'static str, resolve: F) -> F::Output {
+    if !enabled() {
+        return resolve.await;
+    }
+    let (value, elapsed) = self_timed_async(resolve).await;
+    profile().record(fixture, current_test(), elapsed);
+    value
+}
+
+/// Record that a `#[once]` fixture gave its value without resolving it again.
+pub(crate) fn once_hit(fixture: &'static str) {
+    if enabled() {
+        profile().once_hit(fixture)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn aggregate_the_calls_of_each_fixture() {
+        let mut profile = Profile::new();
+
+        profile.record("db", "first".to_owned(), ms(10));
+        profile.record("db", "second".to_owned(), ms(30));
+        profile.record("user", "first".to_owned(), ms(1));
+        profile.once_hit("user");
+
+        assert_eq!(
+            Stats {
+                calls: 2,
+                once_hits: 0,
+                total: ms(40),
+                max: ms(30),
+                slowest_test: "second".to_owned()
+            },
+            profile.fixtures["db"]
+        );
+        assert_eq!(ms(20), profile.fixtures["db"].mean());
+        assert_eq!(1, profile.fixtures["user"].once_hits);
+    }
+
+    #[test]
+    fn report_the_slowest_fixtures_first() {
+        let mut profile = Profile::new();
+
+        profile.record("fast", "a_test".to_owned(), ms(1));
+        profile.record("slow", "other_test".to_owned(), ms(100));
+
+        let report = profile.report();
+        let lines = report.lines().collect::<Vec<_>>();
+
+        assert_eq!(3, lines.len());
+        assert!(lines[0].starts_with("fixture"));
+        assert!(lines[1].starts_with("slow"));
+        assert!(lines[1].ends_with("other_test"));
+        assert!(lines[2].starts_with("fast"));
+    }
+
+    #[test]
+    fn not_count_the_dependencies_time() {
+        let (inner, outer) = self_timed(|| {
+            std::thread::sleep(ms(20));
+            self_timed(|| std::thread::sleep(ms(60))).1
+        });
+
+        assert!(inner >= ms(60));
+        assert!(outer >= ms(20) && outer < ms(60), "{:?}", outer);
+    }
+
+    #[async_std::test]
+    async fn not_count_the_dependencies_time_of_async_fixtures() {
+        let (inner, outer) = self_timed_async(async {
+            async_std::task::sleep(ms(20)).await;
+            self_timed_async(async_std::task::sleep(ms(60))).await.1
+        })
+        .await;
+
+        assert!(inner >= ms(60));
+        assert!(outer >= ms(20) && outer < ms(60), "{:?}", outer);
+    }
+
+    #[test]
+    fn mean_of_no_calls_is_zero() {
+        assert_eq!(Duration::ZERO, Stats::default().mean());
+    }
+}
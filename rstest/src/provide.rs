@@ -0,0 +1,19 @@
+//! Fixtures injected by type: see [`Provide`] and the `#[by_type]` attribute.
+
+/// A type that a fixture can provide: arguments annotated by `#[by_type]` are resolved
+/// by their type's `Provide` implementation, whatever their name is.
+///
+/// Don't implement it by hand: use `#[fixture(provides)]` on the fixture that builds
+/// the type. The type should be defined in your crate (Rust's orphan rule): wrap the
+/// types of other crates, `std` included, in a newtype.
+#[cfg_attr(
+    diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        message = "no fixture provides `{Self}`",
+        label = "`{Self}` cannot be resolved by type",
+        note = "add `#[fixture(provides)]` to the fixture that returns `{Self}`"
+    )
+)]
+pub trait Provide {
+    fn provide() -> Self;
+}
@@ -0,0 +1,386 @@
+//! How fixtures release their resources: see [`TearDown`] and the `#[teardown(...)]`
+//! attribute.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Mutex, Once, PoisonError},
+    task::{Context, Poll},
+};
+
+/// How to release the resources of a fixture: the `#[teardown(expression)]` attribute's
+/// expression should implement it. Any `FnOnce()` closure is a `TearDown`.
+pub trait TearDown {
+    fn tear_down(self);
+}
+
+/// The `TearDown` that does nothing.
+#[derive(Default)]
+pub struct EmptyGuard {}
+
+impl TearDown for EmptyGuard {
+    fn tear_down(self) {}
+}
+
+impl<F: FnOnce()> TearDown for F {
+    fn tear_down(self) {
+        self()
+    }
+}
+
+impl<A: TearDown, B: TearDown> TearDown for (A, B) {
+    fn tear_down(self) {
+        self.0.tear_down();
+        self.1.tear_down();
+    }
+}
+
+pub(crate) struct Registered<G: ?Sized = dyn FnOnce()> {
+    pub(crate) fixture: &'static str,
+    guard: Box<G>,
+}
+
+impl From<Registered<dyn FnOnce() + Send>> for Registered {
+    fn from(Registered { fixture, guard }: Registered<dyn FnOnce() + Send>) -> Self {
+        Self { fixture, guard }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    scopes: Vec<Vec<Registered>>,
+    orphans: Vec<Registered>,
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        // Fixtures resolved outside any test (i.e. by calling `default()` by hand)
+        // are torn down when the thread exits.
+        for failure in tear_down_all(std::mem::take(&mut self.orphans)) {
+            eprintln!("{failure}");
+        }
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+#[doc(hidden)]
+/// Register the `guard` of the given `fixture` in the innermost active `Scope`.
+pub fn register<G: TearDown + 'static>(fixture: &'static str, guard: G) {
+    let registered: Registered = Registered {
+        fixture,
+        guard: Box::new(move || guard.tear_down()),
+    };
+    REGISTRY.with(|r| {
+        let mut r = r.borrow_mut();
+        match r.scopes.last_mut() {
+            Some(scope) => scope.push(registered),
+            None => r.orphans.push(registered),
+        }
+    });
+}
+
+/// Execute `init` and collect the teardowns registered meanwhile instead of adding
+/// them to the current `Scope`. If `init` panics they are executed immediately.
+pub(crate) fn collect<T>(init: impl FnOnce() -> T) -> (T, Vec<Registered>) {
+    let scope = Scope::new();
+    let value = init();
+    (value, scope.take())
+}
+
+/// Like [`collect()`] for the teardowns registered while `future` is polled, whatever
+/// thread polls it. If the future panics or it's dropped they are executed immediately.
+pub(crate) fn collect_async<F: Future>(future: F) -> Collect<F> {
+    Collect {
+        registered: Vec::new(),
+        future: Box::pin(future),
+    }
+}
+
+/// The future returned by [`collect_async()`].
+pub(crate) struct Collect<F: Future> {
+    registered: Vec<Registered>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Collect<F> {
+    type Output = (F::Output, Vec<Registered>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let scope = Scope::new();
+        let poll = this.future.as_mut().poll(cx);
+        this.registered.extend(scope.take());
+        poll.map(|value| (value, std::mem::take(&mut this.registered)))
+    }
+}
+
+impl<F: Future> Drop for Collect<F> {
+    fn drop(&mut self) {
+        report(tear_down_all(std::mem::take(&mut self.registered)))
+    }
+}
+
+static AT_EXIT: Mutex<Vec<Registered<dyn FnOnce() + Send>>> = Mutex::new(Vec::new());
+
+/// Execute `callback` when the process exits. `callback` should never unwind.
+pub(crate) fn at_exit(callback: extern "C" fn()) {
+    #[cfg(any(unix, windows))]
+    // SAFETY: `callback` never unwinds across the FFI boundary
+    unsafe {
+        libc::atexit(callback);
+    }
+    // Other targets cannot tear down anything at exit
+    #[cfg(not(any(unix, windows)))]
+    let _ = callback;
+}
+
+extern "C" fn tear_down_at_exit() {
+    let registered = std::mem::take(&mut *AT_EXIT.lock().unwrap_or_else(PoisonError::into_inner));
+    for failure in tear_down_all(registered.into_iter().map(Into::into).collect()) {
+        eprintln!("{failure}");
+    }
+}
+
+#[doc(hidden)]
+/// Register the `guard` of the given `#[once]` fixture: all these guards are executed
+/// in reverse registration order when the process exits, after all tests are done.
+/// Failures can just be printed on stderr.
+pub fn register_at_exit<G: TearDown + Send + 'static>(fixture: &'static str, guard: G) {
+    static INSTALL: Once = Once::new();
+    // `tear_down_at_exit` catches all panics
+    INSTALL.call_once(|| at_exit(tear_down_at_exit));
+    AT_EXIT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Registered {
+            fixture,
+            guard: Box::new(move || guard.tear_down()),
+        });
+}
+
+#[doc(hidden)]
+/// Collect all teardowns registered while it's alive and execute them in reverse
+/// registration order when dropped: fixtures are always resolved after their
+/// dependencies, so they are torn down before them.
+///
+/// A failing teardown doesn't stop the others. If the test already failed
+/// the teardown failures are just printed, otherwise they make the test fail.
+pub struct Scope(());
+
+impl Scope {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        REGISTRY.with(|r| r.borrow_mut().scopes.push(Vec::new()));
+        Self(())
+    }
+
+    fn pop() -> Vec<Registered> {
+        REGISTRY
+            .with(|r| r.borrow_mut().scopes.pop())
+            .unwrap_or_default()
+    }
+
+    /// Close the scope without executing its teardowns.
+    fn take(self) -> Vec<Registered> {
+        std::mem::forget(self);
+        Self::pop()
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        report(tear_down_all(Self::pop()))
+    }
+}
+
+#[doc(hidden)]
+/// Execute the async test's `future` in its own `Scope`: the teardowns registered while
+/// the future is polled are collected in the future itself, whatever thread polls it,
+/// and executed when it completes (or when it's dropped).
+pub fn scoped<F: Future>(future: F) -> ScopedFuture<F> {
+    ScopedFuture {
+        registered: Vec::new(),
+        future: Box::pin(future),
+    }
+}
+
+#[doc(hidden)]
+/// The future returned by [`scoped()`].
+pub struct ScopedFuture<F: Future> {
+    registered: Vec<Registered>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for ScopedFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        // If the future panics the scope tears down the just registered teardowns and
+        // then `drop()` tears down the older ones
+        let scope = Scope::new();
+        let poll = this.future.as_mut().poll(cx);
+        this.registered.extend(scope.take());
+        if poll.is_ready() {
+            report(tear_down_all(std::mem::take(&mut this.registered)));
+        }
+        poll
+    }
+}
+
+impl<F: Future> Drop for ScopedFuture<F> {
+    fn drop(&mut self) {
+        report(tear_down_all(std::mem::take(&mut self.registered)))
+    }
+}
+
+/// Make the current test fail if some teardown failed, or just print the failures
+/// if it's already failing.
+pub(crate) fn report(failures: Vec<String>) {
+    if failures.is_empty() {
+        return;
+    }
+    let message = failures.join("\n");
+    if std::thread::panicking() {
+        eprintln!("{message}");
+    } else {
+        panic!("{message}");
+    }
+}
+
+pub(crate) fn tear_down_all(registered: Vec<Registered>) -> Vec<String> {
+    registered
+        .into_iter()
+        .rev()
+        .filter_map(|Registered { fixture, guard }| {
+            catch_unwind(AssertUnwindSafe(guard)).err().map(|e| {
+                format!(
+                    "teardown of fixture `{fixture}` failed: {}",
+                    panic_message(e.as_ref())
+                )
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn tear_down_in_reverse_registration_order() {
+        let log = Arc::new(Mutex::new(vec![]));
+        {
+            let _scope = Scope::new();
+            let l = log.clone();
+            register("first", move || l.lock().unwrap().push("first"));
+            let l = log.clone();
+            register("second", move || l.lock().unwrap().push("second"));
+            assert!(log.lock().unwrap().is_empty());
+        }
+
+        assert_eq!(*log.lock().unwrap(), vec!["second", "first"]);
+    }
+
+    #[test]
+    fn tear_down_just_the_innermost_scope() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let _outer = Scope::new();
+        let l = log.clone();
+        register("outer", move || l.lock().unwrap().push("outer"));
+        {
+            let _inner = Scope::new();
+            let l = log.clone();
+            register("inner", move || l.lock().unwrap().push("inner"));
+        }
+
+        assert_eq!(*log.lock().unwrap(), vec!["inner"]);
+    }
+
+    pub(crate) struct NoopWaker;
+
+    impl std::task::Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Pending at the first poll, ready at the second one.
+    pub(crate) struct YieldOnce(pub(crate) bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            if std::mem::replace(&mut self.0, true) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn collect_async_teardowns_in_the_future_and_not_in_the_polling_thread() {
+        let log = Arc::new(Mutex::new(vec![]));
+        let (l1, l2) = (log.clone(), log.clone());
+        let mut future = scoped(async move {
+            register("first", move || l1.lock().unwrap().push("first"));
+            YieldOnce(false).await;
+            register("second", move || l2.lock().unwrap().push("second"));
+        });
+        let waker = std::task::Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        {
+            // Another test's scope is active on this thread
+            let _other = Scope::new();
+            assert!(Pin::new(&mut future).poll(&mut cx).is_ready());
+            assert_eq!(*log.lock().unwrap(), vec!["second", "first"]);
+        }
+        assert_eq!(*log.lock().unwrap(), vec!["second", "first"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "teardown of fixture `broken` failed: cannot close")]
+    fn report_teardown_failures() {
+        let _scope = Scope::new();
+        register("broken", || panic!("cannot close"));
+    }
+
+    #[test]
+    fn run_all_teardowns_even_if_some_fail() {
+        static LOG: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+        let result = std::panic::catch_unwind(|| {
+            let _scope = Scope::new();
+            register("first", || LOG.lock().unwrap().push("first"));
+            register("broken", || panic!("cannot close"));
+        });
+
+        assert!(result.is_err());
+        assert_eq!(*LOG.lock().unwrap(), vec!["first"]);
+    }
+
+    pub(crate) fn registered_at_exit(fixture: &str) -> usize {
+        AT_EXIT
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.fixture == fixture)
+            .count()
+    }
+}
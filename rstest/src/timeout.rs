@@ -8,6 +8,28 @@ use futures_timer::Delay;
 pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
     code: F,
     timeout: Duration,
+) -> T {
+    run_with_timeout_sync(code, timeout, || format!("Timeout {:?} expired", timeout))
+}
+
+/// Like [`execute_with_timeout_sync`] but the panic message names the `fixture`
+/// that timed out.
+pub fn fixture_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
+    fixture: &'static str,
+    code: F,
+    timeout: Duration,
+) -> T {
+    run_with_timeout_sync(code, timeout, || fixture_timeout_message(fixture, timeout))
+}
+
+fn fixture_timeout_message(fixture: &str, timeout: Duration) -> String {
+    format!("Timeout {:?} expired resolving fixture `{}`", timeout, fixture)
+}
+
+fn run_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
+    code: F,
+    timeout: Duration,
+    message: impl FnOnce() -> String,
 ) -> T {
     let (sender, receiver) = mpsc::channel();
     let thread = if let Some(name) = thread::current().name() {
@@ -23,7 +45,7 @@ pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 's
             handle.join().unwrap().unwrap();
             result
         }
-        Err(mpsc::RecvTimeoutError::Timeout) => panic!("Timeout {:?} expired", timeout),
+        Err(mpsc::RecvTimeoutError::Timeout) => panic!("{}", message()),
         Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
             Err(any) => std::panic::resume_unwind(any),
             Ok(_) => unreachable!(),
@@ -35,11 +57,31 @@ pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 's
 pub async fn execute_with_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
     code: F,
     timeout: Duration,
+) -> T {
+    run_with_timeout_async(code, timeout, || format!("Timeout {:?} expired", timeout)).await
+}
+
+/// Like [`execute_with_timeout_async`] but the panic message names the `fixture`
+/// that timed out.
+#[cfg(feature = "async-timeout")]
+pub async fn fixture_with_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
+    fixture: &'static str,
+    code: F,
+    timeout: Duration,
+) -> T {
+    run_with_timeout_async(code, timeout, || fixture_timeout_message(fixture, timeout)).await
+}
+
+#[cfg(feature = "async-timeout")]
+async fn run_with_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
+    code: F,
+    timeout: Duration,
+    message: impl FnOnce() -> String,
 ) -> T {
     select! {
         () = async {
             Delay::new(timeout).await;
-        }.fuse() => panic!("Timeout {:?} expired", timeout),
+        }.fuse() => panic!("{}", message()),
         out = code().fuse() => out,
     }
 }
@@ -76,6 +118,17 @@ mod tests {
                 .await
             }
 
+            #[async_std::test]
+            #[should_panic = "Timeout 10ms expired resolving fixture `slow`"]
+            async fn should_fail_naming_the_fixture() {
+                fixture_with_timeout_async(
+                    "slow",
+                    || delayed_sum(2, 2, Duration::from_millis(40)),
+                    Duration::from_millis(10),
+                )
+                .await;
+            }
+
             #[async_std::test]
             async fn should_pass() {
                 execute_with_timeout_async(
@@ -195,5 +248,15 @@ mod tests {
 
             execute_with_timeout_sync(move || test(s), Duration::from_millis(20))
         }
+
+        #[test]
+        #[should_panic = "Timeout 30ms expired resolving fixture `slow`"]
+        fn should_fail_naming_the_fixture() {
+            fixture_with_timeout_sync(
+                "slow",
+                || delayed_sum(2, 2, Duration::from_millis(70)),
+                Duration::from_millis(30),
+            );
+        }
     }
 }
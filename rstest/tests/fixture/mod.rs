@@ -172,6 +172,23 @@ mod should {
             .assert(output);
     }
 
+    #[test]
+    fn apply_named_fixture_arguments() {
+        let prj = prj("named.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("default")
+            .ok("named_in_fixture")
+            .ok("named_in_test")
+            .ok("named_in_any_order")
+            .ok("named_async")
+            .ok("by_builder")
+            .assert(output);
+    }
+
     #[rstest]
     #[case::compact_form("default.rs")]
     #[case::attrs_form("default_in_attrs.rs")]
@@ -221,6 +238,361 @@ mod should {
         assert_eq!(1, occurences);
     }
 
+    #[test]
+    fn accept_once_attribute_on_async_fixture_and_call_fixture_just_once() {
+        let prj = prj("once_async.rs").with_nocapture();
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+        prj.add_dependency("tokio", r#"{version="*", features=["rt", "macros"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("base")
+            .ok("awt")
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .ok("other_runtime")
+            .assert(output.clone());
+        assert_eq!(1, output.stderr.str().count("Exec fixture() just once"));
+    }
+
+    #[test]
+    fn share_group_scoped_arguments_among_the_tests_of_the_group() {
+        let prj = prj("group.rs").with_nocapture();
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .ok("matrix::_a_1_1::_b_1_3")
+            .ok("matrix::_a_1_1::_b_2_4")
+            .ok("matrix::_a_2_2::_b_1_3")
+            .ok("matrix::_a_2_2::_b_2_4")
+            .ok("matrix_cases::case_1::_v_1_1")
+            .ok("matrix_cases::case_1::_v_2_2")
+            .ok("matrix_cases::case_2::_v_1_1")
+            .ok("matrix_cases::case_2::_v_2_2")
+            .ok("single")
+            .assert(output.clone());
+        let stderr = output.stderr.str();
+        assert_eq!(5, stderr.count("create resource"));
+        assert_eq!(5, stderr.count("teardown resource"));
+        assert_eq!(5, stderr.count("drop resource"));
+    }
+
+    #[test]
+    fn report_the_failing_once_fixture_and_the_test_that_initialized_it() {
+        let prj = prj("once_panic.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .fail("x::sync::case_1")
+            .fail("x::sync::case_2")
+            .fail("x::sync::case_3")
+            .fail("x::not_sync::case_1")
+            .fail("x::not_sync::case_2")
+            .fail("x::not_sync::case_3")
+            .assert(output.clone());
+        let stdout = output.stdout.str();
+        assert_eq!(1, stdout.count_regex("^connection refused$"));
+        assert_eq!(1, stdout.count_regex("^async connection refused$"));
+        assert_eq!(
+            2,
+            stdout.count("fixture `db` failed to initialize in test `x::sync::case_")
+        );
+        assert_eq!(
+            2,
+            stdout.count("fixture `async_db` failed to initialize in test `x::not_sync::case_")
+        );
+    }
+
+    #[test]
+    fn report_fallible_fixtures_failures_as_setup_errors() {
+        let prj = prj("fallible.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("use_value")
+            .ok("use_once_value")
+            .fail("fail_setup")
+            .fail("fail_async_setup")
+            .fail("fail_boxed_setup")
+            .assert(output.clone());
+        let stdout = output.stdout.str();
+        assert_eq!(
+            1,
+            stdout.count_regex("^setup of fixture `db` failed: cannot connect to the db$")
+        );
+        assert_eq!(
+            1,
+            stdout.count_regex("^caused by: port 5432 refused the connection$")
+        );
+        assert_eq!(
+            1,
+            stdout.count_regex("^setup of fixture `async_db` failed: async timeout$")
+        );
+        assert_eq!(
+            1,
+            stdout.count_regex("^setup of fixture `boxed` failed: boxed error$")
+        );
+    }
+
+    #[test]
+    fn accept_once_attribute_on_generic_fixture_and_call_fixture_just_once_for_each_type() {
+        let prj = prj("once_generic.rs").with_nocapture();
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("json")
+            .ok("yaml")
+            .ok("both::case_1")
+            .ok("both::case_2")
+            .ok("async_json")
+            .ok("async_yaml::case_1")
+            .ok("async_yaml::case_2")
+            .assert(output.clone());
+        for message in [
+            "Exec codec() just once for json",
+            "Exec codec() just once for yaml",
+            "Exec async_codec() just once for json",
+            "Exec async_codec() just once for yaml",
+        ] {
+            assert_eq!(1, output.stderr.str().count(message), "{message}");
+        }
+    }
+
+    #[test]
+    fn tear_down_once_fixtures_when_the_process_exits() {
+        let prj = prj("once_teardown.rs").with_nocapture();
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("use_resources::case_1")
+            .ok("use_resources::case_2")
+            .ok("use_async_resource")
+            .assert(output.clone());
+        let err = output.stderr.str().to_string();
+        for message in [
+            "Tear down client",
+            "Tear down server",
+            "Tear down async_resource",
+            "Tear down generic",
+        ] {
+            assert_eq!(1, err.count(message), "{message}");
+        }
+        // The values are never dropped: timed out tests can still use them
+        assert_not_in!(err, "Drop ");
+        let position = |message| err.find(message).unwrap();
+        assert!(position("Tear down client") < position("Tear down server"));
+    }
+
+    #[test]
+    fn keep_the_dependencies_of_once_fixtures_till_the_process_exits() {
+        let prj = prj("once_teardown_dependencies.rs").with_nocapture();
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("use_server::case_1")
+            .ok("use_server::case_2")
+            .fail("use_async_server")
+            .assert(output.clone());
+        let err = output.stderr.str().to_string();
+        assert_eq!(1, err.count("Remove server_dir"));
+        assert_in!(
+            err,
+            "async #[once] fixture `async_server` cannot keep the teardowns of its dependencies \
+            till the process exits: remove the teardowns of `dir` or make `async_server` sync"
+        );
+        assert_eq!(1, err.count("Remove async_server_dir"));
+    }
+
+    #[test]
+    fn run_teardown_after_test_in_reverse_dependency_order() {
+        let prj = prj("teardown.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+        let out = output.stdout.str().to_string();
+
+        TestResults::new()
+            .ok("reverse_dependency_order")
+            .ok("use_fixture_value")
+            .ok("use_fixture_value_in_partial::case_1")
+            .ok("use_fixture_value_in_partial::case_2")
+            .fail("teardown_failure")
+            .fail("test_failure_and_teardown_failure")
+            .ok("async_teardown")
+            .assert(output);
+
+        assert_eq!(
+            2,
+            out.count("teardown of fixture `broken` failed: cannot release resource")
+        );
+        assert_regex!(
+            "---- test_failure_and_teardown_failure stdout ----\n(.*\n)*.*left: 41",
+            out
+        );
+    }
+
+    #[test]
+    fn bundle_fixtures_in_a_struct() {
+        let prj = prj("bundle.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("resolve_fields")
+            .ok("rename_bundle::case_1")
+            .ok("rename_bundle::case_2")
+            .ok("await_future_fields")
+            .ok("give_some_fields")
+            .ok("build_by_get")
+            .ok("explicit_name")
+            .assert(output);
+    }
+
+    #[test]
+    fn multiply_the_tests_by_the_variants_of_parametrized_fixtures() {
+        let prj = prj("parametrized.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("single::store_1_Backend__Mem")
+            .ok("single::store_2_Backend__Disk")
+            .ok("renamed::db_1_Backend__Mem")
+            .ok("renamed::db_2_Backend__Disk")
+            .ok("product::pair_1_1___x__")
+            .ok("product::pair_2_1___y__")
+            .ok("product::pair_3_2___x__")
+            .ok("product::pair_4_2___y__")
+            .ok("with_values::store_1_Backend__Mem::n_1_1")
+            .ok("with_values::store_1_Backend__Mem::n_2_2")
+            .ok("with_values::store_2_Backend__Disk::n_1_1")
+            .ok("with_values::store_2_Backend__Disk::n_2_2")
+            .ok("with_cases::case_1::store_1_Backend__Mem")
+            .ok("with_cases::case_1::store_2_Backend__Disk")
+            .ok("with_cases::case_2::store_1_Backend__Mem")
+            .ok("with_cases::case_2::store_2_Backend__Disk")
+            .ok("two_fixtures::store_1_Backend__Mem::pair_1_1___x__")
+            .ok("two_fixtures::store_1_Backend__Mem::pair_2_1___y__")
+            .ok("two_fixtures::store_1_Backend__Mem::pair_3_2___x__")
+            .ok("two_fixtures::store_1_Backend__Mem::pair_4_2___y__")
+            .ok("two_fixtures::store_2_Backend__Disk::pair_1_1___x__")
+            .ok("two_fixtures::store_2_Backend__Disk::pair_2_1___y__")
+            .ok("two_fixtures::store_2_Backend__Disk::pair_3_2___x__")
+            .ok("two_fixtures::store_2_Backend__Disk::pair_4_2___y__")
+            .fail("await_variants::remote_1_Backend__Mem")
+            .ok("await_variants::remote_2_Backend__Disk")
+            .ok("nested::shadowed::store_1_3")
+            .fail("nested::shadowed::store_2_4")
+            .assert(output);
+    }
+
+    #[test]
+    fn not_multiply_the_tests_by_parametrized_fixtures_of_other_crates() {
+        let prj = prj("parametrized_other_crate.rs");
+        let fixtures = crate::ROOT_PROJECT
+            .subproject(format!("{}_fixtures", prj.get_name()))
+            .set_code_file(resources(Path::new("fixture").join("parametrized_lib.rs")));
+        fixtures.add_local_dependency("rstest");
+        prj.add_dependency(
+            "shared_fixtures",
+            &format!(
+                r#"{{path="{}", package="{}"}}"#,
+                fixtures.path().display(),
+                fixtures.get_name()
+            ),
+        );
+
+        let output = prj.run_tests().unwrap();
+
+        assert_in!(
+            output.stderr.str(),
+            "error: cannot find macro `backend` in this scope"
+        );
+    }
+
+    #[test]
+    fn resolve_arguments_by_type() {
+        let (output, _) = run_test("provide.rs");
+
+        TestResults::new()
+            .ok("resolve_by_type")
+            .ok("resolve_dependencies_by_type")
+            .ok("resolve_once_by_type")
+            .ok("mix_with_names")
+            .ok("resolve_in_cases::case_1")
+            .ok("resolve_in_cases::case_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn show_correct_provide_errors() {
+        let prj = prj("provide_errors.rs");
+        let output = prj.run_tests().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error[E0119]: conflicting implementations of trait `Provide` for type `Config`
+                  --> {name}/src/lib.rs:10:11
+                "#
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error[E0277]: no fixture provides `Unknown`
+                  --> {name}/src/lib.rs:18:34
+                "#
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot use provides on an async fixture: `rstest::Provide` is sync.
+                  --> {name}/src/lib.rs:20:11
+                "#
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot use provides on a fixture that returns a type defined outside your crate: the orphan rule forbids implementing `Provide` for it. Wrap it in a newtype.
+                  --> {name}/src/lib.rs:25:11
+                "#
+            )
+            .unindent()
+        );
+    }
+
     #[test]
     fn show_correct_errors() {
         let prj = prj("errors.rs");
@@ -272,6 +644,18 @@ mod should {
                |                             ^^^^^^"
                 .unindent()
         );
+        // The fixture's builder reports the wrong type on the argument too
+        assert_not_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error[E0308]: mismatched types
+                  --> {}/src/lib.rs:15:1
+                ",
+                name
+            )
+            .unindent()
+        );
 
         assert_in!(
             output.stderr.str(),
@@ -307,7 +691,7 @@ mod should {
             output.stderr.str(),
             format!(
                 r#"
-                error: Cannot apply #[once] to async fixture.
+                error: Cannot apply #[once] on generic fixture.
                   --> {}/src/lib.rs:38:3
                    |
                 38 | #[once]
@@ -323,24 +707,9 @@ mod should {
             format!(
                 r#"
                 error: Cannot apply #[once] on generic fixture.
-                  --> {}/src/lib.rs:43:3
-                   |
-                43 | #[once]
-                   |   ^^^^
-                "#,
-                name
-            )
-            .unindent()
-        );
-
-        assert_in!(
-            output.stderr.str(),
-            format!(
-                r#"
-                error: Cannot apply #[once] on generic fixture.
-                  --> {}/src/lib.rs:49:3
+                  --> {}/src/lib.rs:44:3
                    |
-                49 | #[once]
+                44 | #[once]
                    |   ^^^^
                 "#,
                 name
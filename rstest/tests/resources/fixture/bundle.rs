@@ -0,0 +1,82 @@
+use rstest::*;
+
+pub struct Db(u32);
+pub struct User(&'static str);
+
+#[fixture]
+fn db() -> Db {
+    Db(42)
+}
+
+#[fixture]
+fn user(#[default("guest")] role: &'static str) -> User {
+    User(role)
+}
+
+#[fixture]
+async fn port() -> u16 {
+    8080
+}
+
+#[derive(Fixtures)]
+struct TestEnv {
+    db: Db,
+    #[from(user)]
+    #[with("admin")]
+    admin: User,
+    #[from(user)]
+    guest: User,
+}
+
+#[derive(Fixtures)]
+struct AsyncEnv {
+    db: Db,
+    #[future]
+    port: u16,
+}
+
+#[rstest]
+fn resolve_fields(test_env: TestEnv) {
+    assert_eq!(42, test_env.db.0);
+    assert_eq!("admin", test_env.admin.0);
+    assert_eq!("guest", test_env.guest.0);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn rename_bundle(#[case] _n: u32, #[from(test_env)] env: TestEnv) {
+    assert_eq!("admin", env.admin.0);
+}
+
+#[rstest]
+async fn await_future_fields(#[future(awt)] async_env: AsyncEnv) {
+    assert_eq!(42, async_env.db.0);
+    assert_eq!(8080, async_env.port);
+}
+
+#[rstest]
+fn give_some_fields(#[with(Db(1))] test_env: TestEnv) {
+    assert_eq!(1, test_env.db.0);
+    assert_eq!("admin", test_env.admin.0);
+}
+
+#[test]
+fn build_by_get() {
+    let env = test_env::get(Db(2), User("root"), User("nobody"));
+
+    assert_eq!(2, env.db.0);
+    assert_eq!("root", env.admin.0);
+    assert_eq!("nobody", env.guest.0);
+}
+
+#[derive(Fixtures)]
+#[fixture_name(env)]
+struct TestEnvironment {
+    db: Db,
+}
+
+#[rstest]
+fn explicit_name(env: TestEnvironment) {
+    assert_eq!(42, env.db.0);
+}
@@ -36,13 +36,8 @@ fn error_inject_a_fixture_more_than_once(f: String) {
 
 #[fixture]
 #[once]
-async fn error_async_once_fixture() {
-}
-
-#[fixture]
-#[once]
-fn error_generics_once_fixture<T: std::fmt::Debug>() -> T {
-    42
+fn error_const_generics_once_fixture<const N: usize>() -> usize {
+    N
 }
 
 #[fixture]
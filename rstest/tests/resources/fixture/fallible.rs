@@ -0,0 +1,76 @@
+use rstest::*;
+use std::{error::Error, fmt, io};
+
+#[derive(Debug)]
+struct ConnectError(io::Error);
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot connect to the db")
+    }
+}
+
+impl Error for ConnectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[fixture]
+#[fallible]
+fn port() -> Result<u16, String> {
+    Ok(5432)
+}
+
+#[fixture]
+#[fallible]
+fn db(port: u16) -> Result<u16, ConnectError> {
+    Err(ConnectError(io::Error::new(
+        io::ErrorKind::ConnectionRefused,
+        format!("port {port} refused the connection"),
+    )))
+}
+
+#[fixture]
+#[fallible]
+async fn async_db() -> io::Result<u16> {
+    Err(io::Error::new(io::ErrorKind::TimedOut, "async timeout"))
+}
+
+#[fixture]
+#[fallible]
+fn boxed() -> Result<u16, Box<dyn Error>> {
+    Err("boxed error".into())
+}
+
+#[fixture]
+#[fallible]
+#[once]
+fn shared(#[default(4)] value: u16) -> Result<u16, String> {
+    Ok(value)
+}
+
+#[rstest]
+fn use_value(port: u16) {
+    assert_eq!(5432, port);
+}
+
+#[rstest]
+fn use_once_value(shared: &u16) {
+    assert_eq!(4, *shared);
+}
+
+#[rstest]
+fn fail_setup(db: u16) {
+    assert_eq!(5432, db);
+}
+
+#[rstest]
+async fn fail_async_setup(#[future(awt)] async_db: u16) {
+    assert_eq!(5432, async_db);
+}
+
+#[rstest]
+fn fail_boxed_setup(boxed: u16) {
+    assert_eq!(5432, boxed);
+}
@@ -0,0 +1,42 @@
+use rstest::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+pub struct Resource(u32);
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        eprintln!("drop resource");
+    }
+}
+
+#[fixture]
+#[teardown(|| eprintln!("teardown resource"))]
+fn resource() -> Resource {
+    eprintln!("create resource");
+    Resource(NEXT_ID.fetch_add(1, Ordering::SeqCst))
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+fn cases(#[scope(group)] resource: &Resource, #[case] _n: u32) {
+    assert!(resource.0 > 0);
+}
+
+#[rstest]
+fn matrix(#[scope(group)] resource: &Resource, #[values(1, 2)] _a: u32, #[values(3, 4)] _b: u32) {
+    assert!(resource.0 > 0);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn matrix_cases(#[scope(group)] _resource: &Resource, #[case] _c: u32, #[values(1, 2)] _v: u32) {}
+
+#[rstest]
+fn single(#[scope(group)] resource: &Resource) {
+    assert!(resource.0 > 0);
+}
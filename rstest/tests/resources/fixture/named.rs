@@ -0,0 +1,88 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+struct User {
+    name: String,
+    age: u8,
+    country: String,
+}
+
+#[fixture]
+fn name() -> &'static str {
+    "Alice"
+}
+
+#[fixture]
+fn user(name: impl AsRef<str>, #[default(22)] age: u8, #[default("US")] country: &str) -> User {
+    User {
+        name: name.as_ref().to_owned(),
+        age,
+        country: country.to_owned(),
+    }
+}
+
+#[fixture]
+fn italian(#[with(country = "IT")] user: User) -> User {
+    user
+}
+
+#[fixture]
+async fn async_user(#[default(22)] age: u8, #[default("US")] country: &str) -> User {
+    User {
+        name: "Alice".to_owned(),
+        age,
+        country: country.to_owned(),
+    }
+}
+
+#[rstest]
+fn default(user: User) {
+    assert_eq!(
+        ("Alice", 22, "US"),
+        (user.name.as_str(), user.age, user.country.as_str())
+    );
+}
+
+#[rstest]
+fn named_in_fixture(italian: User) {
+    assert_eq!(
+        ("Alice", 22, "IT"),
+        (italian.name.as_str(), italian.age, italian.country.as_str())
+    );
+}
+
+#[rstest]
+fn named_in_test(#[with(age = 42, country = "IT")] user: User) {
+    assert_eq!(
+        ("Alice", 42, "IT"),
+        (user.name.as_str(), user.age, user.country.as_str())
+    );
+}
+
+#[rstest]
+fn named_in_any_order(#[with(country = &String::from("IT"), name = "Bob")] user: User) {
+    assert_eq!(
+        ("Bob", 22, "IT"),
+        (user.name.as_str(), user.age, user.country.as_str())
+    );
+}
+
+#[rstest]
+#[async_std::test]
+async fn named_async(
+    #[future]
+    #[with(age = 42)]
+    async_user: User,
+) {
+    assert_eq!(42, async_user.await.age);
+}
+
+#[test]
+fn by_builder() {
+    let user = user::builder().name("Bob").age(3).build();
+
+    assert_eq!(
+        ("Bob", 3, "US"),
+        (user.name.as_str(), user.age, user.country.as_str())
+    );
+}
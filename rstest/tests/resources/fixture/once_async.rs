@@ -0,0 +1,34 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+#[once]
+async fn once_fixture() -> u32 {
+    eprintln!("Exec fixture() just once");
+    async_std::task::sleep(Duration::from_millis(100)).await;
+    42
+}
+
+#[rstest]
+async fn base(#[future] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture.await);
+}
+
+#[rstest]
+async fn awt(#[future(awt)] once_fixture: &'static u32) {
+    assert_eq!(&42, once_fixture);
+}
+
+#[rstest]
+#[case(2)]
+#[case(3)]
+#[case(7)]
+async fn cases(#[future(awt)] once_fixture: &u32, #[case] divisor: u32) {
+    assert_eq!(0, *once_fixture % divisor);
+}
+
+#[rstest]
+#[tokio::test]
+async fn other_runtime(#[future(awt)] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture);
+}
@@ -0,0 +1,68 @@
+use rstest::*;
+
+trait Codec: Default {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct Json;
+#[derive(Default)]
+struct Yaml;
+
+impl Codec for Json {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+}
+
+impl Codec for Yaml {
+    fn name(&self) -> &'static str {
+        "yaml"
+    }
+}
+
+#[fixture]
+#[once]
+fn codec<C: Codec>() -> C {
+    let c = C::default();
+    eprintln!("Exec codec() just once for {}", c.name());
+    c
+}
+
+#[fixture]
+#[once]
+async fn async_codec<C: Codec>() -> C {
+    let c = C::default();
+    eprintln!("Exec async_codec() just once for {}", c.name());
+    c
+}
+
+#[rstest]
+fn json(codec: &Json) {
+    assert_eq!("json", codec.name());
+}
+
+#[rstest]
+fn yaml(codec: &Yaml) {
+    assert_eq!("yaml", codec.name());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn both(#[case] _case: u32, codec: &Json, #[from(codec)] other: &Yaml) {
+    assert_eq!("json", codec.name());
+    assert_eq!("yaml", other.name());
+}
+
+#[rstest]
+async fn async_json(#[future(awt)] async_codec: &Json) {
+    assert_eq!("json", async_codec.name());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+async fn async_yaml(#[case] _case: u32, #[future(awt)] async_codec: &Yaml) {
+    assert_eq!("yaml", async_codec.name());
+}
@@ -0,0 +1,33 @@
+use rstest::*;
+
+#[fixture]
+#[once]
+fn db() -> u32 {
+    panic!("connection refused")
+}
+
+#[fixture]
+#[once]
+async fn async_db() -> u32 {
+    panic!("async connection refused")
+}
+
+mod x {
+    use super::*;
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    fn sync(db: &u32, #[case] expected: u32) {
+        assert_eq!(expected, *db);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    async fn not_sync(#[future(awt)] async_db: &u32, #[case] expected: u32) {
+        assert_eq!(expected, *async_db);
+    }
+}
@@ -0,0 +1,63 @@
+use rstest::*;
+
+struct Resource(&'static str);
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        eprintln!("Drop {}", self.0);
+    }
+}
+
+#[fixture]
+#[once]
+#[teardown(move || eprintln!("Tear down {}", server.0))]
+fn server() -> Resource {
+    Resource("server")
+}
+
+#[fixture]
+#[once]
+#[teardown(move || eprintln!("Tear down {}", client.0))]
+fn client(server: &'static Resource) -> Resource {
+    assert_eq!("server", server.0);
+    Resource("client")
+}
+
+#[fixture]
+#[once]
+fn never_dropped() -> Resource {
+    Resource("never_dropped")
+}
+
+#[fixture]
+#[once]
+#[teardown(move || eprintln!("Tear down {}", async_resource.0))]
+async fn async_resource() -> Resource {
+    Resource("async_resource")
+}
+
+#[fixture]
+#[once]
+#[teardown(move || eprintln!("Tear down {}", generic.1 .0))]
+fn generic<T: Default + Send + Sync>() -> (T, Resource) {
+    (T::default(), Resource("generic"))
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn use_resources(
+    #[case] _case: u32,
+    client: &Resource,
+    never_dropped: &Resource,
+    generic: &(u32, Resource),
+) {
+    assert_eq!("client", client.0);
+    assert_eq!("never_dropped", never_dropped.0);
+    assert_eq!("generic", generic.1 .0);
+}
+
+#[rstest]
+async fn use_async_resource(#[future(awt)] async_resource: &Resource) {
+    assert_eq!("async_resource", async_resource.0);
+}
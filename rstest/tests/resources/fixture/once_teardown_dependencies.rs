@@ -0,0 +1,44 @@
+use rstest::*;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+#[fixture]
+#[teardown({
+    let path = dir.clone();
+    // Teardowns don't need to be `Send`
+    let not_send = Rc::new(());
+    move || {
+        drop(not_send);
+        eprintln!("Remove {}", path.file_name().unwrap().to_str().unwrap());
+        std::fs::remove_dir_all(path).unwrap()
+    }
+})]
+fn dir(#[default("server")] name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}_dir_{}", name, std::process::id()));
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[fixture]
+#[once]
+fn server(dir: PathBuf) -> PathBuf {
+    dir
+}
+
+#[fixture]
+#[once]
+async fn async_server(#[with("async_server")] dir: PathBuf) -> PathBuf {
+    dir
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn use_server(#[case] _case: u32, server: &PathBuf) {
+    assert!(server.is_dir());
+}
+
+#[rstest]
+async fn use_async_server(#[future(awt)] async_server: &PathBuf) {
+    assert!(async_server.is_dir());
+}
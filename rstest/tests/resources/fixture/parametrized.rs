@@ -0,0 +1,88 @@
+use rstest::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backend {
+    Mem,
+    Disk,
+}
+
+pub struct Store {
+    backend: Backend,
+    size: u32,
+}
+
+#[fixture]
+fn size() -> u32 {
+    42
+}
+
+#[fixture]
+fn store(#[values(Backend::Mem, Backend::Disk)] backend: Backend, size: u32) -> Store {
+    Store { backend, size }
+}
+
+#[fixture]
+fn pair(#[values(1, 2)] a: u32, #[values("x", "y")] b: &str) -> (u32, String) {
+    (a, b.to_owned())
+}
+
+#[fixture]
+async fn remote(#[values(Backend::Mem, Backend::Disk)] backend: Backend) -> Backend {
+    backend
+}
+
+#[rstest]
+fn single(#[values] store: Store) {
+    assert_eq!(42, store.size);
+    assert!(matches!(store.backend, Backend::Mem | Backend::Disk));
+}
+
+#[rstest]
+fn renamed(#[values] #[from(store)] db: Store) {
+    assert_eq!(42, db.size);
+}
+
+#[rstest]
+fn product(#[values] pair: (u32, String)) {
+    assert!([1, 2].contains(&pair.0));
+}
+
+#[rstest]
+fn with_values(#[values] store: Store, #[values(1, 2)] n: u32) {
+    assert!(n > 0 && store.size == 42);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn with_cases(#[case] n: u32, #[values] store: Store) {
+    assert!(n > 0 && store.size == 42);
+}
+
+#[rstest]
+fn two_fixtures(#[values] store: Store, #[values] pair: (u32, String)) {
+    assert!(pair.0 > 0 && store.size == 42);
+}
+
+#[rstest]
+async fn await_variants(#[values] #[future(awt)] remote: Backend) {
+    assert_eq!(Backend::Disk, remote);
+}
+
+mod nested {
+    use super::{Backend, Store};
+    use rstest::*;
+
+    #[fixture]
+    fn store(#[values(3, 4)] size: u32) -> Store {
+        Store {
+            backend: Backend::Mem,
+            size,
+        }
+    }
+
+    #[rstest]
+    fn shadowed(#[values] store: Store) {
+        assert_eq!(3, store.size);
+    }
+}
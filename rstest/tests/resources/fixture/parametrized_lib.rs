@@ -0,0 +1,6 @@
+use rstest::*;
+
+#[fixture]
+pub fn backend(#[values(1, 2)] id: u32) -> u32 {
+    id
+}
@@ -0,0 +1,7 @@
+use rstest::*;
+use shared_fixtures::*;
+
+#[rstest]
+fn other_crate(#[values] backend: u32) {
+    assert!(backend > 0);
+}
@@ -0,0 +1,56 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    port: u16,
+}
+
+#[fixture(provides)]
+fn config(#[default(8080)] port: u16) -> Config {
+    Config { port }
+}
+
+pub struct Server {
+    port: u16,
+}
+
+#[fixture(provides)]
+fn server(#[by_type] cfg: Config) -> Server {
+    Server { port: cfg.port }
+}
+
+pub struct Pool(u32);
+
+#[fixture(provides)]
+#[once]
+fn pool() -> Pool {
+    Pool(42)
+}
+
+#[rstest]
+fn resolve_by_type(#[by_type] main: Config, #[by_type] other: Config) {
+    assert_eq!(main, other);
+    assert_eq!(8080, main.port);
+}
+
+#[rstest]
+fn resolve_dependencies_by_type(#[by_type] srv: Server) {
+    assert_eq!(8080, srv.port);
+}
+
+#[rstest]
+fn resolve_once_by_type(#[by_type] p: &Pool) {
+    assert_eq!(42, p.0);
+}
+
+#[rstest]
+fn mix_with_names(config: Config, #[by_type] cfg: Config) {
+    assert_eq!(config, cfg);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn resolve_in_cases(#[case] _n: u32, #[by_type] cfg: Config) {
+    assert_eq!(8080, cfg.port);
+}
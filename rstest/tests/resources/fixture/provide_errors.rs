@@ -0,0 +1,28 @@
+use rstest::*;
+
+pub struct Config;
+
+#[fixture(provides)]
+fn config() -> Config {
+    Config
+}
+
+#[fixture(provides)]
+fn other_config() -> Config {
+    Config
+}
+
+pub struct Unknown;
+
+#[rstest]
+fn missed_provider(#[by_type] u: Unknown) {}
+
+#[fixture(provides)]
+async fn async_provider() -> u32 {
+    42
+}
+
+#[fixture(provides)]
+fn port() -> u16 {
+    8080
+}
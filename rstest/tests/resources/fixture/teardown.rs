@@ -0,0 +1,83 @@
+use rstest::*;
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+thread_local! {
+    static TORN_DOWN: RefCell<Vec<&'static str>> = RefCell::new(vec![]);
+}
+
+fn torn_down(name: &'static str) {
+    TORN_DOWN.with(|t| t.borrow_mut().push(name))
+}
+
+fn already_torn_down() -> Vec<&'static str> {
+    TORN_DOWN.with(|t| t.borrow().clone())
+}
+
+#[fixture]
+#[teardown(|| assert_eq!(vec!["dependent"], already_torn_down()))]
+fn base() -> u32 {
+    21
+}
+
+#[fixture]
+#[teardown(|| torn_down("dependent"))]
+fn dependent(base: u32) -> u32 {
+    base * 2
+}
+
+#[fixture]
+#[teardown({
+    let path = temp_file.clone();
+    move || std::fs::remove_file(path).unwrap()
+})]
+fn temp_file(#[default("teardown_temp_file")] name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+    std::fs::write(&path, "some data").unwrap();
+    path
+}
+
+#[fixture]
+#[teardown(|| panic!("cannot release resource"))]
+fn broken() -> u32 {
+    42
+}
+
+#[fixture]
+#[teardown(|| torn_down("async_fixture"))]
+async fn async_fixture() -> u32 {
+    42
+}
+
+#[rstest]
+fn reverse_dependency_order(dependent: u32) {
+    assert_eq!(42, dependent);
+    assert!(already_torn_down().is_empty());
+}
+
+#[rstest]
+fn use_fixture_value(temp_file: PathBuf) {
+    assert!(temp_file.exists());
+}
+
+#[rstest]
+#[case("first")]
+#[case("second")]
+fn use_fixture_value_in_partial(#[case] name: &str, #[with(name)] temp_file: PathBuf) {
+    assert!(temp_file.exists());
+}
+
+#[rstest]
+fn teardown_failure(broken: u32) {
+    assert_eq!(42, broken);
+}
+
+#[rstest]
+fn test_failure_and_teardown_failure(broken: u32) {
+    assert_eq!(41, broken);
+}
+
+#[rstest]
+async fn async_teardown(#[future] async_fixture: u32) {
+    assert_eq!(42, async_fixture.await);
+}
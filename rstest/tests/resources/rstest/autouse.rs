@@ -0,0 +1,101 @@
+use rstest::*;
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log(message: &'static str) {
+    LOG.with(|l| l.borrow_mut().push(message));
+}
+
+fn logged() -> Vec<&'static str> {
+    LOG.with(|l| l.borrow().clone())
+}
+
+#[fixture]
+fn logging() {
+    log("logging");
+}
+
+#[fixture]
+fn locale() -> &'static str {
+    log("locale");
+    "en_US"
+}
+
+#[fixture]
+fn registry() {
+    log("registry");
+}
+
+#[fixture]
+async fn connect() {
+    log("connect");
+}
+
+#[fixture]
+fn value() -> u32 {
+    log("value");
+    42
+}
+
+#[rstest]
+#[autouse(logging, locale)]
+fn single(value: u32) {
+    assert_eq!(42, value);
+    assert_eq!(vec!["logging", "locale", "value"], logged());
+}
+
+#[rstest]
+#[autouse(logging, locale)]
+fn not_twice_if_argument(locale: &str) {
+    assert_eq!("en_US", locale);
+    assert_eq!(vec!["logging", "locale"], logged());
+}
+
+#[rstest]
+#[autouse(logging, connect)]
+async fn await_async_fixtures() {
+    assert_eq!(vec!["logging", "connect"], logged());
+}
+
+#[autouse(logging)]
+mod module {
+    use super::*;
+
+    #[rstest]
+    fn single() {
+        assert_eq!(vec!["logging"], logged());
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn cases(#[case] _v: u32) {
+        assert_eq!(vec!["logging"], logged());
+    }
+
+    #[rstest]
+    fn matrix(#[values(1, 2)] _v: u32) {
+        assert_eq!(vec!["logging"], logged());
+    }
+
+    fn not_a_test() {}
+
+    #[test]
+    fn plain_test() {
+        not_a_test();
+        assert!(logged().is_empty());
+    }
+
+    #[autouse(registry)]
+    mod nested {
+        use super::*;
+
+        #[rstest]
+        fn single() {
+            assert_eq!(vec!["logging", "registry"], logged());
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use rstest::*;
+use std::cell::Cell;
+
+pub struct Connection {
+    open: Cell<u32>,
+}
+
+impl Connection {
+    fn open(&self) -> u32 {
+        self.open.get()
+    }
+}
+
+pub struct Transaction<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> Transaction<'a> {
+    fn new(conn: &'a Connection) -> Self {
+        conn.open.set(conn.open.get() + 1);
+        Self { conn }
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        self.conn.open.set(self.conn.open.get() - 1);
+    }
+}
+
+#[fixture]
+fn connection() -> Connection {
+    Connection { open: Cell::new(0) }
+}
+
+#[fixture]
+fn transaction(#[by_ref] connection: &Connection) -> Transaction<'_> {
+    Transaction::new(connection)
+}
+
+#[fixture]
+fn nested<'a>(#[by_ref] connection: &'a Connection, #[default(2)] n: u32) -> Vec<Transaction<'a>> {
+    (0..n).map(|_| Transaction::new(connection)).collect()
+}
+
+#[rstest]
+fn borrow_fixture(#[by_ref] connection: &Connection, #[with(connection)] transaction: Transaction) {
+    assert_eq!(1, connection.open());
+    drop(transaction);
+    assert_eq!(0, connection.open());
+}
+
+#[rstest]
+fn more_borrowers(
+    #[by_ref] connection: &Connection,
+    #[with(connection)] transaction: Transaction,
+    #[with(connection)] nested: Vec<Transaction>,
+) {
+    assert_eq!(3, connection.open());
+    drop((transaction, nested));
+    assert_eq!(0, connection.open());
+}
+
+#[rstest]
+#[case(1)]
+#[case(3)]
+fn with_cases(
+    #[by_ref] connection: &Connection,
+    #[case] n: u32,
+    #[with(connection, n)] nested: Vec<Transaction>,
+) {
+    assert_eq!(n as usize, nested.len());
+    assert_eq!(n, connection.open());
+}
+
+#[rstest]
+fn fail(#[by_ref] connection: &Connection) {
+    assert_eq!(1, connection.open());
+}
@@ -0,0 +1,57 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+pub enum Role {
+    Admin,
+    Guest,
+}
+
+#[fixture]
+fn user(#[default(Role::Guest)] role: Role, #[default("Bob")] name: &str) -> (String, Role) {
+    (name.to_owned(), role)
+}
+
+#[rstest]
+#[with(user(Role::Admin))]
+#[case(true)]
+#[case(false)]
+fn positional(#[case] is_admin: bool, user: (String, Role)) {
+    assert_eq!(is_admin, user.1 == Role::Admin);
+}
+
+#[rstest]
+#[with(user(name = "Alice"))]
+#[case("Alice")]
+#[with(user(role = Role::Admin, name = "Carl"))]
+#[case::admin("Carl")]
+#[case("Bob")]
+fn named(#[case] expected: &str, user: (String, Role)) {
+    assert_eq!(expected, user.0);
+}
+
+#[rstest]
+#[with(user(Role::Admin))]
+#[case(Role::Admin, "Bob")]
+#[case(Role::Guest, "Dan")]
+fn replace_test_with(
+    #[case] role: Role,
+    #[case] name: &str,
+    #[with(Role::Guest, "Dan")] user: (String, Role),
+) {
+    assert_eq!((name.to_owned(), role), user);
+}
+
+#[rstest]
+#[with(user(Role::Admin))]
+#[case(Role::Admin)]
+#[case(Role::Guest)]
+fn matrix(#[case] expected: Role, user: (String, Role), #[values(1, 2)] _v: u32) {
+    assert_eq!(expected, user.1);
+}
+
+#[rstest]
+#[with(user(Role::Admin))]
+#[case(Role::Guest)]
+fn fail(#[case] expected: Role, user: (String, Role)) {
+    assert_eq!(expected, user.1);
+}
@@ -0,0 +1,33 @@
+use rstest::*;
+use std::net::SocketAddr;
+
+#[rstest]
+#[cases_from("data/sum.csv")]
+fn csv(#[case] a: u32, #[case] b: u32, #[case] sum: u32) {
+    assert_eq!(sum, a + b);
+}
+
+#[rstest]
+#[cases_from("data/sum.json")]
+fn json(#[case] a: u32, #[case] b: u32, #[case] sum: u32) {
+    assert_eq!(sum, a + b);
+}
+
+#[rstest]
+#[cases_from("data/sum.toml")]
+fn toml(#[case] a: u32, #[case] b: u32, #[case] sum: u32) {
+    assert_eq!(sum, a + b);
+}
+
+#[rstest]
+#[case("127.0.0.1:80")]
+#[cases_from("data/addresses.txt")]
+fn lines(#[case] address: SocketAddr) {
+    assert_ne!(0, address.port());
+}
+
+#[rstest]
+#[cases_from("data/names.txt")]
+fn matrix(#[case] name: &str, #[values(1, 2)] n: usize) {
+    assert!(name.len() > n);
+}
@@ -0,0 +1,109 @@
+use rstest::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static NEVER: AtomicBool = AtomicBool::new(false);
+
+async fn wait_for(flag: &AtomicBool) -> bool {
+    for _ in 0..100 {
+        if flag.load(Ordering::SeqCst) {
+            return true;
+        }
+        async_std::task::yield_now().await;
+    }
+    false
+}
+
+#[fixture]
+async fn waiter(#[default(&NEVER)] flag: &'static AtomicBool) -> bool {
+    wait_for(flag).await
+}
+
+#[fixture]
+async fn notifier(#[default(&NEVER)] flag: &'static AtomicBool) -> u32 {
+    flag.store(true, Ordering::SeqCst);
+    42
+}
+
+static GLOBAL: AtomicBool = AtomicBool::new(false);
+
+#[rstest]
+#[concurrent]
+#[awt]
+async fn global_awt(
+    #[future]
+    #[with(&GLOBAL)]
+    waiter: bool,
+    #[future]
+    #[with(&GLOBAL)]
+    notifier: u32,
+) {
+    assert!(waiter);
+    assert_eq!(42, notifier);
+}
+
+static SELECTIVE: AtomicBool = AtomicBool::new(false);
+
+#[rstest]
+#[concurrent]
+async fn selective_awt(
+    #[future(awt)]
+    #[with(&SELECTIVE)]
+    waiter: bool,
+    #[future(awt)]
+    #[with(&SELECTIVE)]
+    notifier: u32,
+) {
+    assert!(waiter);
+    assert_eq!(42, notifier);
+}
+
+static FIXTURE: AtomicBool = AtomicBool::new(false);
+
+#[fixture]
+#[concurrent]
+#[awt]
+async fn both(
+    #[future]
+    #[with(&FIXTURE)]
+    waiter: bool,
+    #[future]
+    #[with(&FIXTURE)]
+    notifier: u32,
+) -> bool {
+    waiter && notifier == 42
+}
+
+#[rstest]
+#[awt]
+async fn in_fixture(#[future] both: bool) {
+    assert!(both);
+}
+
+static SEQUENTIAL: AtomicBool = AtomicBool::new(false);
+
+#[rstest]
+#[awt]
+async fn sequential(
+    #[future]
+    #[with(&SEQUENTIAL)]
+    waiter: bool,
+    #[future]
+    #[with(&SEQUENTIAL)]
+    notifier: u32,
+) {
+    assert!(waiter);
+    assert_eq!(42, notifier);
+}
+
+static SPAWNED: AtomicBool = AtomicBool::new(false);
+
+// A concurrent fixture's future can be spawned on a multithreaded runtime
+#[rstest]
+async fn spawn_send_fixture() {
+    let both = async_std::task::spawn(both::get(
+        waiter::partial_1(&SPAWNED),
+        notifier::partial_1(&SPAWNED),
+    ));
+
+    assert!(both.await);
+}
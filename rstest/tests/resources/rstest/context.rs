@@ -0,0 +1,46 @@
+use rstest::*;
+
+#[rstest]
+fn single(#[context] ctx: Context) {
+    assert_eq!("single", ctx.name);
+    assert_eq!("single", ctx.path);
+    assert_eq!(None, ctx.case);
+    assert_eq!(None, ctx.description);
+    assert!(ctx.values.is_empty());
+    assert!(ctx.start.elapsed() < std::time::Duration::from_secs(60));
+}
+
+#[rstest]
+#[case::first(1, Some("first"))]
+#[case(2, None)]
+#[case::third(3, Some("third"))]
+fn cases(#[case] index: usize, #[case] description: Option<&str>, #[context] ctx: rstest::Context) {
+    assert_eq!("cases", ctx.name);
+    assert!(ctx.path.starts_with("cases::case_"));
+    assert_eq!(Some(index), ctx.case);
+    assert_eq!(description, ctx.description);
+}
+
+#[rstest]
+fn values(#[values(1, 2)] _x: u32, #[values("a")] _y: &str, #[context] ctx: Context) {
+    assert_eq!("values", ctx.name);
+    assert_eq!(None, ctx.case);
+    assert_eq!(format!("values::{}", ctx.values.join("::")), ctx.path);
+}
+
+#[rstest]
+#[case(1)]
+fn case_values(#[case] index: usize, #[values(3)] _x: u32, #[context] ctx: Context) {
+    assert_eq!(Some(index), ctx.case);
+    assert_eq!(&["_x_1_3"], ctx.values);
+    assert_eq!("case_values::case_1::_x_1_3", ctx.path);
+}
+
+mod module {
+    use super::*;
+
+    #[rstest]
+    async fn in_module(#[context] ctx: Context) {
+        assert_eq!("module::in_module", ctx.path);
+    }
+}
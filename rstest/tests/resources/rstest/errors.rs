@@ -118,3 +118,30 @@ fn error_timeout_without_duration() {}
 
 #[rstest]
 fn error_absolute_path_files(#[files("/tmp/tmp.Q81idVZYAV/*.txt")] path: std::path::PathBuf) {}
+
+#[fixture]
+async fn async_setup() {}
+
+#[rstest]
+#[autouse(async_setup)]
+fn error_async_autouse_in_sync_test() {}
+
+#[fixture]
+fn shifted(#[default(0)] base: u32) -> u32 {
+    base + 2
+}
+
+#[rstest]
+#[case(40)]
+#[case(41)]
+fn error_group_with_test_argument(
+    #[case] base: u32,
+    #[scope(group)]
+    #[with(base)]
+    shifted: &u32,
+) {
+}
+
+#[rstest]
+#[cases_from("data/cases.yaml")]
+fn error_cases_from_unsupported_format(#[case] a: u32) {}
@@ -32,3 +32,23 @@ fn start_with_name_with_include(
 
     assert!(contents.starts_with(name.to_str().unwrap()))
 }
+
+#[rstest]
+fn contents_as_str(
+    #[files("files/*.txt")]
+    #[exclude("exclude")]
+    #[mode = str]
+    contents: &str,
+) {
+    assert!(contents.starts_with("element_"))
+}
+
+#[rstest]
+fn contents_as_bytes(
+    #[files("files/*.txt")]
+    #[exclude("exclude")]
+    #[mode = bytes]
+    contents: &[u8],
+) {
+    assert!(contents.starts_with(b"element_"))
+}
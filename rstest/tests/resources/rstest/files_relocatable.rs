@@ -0,0 +1,13 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn relocated(
+    #[files("files/*.txt")]
+    #[mode = relocatable]
+    path: PathBuf,
+) {
+    let base = std::env::var("RSTEST_FILES_BASE").unwrap();
+    assert!(path.starts_with(base));
+    assert_eq!("relocated", std::fs::read_to_string(path).unwrap())
+}
@@ -0,0 +1,23 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn golden(
+    #[files("cases/*.input")]
+    #[mode = str]
+    input: &str,
+    #[sibling("{stem}.expected")]
+    #[mode = str]
+    expected: &str,
+) {
+    assert_eq!(expected, input.to_uppercase())
+}
+
+#[rstest]
+fn paths(
+    #[files("cases/*.input")] input: PathBuf,
+    #[sibling("{stem}.expected")] expected: PathBuf,
+) {
+    assert_eq!(input.file_stem(), expected.file_stem());
+    assert_eq!(input.parent(), expected.parent());
+}
@@ -0,0 +1,7 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn tracked(#[files("files/*.txt")] path: PathBuf) {
+    assert!(path.exists())
+}
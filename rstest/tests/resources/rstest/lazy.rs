@@ -0,0 +1,83 @@
+use rstest::*;
+use std::{
+    rc::Rc,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+static BUILT: AtomicU32 = AtomicU32::new(0);
+
+#[fixture]
+fn expensive() -> u32 {
+    BUILT.fetch_add(1, Ordering::SeqCst);
+    42
+}
+
+#[fixture]
+fn never() -> u32 {
+    panic!("should never be resolved")
+}
+
+#[fixture]
+fn add(#[default(1)] a: u32, #[default(2)] b: u32) -> u32 {
+    a + b
+}
+
+#[fixture]
+async fn async_value() -> u32 {
+    42
+}
+
+#[rstest]
+fn resolve_when_used(#[lazy] expensive: Lazy<u32>) {
+    let before = BUILT.load(Ordering::SeqCst);
+    assert!(!expensive.is_resolved());
+
+    assert_eq!(42, *expensive);
+    assert_eq!(42, *expensive);
+    assert_eq!(before + 1, BUILT.load(Ordering::SeqCst));
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn resolve_only_if_used(#[case] need: bool, #[lazy] never: Lazy<u32>) {
+    if need {
+        assert_eq!(0, *never);
+    }
+}
+
+#[rstest]
+fn honor_with(
+    #[lazy]
+    #[with(40)]
+    add: Lazy<u32>,
+) {
+    assert_eq!(42, *add);
+}
+
+#[fixture]
+fn double(#[default(Rc::new(0))] value: Rc<u32>) -> u32 {
+    2 * *value
+}
+
+#[rstest]
+#[case(Rc::new(21))]
+fn capture_not_send_values(
+    #[case] value: Rc<u32>,
+    #[lazy]
+    #[with(value.clone())]
+    double: Lazy<u32>,
+) {
+    assert_eq!(42, *double);
+    assert_eq!(21, *value);
+}
+
+#[rstest]
+async fn async_fixture(#[lazy] async_value: AsyncLazy<u32>) {
+    assert!(!async_value.is_resolved());
+
+    assert_eq!(42, *async_value.get().await);
+}
+
+#[rstest]
+fn matrix(#[lazy] _never: Lazy<u32>, #[values(1, 2)] _v: u32) {}
@@ -0,0 +1,52 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+fn slow() -> u32 {
+    std::thread::sleep(Duration::from_millis(20));
+    42
+}
+
+#[fixture]
+fn on_slow(slow: u32) -> u32 {
+    slow + 1
+}
+
+#[fixture]
+fn fast(#[default(1)] n: u32) -> u32 {
+    n
+}
+
+#[fixture]
+#[once]
+fn shared() -> u32 {
+    3
+}
+
+#[fixture]
+async fn async_slow() -> u32 {
+    async_std::task::sleep(Duration::from_millis(20)).await;
+    42
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn sync_test(slow: u32, fast: u32, shared: &u32, #[case] _n: u32) {
+    assert_eq!(46, slow + fast + shared);
+}
+
+#[rstest]
+fn with_partial(#[with(1)] fast: u32, shared: &u32) {
+    assert_eq!(4, fast + shared);
+}
+
+#[rstest]
+async fn async_test(#[future(awt)] async_slow: u32) {
+    assert_eq!(42, async_slow);
+}
+
+#[rstest]
+fn dependent(on_slow: u32) {
+    assert_eq!(43, on_slow);
+}
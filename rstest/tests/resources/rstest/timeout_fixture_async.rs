@@ -0,0 +1,13 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+#[timeout(Duration::from_millis(80))]
+async fn slow() -> u32 {
+    42
+}
+
+#[rstest]
+async fn use_slow(#[future(awt)] slow: u32) {
+    assert_eq!(42, slow);
+}
@@ -0,0 +1,13 @@
+use rstest::*;
+use std::time::Duration;
+
+#[fixture]
+#[timeout(Duration::from_millis(80))]
+fn fast() -> u32 {
+    42
+}
+
+#[rstest]
+fn use_fast(fast: u32) {
+    assert_eq!(42, fast);
+}
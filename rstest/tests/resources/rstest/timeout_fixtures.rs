@@ -0,0 +1,84 @@
+use rstest::*;
+use std::time::Duration;
+
+fn ms(ms: u32) -> Duration {
+    Duration::from_millis(ms.into())
+}
+
+mod thread {
+    use super::*;
+
+    #[fixture]
+    fn slow() -> u32 {
+        std::thread::sleep(ms(200));
+        42
+    }
+
+    #[fixture]
+    #[timeout(ms(10))]
+    fn slow_with_timeout() -> u32 {
+        std::thread::sleep(ms(200));
+        42
+    }
+
+    #[fixture]
+    #[timeout(ms(500))]
+    fn fast_with_timeout() -> u32 {
+        42
+    }
+
+    #[rstest]
+    #[timeout(ms(50))]
+    fn test_timeout_covers_fixtures(slow: u32) {
+        assert_eq!(42, slow);
+    }
+
+    #[rstest]
+    fn fixture_timeout_fail(slow_with_timeout: u32) {
+        assert_eq!(42, slow_with_timeout);
+    }
+
+    #[rstest]
+    fn fixture_timeout_pass(fast_with_timeout: u32) {
+        assert_eq!(42, fast_with_timeout);
+    }
+}
+
+mod async_std_cases {
+    use super::*;
+
+    #[fixture]
+    async fn slow() -> u32 {
+        async_std::task::sleep(ms(200)).await;
+        42
+    }
+
+    #[fixture]
+    #[timeout(ms(10))]
+    async fn slow_with_timeout() -> u32 {
+        async_std::task::sleep(ms(200)).await;
+        42
+    }
+
+    #[fixture]
+    #[timeout(ms(500))]
+    async fn fast_with_timeout() -> u32 {
+        42
+    }
+
+    #[rstest]
+    #[timeout(ms(50))]
+    async fn test_timeout_covers_fixtures(#[future(awt)] slow: u32) {
+        assert_eq!(42, slow);
+    }
+
+    #[rstest]
+    async fn fixture_timeout_fail(#[future(awt)] slow_with_timeout: u32) {
+        assert_eq!(42, slow_with_timeout);
+    }
+
+    #[rstest]
+    async fn fixture_timeout_pass(#[future(awt)] fast_with_timeout: u32) {
+        assert_eq!(42, fast_with_timeout);
+    }
+}
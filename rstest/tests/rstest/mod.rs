@@ -74,9 +74,117 @@ fn files() {
         .ok("start_with_name_with_include::path_4_files_element_2_txt")
         .ok("start_with_name_with_include::path_5_files_element_3_txt")
         .ok("start_with_name_with_include::path_6_files_sub_sub_dir_file_txt")
+        .ok("contents_as_str::contents_1_files_element_0_txt")
+        .ok("contents_as_str::contents_2_files_element_1_txt")
+        .ok("contents_as_str::contents_3_files_element_2_txt")
+        .ok("contents_as_str::contents_4_files_element_3_txt")
+        .ok("contents_as_bytes::contents_1_files_element_0_txt")
+        .ok("contents_as_bytes::contents_2_files_element_1_txt")
+        .ok("contents_as_bytes::contents_3_files_element_2_txt")
+        .ok("contents_as_bytes::contents_4_files_element_3_txt")
         .assert(output);
 }
 
+#[test]
+fn files_relocatable() {
+    let mut prj = prj("files_relocatable.rs");
+    let relocated = prj.path().join("relocated");
+    for base in [prj.path().to_owned(), relocated.clone()] {
+        std::fs::create_dir_all(base.join("files")).unwrap();
+        for name in ["a.txt", "b.txt"] {
+            let contents = if base == relocated { "relocated" } else { "built" };
+            std::fs::write(base.join("files").join(name), contents).unwrap();
+        }
+    }
+    prj.set_env("RSTEST_FILES_BASE", &relocated);
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("relocated::path_1_files_a_txt")
+        .ok("relocated::path_2_files_b_txt")
+        .assert(output);
+}
+
+#[test]
+fn files_tracked_by_build_script() {
+    let prj = prj("files_tracked.rs");
+    prj.add_build_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", default-features=false, features=["build"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    std::fs::write(
+        prj.path().join("build.rs"),
+        "fn main() {\n    rstest::build::track_files();\n}\n",
+    )
+    .unwrap();
+    let files = prj.path().join("files");
+    std::fs::create_dir(&files).unwrap();
+    std::fs::write(files.join("a.txt"), "a").unwrap();
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("tracked::path_1_files_a_txt")
+        .assert(output);
+
+    std::fs::write(files.join("b.txt"), "b").unwrap();
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("tracked::path_1_files_a_txt")
+        .ok("tracked::path_2_files_b_txt")
+        .assert(output);
+}
+
+fn files_siblings_prj(files: &[(&str, &str)]) -> Project {
+    let prj = prj("files_siblings.rs");
+    let cases = prj.path().join("cases");
+    std::fs::create_dir(&cases).unwrap();
+    for (name, contents) in files {
+        std::fs::write(cases.join(name), contents).unwrap();
+    }
+    prj
+}
+
+#[test]
+fn files_siblings() {
+    let prj = files_siblings_prj(&[
+        ("ok.input", "hello"),
+        ("ok.expected", "HELLO"),
+        ("wrong.input", "hello"),
+        ("wrong.expected", "hi"),
+    ]);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("golden::input_1_cases_ok")
+        .fail("golden::input_2_cases_wrong")
+        .ok("paths::input_1_cases_ok")
+        .ok("paths::input_2_cases_wrong")
+        .assert(output);
+}
+
+#[test]
+fn files_siblings_missing() {
+    let prj = files_siblings_prj(&[
+        ("ok.input", "hello"),
+        ("ok.expected", "HELLO"),
+        ("missed.input", "hello"),
+    ]);
+
+    let output = prj.run_tests().unwrap();
+
+    assert_in!(
+        output.stderr.str(),
+        "Missing sibling `cases/missed.expected` of `cases/missed.input`"
+    );
+}
+
 #[test]
 fn mutable_input() {
     let (output, _) = run_test("mut.rs");
@@ -971,6 +1079,118 @@ fn ignore_underscore_args() {
         .assert(output);
 }
 
+#[test]
+fn autouse() {
+    let prj = prj("autouse.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single")
+        .ok("not_twice_if_argument")
+        .ok("await_async_fixtures")
+        .ok("module::single")
+        .ok("module::cases::case_1")
+        .ok("module::cases::case_2")
+        .ok("module::matrix::_v_1_1")
+        .ok("module::matrix::_v_2_2")
+        .ok("module::plain_test")
+        .ok("module::nested::single")
+        .assert(output);
+}
+
+#[test]
+fn context() {
+    let prj = prj("context.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("single")
+        .ok("cases::case_1_first")
+        .ok("cases::case_2")
+        .ok("cases::case_3_third")
+        .ok("values::_x_1_1::_y_1___a__")
+        .ok("values::_x_2_2::_y_1___a__")
+        .ok("case_values::case_1::_x_1_3")
+        .ok("module::in_module")
+        .assert(output);
+}
+
+#[test]
+fn lazy() {
+    let prj = prj("lazy.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("resolve_when_used")
+        .ok("resolve_only_if_used::case_1")
+        .fail("resolve_only_if_used::case_2")
+        .ok("honor_with")
+        .ok("capture_not_send_values::case_1")
+        .ok("async_fixture")
+        .ok("matrix::_v_1_1")
+        .ok("matrix::_v_2_2")
+        .assert(output);
+}
+
+#[test]
+fn by_ref() {
+    let prj = prj("by_ref.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("borrow_fixture")
+        .ok("more_borrowers")
+        .ok("with_cases::case_1")
+        .ok("with_cases::case_2")
+        .fail("fail")
+        .assert(output);
+}
+
+#[test]
+fn case_fixtures() {
+    let prj = prj("case_fixtures.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("positional::case_1")
+        .ok("positional::case_2")
+        .ok("named::case_1")
+        .ok("named::case_2_admin")
+        .ok("named::case_3")
+        .ok("replace_test_with::case_1")
+        .ok("replace_test_with::case_2")
+        .ok("matrix::case_1::_v_1_1")
+        .ok("matrix::case_1::_v_2_2")
+        .ok("matrix::case_2::_v_1_1")
+        .ok("matrix::case_2::_v_2_2")
+        .fail("fail::case_1")
+        .assert(output);
+}
+
+#[test]
+fn concurrent() {
+    let prj = prj("concurrent.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("global_awt")
+        .ok("selective_awt")
+        .ok("in_fixture")
+        .fail("sequential")
+        .ok("spawn_send_fixture")
+        .assert(output);
+}
+
 #[test]
 fn timeout() {
     let mut prj = prj("timeout.rs");
@@ -1021,6 +1241,147 @@ fn timeout() {
         .assert(output);
 }
 
+fn cases_from_prj(features: &[&str]) -> Project {
+    let prj = crate::base_prj().set_code_file(resources("cases_from.rs"));
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features=[{}]}}"#,
+            prj.exec_dir_str(),
+            features
+                .iter()
+                .map(|f| format!(r#""{f}""#))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    );
+    let data = prj.path().join("data");
+    std::fs::create_dir(&data).unwrap();
+    std::fs::write(data.join("sum.csv"), "a, b, sum\n1, 2, 3\n2, 2, 5\n").unwrap();
+    std::fs::write(
+        data.join("sum.json"),
+        r#"[{"a": 1, "b": 2, "sum": 3}, {"sum": "4", "a": "2", "b": "2"}]"#,
+    )
+    .unwrap();
+    std::fs::write(
+        data.join("sum.toml"),
+        "[[case]]\na = 1\nb = 2\nsum = 3\n\n[[case]]\na = 2\nb = 2\nsum = 5\n",
+    )
+    .unwrap();
+    std::fs::write(data.join("addresses.txt"), "10.0.0.1:8080\n\n[::1]:0\n").unwrap();
+    std::fs::write(data.join("names.txt"), "bob\nal\n").unwrap();
+    prj
+}
+
+#[test]
+fn cases_from() {
+    let prj = cases_from_prj(&["cases-csv", "cases-json", "cases-toml"]);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("csv::case_1")
+        .fail("csv::case_2")
+        .ok("json::case_1")
+        .ok("json::case_2")
+        .ok("toml::case_1")
+        .fail("toml::case_2")
+        .ok("lines::case_1")
+        .ok("lines::case_2")
+        .fail("lines::case_3")
+        .ok("matrix::case_1::n_1_1")
+        .ok("matrix::case_1::n_2_2")
+        .ok("matrix::case_2::n_1_1")
+        .fail("matrix::case_2::n_2_2")
+        .assert(output);
+}
+
+#[test]
+fn cases_from_needs_the_format_feature() {
+    let prj = cases_from_prj(&["cases-json"]);
+
+    let output = prj.run_tests().unwrap();
+    let stderr = output.stderr.str();
+
+    assert_in!(
+        stderr,
+        "enable the `cases-csv` feature of rstest to read CSV files"
+    );
+    assert_in!(
+        stderr,
+        "enable the `cases-toml` feature of rstest to read TOML files"
+    );
+    assert_not_in!(stderr, "cases-json");
+}
+
+#[test]
+fn profile_fixtures() {
+    let mut prj = prj("profile.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    prj.set_env("RSTEST_PROFILE", "1");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sync_test::case_1")
+        .ok("sync_test::case_2")
+        .ok("with_partial")
+        .ok("async_test")
+        .ok("dependent")
+        .assert(output.clone());
+
+    let stderr = output.stderr.str();
+    let path = stderr
+        .lines()
+        .find_map(|l| l.strip_prefix("rstest profile report written to "))
+        .expect("Should write the profile report");
+    let report = std::fs::read_to_string(path).unwrap();
+    let stats = |fixture: &str| {
+        report
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>())
+            .find(|cols| cols[0] == fixture)
+            .map(|cols| (cols[1].to_owned(), cols[2].to_owned(), cols[3].to_owned()))
+            .unwrap_or_else(|| panic!("Missed fixture {fixture} in report:\n{report}"))
+    };
+    let calls = |fixture: &str| {
+        let (calls, once_hits, _) = stats(fixture);
+        (calls, once_hits)
+    };
+
+    assert_eq!(("3".to_owned(), "0".to_owned()), calls("slow"));
+    assert_eq!(("3".to_owned(), "0".to_owned()), calls("fast"));
+    assert_eq!(("3".to_owned(), "2".to_owned()), calls("shared"));
+    assert_eq!(("1".to_owned(), "0".to_owned()), calls("async_slow"));
+    assert_eq!(("1".to_owned(), "0".to_owned()), calls("on_slow"));
+    // The report gives the self time: `on_slow` doesn't count the 20ms of `slow`
+    let (_, _, on_slow_total) = stats("on_slow");
+    assert!(
+        on_slow_total.ends_with("µs") || on_slow_total.ends_with("ns"),
+        "{report}"
+    );
+}
+
+#[test]
+fn timeout_fixtures() {
+    let prj = prj("timeout_fixtures.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .fail("thread::test_timeout_covers_fixtures")
+        .fail("thread::fixture_timeout_fail")
+        .ok("thread::fixture_timeout_pass")
+        .fail("async_std_cases::test_timeout_covers_fixtures")
+        .fail("async_std_cases::fixture_timeout_fail")
+        .ok("async_std_cases::fixture_timeout_pass")
+        .assert(output.clone());
+
+    assert_in!(
+        output.stdout.str(),
+        "resolving fixture `slow_with_timeout`"
+    );
+}
+
 mod async_timeout_feature {
     use super::*;
 
@@ -1064,6 +1425,29 @@ mod async_timeout_feature {
 
         TestResults::new().ok("single_pass").assert(output);
     }
+
+    #[test]
+    fn should_not_compile_async_fixture_timeout_if_feature_disable() {
+        let prj = build_prj(&[]);
+        let output = prj
+            .set_code_file(resources("timeout_fixture_async.rs"))
+            .run_tests()
+            .unwrap();
+
+        assert_in!(output.stderr.str(), "error: Enable async-timeout feature");
+        assert_not_in!(output.stderr.str(), "fixture_with_timeout_async");
+    }
+
+    #[test]
+    fn should_resolve_sync_fixture_timeout_if_feature_disable() {
+        let prj = build_prj(&[]);
+        let output = prj
+            .set_code_file(resources("timeout_fixture_sync.rs"))
+            .run_tests()
+            .unwrap();
+
+        TestResults::new().ok("use_fast").assert(output);
+    }
 }
 
 mod should_show_correct_errors {
@@ -1587,4 +1971,54 @@ mod should_show_correct_errors {
             .unindent()
         );
     }
+
+    #[test]
+    fn if_async_autouse_fixture_in_sync_test() {
+        let (output, name) = execute();
+
+        assert_regex!(
+            format!(
+                r#"error\[E0308\]: mismatched types\s+--> {name}/src/lib.rs:126:11\s+\|
+126 \| #\[autouse\(async_setup\)\]"#
+            ),
+            output.stderr.str()
+        );
+        assert_in!(output.stderr.str(), "AsyncFixtureInSyncTest");
+    }
+
+    #[test]
+    fn if_group_argument_is_given_by_a_test_argument() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: Cannot use the test argument 'base' in #[with(...)] of a #[scope(group)] argument: its value is shared among all the tests of the group.
+                   --> {name}/src/lib.rs:140:12
+                    |
+                140 |     #[with(base)]
+                    |            ^^^^"
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn if_cases_from_has_an_unsupported_format() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: data/cases.yaml: unsupported `yaml` file: use CSV (`cases-csv` feature of rstest), JSON (`cases-json` feature), TOML (`cases-toml` feature) or a text file with a case per line (`.txt` or no extension)
+                   --> {name}/src/lib.rs:146:1
+                    |
+                146 | #[cases_from("data/cases.yaml")]
+                    | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"#
+            )
+            .unindent()
+        );
+    }
 }
@@ -1,22 +1,6 @@
 use std::fmt::Debug;
 
-pub trait TearDown {
-    fn tear_down(self);
-}
-
-#[derive(Default)]
-pub struct EmptyGuard {}
-
-impl TearDown for EmptyGuard {
-    fn tear_down(self) {}
-}
-
-impl<A: TearDown, B: TearDown> TearDown for (A, B) {
-    fn tear_down(self) {
-        self.0.tear_down();
-        self.1.tear_down();
-    }
-}
+pub use rstest::teardown::{EmptyGuard, TearDown};
 
 pub struct Fixture<T, G: TearDown> {
     inner: Option<T>,
@@ -92,6 +76,16 @@ mod test {
         assert_eq!(*guard.borrow(), vec!["destroyed"]);
     }
 
+    #[test]
+    fn plain_closure_should_be_used_as_guard() {
+        let guard = RefCell::new(vec![]);
+        {
+            let _fixture = Fixture::new(42, || guard.borrow_mut().push("destroyed"));
+        }
+
+        assert_eq!(*guard.borrow(), vec!["destroyed"]);
+    }
+
     #[test]
     fn tear_down_closure_should_can_move_all_needed_data() {
         let guard = Rc::new(RefCell::new(vec![]));
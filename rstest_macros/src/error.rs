@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 use proc_macro2::TokenStream;
 use syn::{spanned::Spanned, visit::Visit};
-use syn::{visit, ItemFn};
+use syn::{visit, Ident, ItemFn};
 
 use crate::parse::{
     fixture::FixtureInfo,
@@ -11,13 +11,15 @@ use crate::parse::{
 };
 use crate::refident::MaybeIdent;
 
-use super::utils::fn_args_has_ident;
+use super::utils::{attr_in, fn_args_has_ident, fn_args_idents, result_ok_type};
 
 pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
         .chain(invalid_cases(&info.data))
         .chain(case_args_without_cases(&info.data))
+        .chain(invalid_group_arguments(test, info))
+        .chain(invalid_case_fixtures(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
@@ -25,22 +27,16 @@ pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
 pub(crate) fn fixture(test: &ItemFn, info: &FixtureInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
-        .chain(async_once(test, info))
         .chain(generics_once(test, info))
+        .chain(parametrized_fixture(test))
+        .chain(invalid_parametrized_fixture(test, info))
+        .chain(fallible_without_result(test, info))
+        .chain(provides_without_concrete_type(test, info))
+        .chain(timeout_with_borrowed_arguments(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
 
-fn async_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (test.sig.asyncness, info.arguments.get_once()) {
-        (Some(_asyncness), Some(once)) => Box::new(std::iter::once(syn::Error::new(
-            once.span(),
-            "Cannot apply #[once] to async fixture.",
-        ))),
-        _ => Box::new(std::iter::empty()),
-    }
-}
-
 #[derive(Default)]
 struct SearchImpl(bool);
 
@@ -64,12 +60,18 @@ impl SearchImpl {
     }
 }
 
-fn has_some_generics(test: &ItemFn) -> bool {
-    !test.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(test)
+// `#[once]` fixtures store a value for each concrete type, so just type generics are supported
+fn has_some_not_type_generics(test: &ItemFn) -> bool {
+    test.sig
+        .generics
+        .params
+        .iter()
+        .any(|p| !matches!(p, syn::GenericParam::Type(_)))
+        || SearchImpl::function_has_some_impl(test)
 }
 
 fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (has_some_generics(test), info.arguments.get_once()) {
+    match (has_some_not_type_generics(test), info.arguments.get_once()) {
         (true, Some(once)) => Box::new(std::iter::once(syn::Error::new(
             once.span(),
             "Cannot apply #[once] on generic fixture.",
@@ -78,6 +80,150 @@ fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
     }
 }
 
+fn fallible_without_result<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (
+        result_ok_type(&test.sig.output),
+        info.arguments.get_fallible(),
+    ) {
+        (None, Some(fallible)) => Box::new(std::iter::once(syn::Error::new(
+            fallible.span(),
+            "Cannot apply #[fallible] on a fixture that doesn't return a `Result<T, E>`.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+// `rstest::Provide::provide()` is sync and should be implemented for a single known type.
+fn provides_without_concrete_type<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    let provides = match info.attributes.provides() {
+        Some(provides) => provides,
+        None => return Box::new(std::iter::empty()),
+    };
+    let message = if test.sig.asyncness.is_some() {
+        "Cannot use provides on an async fixture: `rstest::Provide` is sync."
+    } else if !test.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(test) {
+        "Cannot use provides on a generic fixture: it should return a concrete type."
+    } else if matches!(test.sig.output, syn::ReturnType::Default) {
+        "Cannot use provides on a fixture that doesn't return a value."
+    } else if fn_args_idents(test).any(|arg| info.arguments.is_by_ref(arg)) {
+        "Cannot use provides on a fixture with #[by_ref] arguments: they should be given."
+    } else if matches!(&test.sig.output, syn::ReturnType::Type(_, ty) if is_foreign_type(ty)) {
+        "Cannot use provides on a fixture that returns a type defined outside your crate: \
+        the orphan rule forbids implementing `Provide` for it. Wrap it in a newtype."
+    } else {
+        return Box::new(std::iter::empty());
+    };
+    Box::new(std::iter::once(syn::Error::new(provides.span(), message)))
+}
+
+// The types that surely come from another crate (primitives, tuples, slices, the standard
+// library's ones...): references, `Box` and `Pin` are fundamental and just wrap a type
+// that could be local.
+fn is_foreign_type(ty: &syn::Type) -> bool {
+    const PRELUDE: &[&str] = &[
+        "bool", "char", "str", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32",
+        "u64", "u128", "usize", "f32", "f64", "String", "Vec", "Option", "Result",
+    ];
+    match ty {
+        syn::Type::Group(g) => is_foreign_type(&g.elem),
+        syn::Type::Paren(p) => is_foreign_type(&p.elem),
+        syn::Type::Reference(r) => is_foreign_type(&r.elem),
+        syn::Type::Path(p) if p.qself.is_none() => {
+            let path = &p.path;
+            let (first, last) = match (path.segments.first(), path.segments.last()) {
+                (Some(first), Some(last)) => (first.ident.to_string(), last),
+                _ => return false,
+            };
+            match &last.arguments {
+                syn::PathArguments::AngleBracketed(args)
+                    if last.ident == "Box" || last.ident == "Pin" =>
+                {
+                    args.args.iter().any(
+                        |arg| matches!(arg, syn::GenericArgument::Type(ty) if is_foreign_type(ty)),
+                    )
+                }
+                _ if path.segments.len() > 1 => {
+                    path.leading_colon.is_some()
+                        || ["std", "core", "alloc"].contains(&first.as_str())
+                }
+                _ => PRELUDE.contains(&first.as_str()),
+            }
+        }
+        syn::Type::Array(_)
+        | syn::Type::BareFn(_)
+        | syn::Type::Never(_)
+        | syn::Type::Ptr(_)
+        | syn::Type::Slice(_)
+        | syn::Type::TraitObject(_)
+        | syn::Type::Tuple(_) => true,
+        _ => false,
+    }
+}
+
+// A sync fixture with a timeout is executed in another thread that cannot borrow the
+// caller's values.
+fn timeout_with_borrowed_arguments<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match info.arguments.get_timeout() {
+        Some(timeout)
+            if test.sig.asyncness.is_none()
+                && fn_args_idents(test).any(|arg| info.arguments.is_by_ref(arg)) =>
+        {
+            Box::new(std::iter::once(syn::Error::new_spanned(
+                timeout,
+                "Cannot use #[timeout] on a sync fixture with #[by_ref] arguments: \
+                it's resolved in another thread.",
+            )))
+        }
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+// A fixture is parametrized just by the `#[values(...)]` of its arguments: each variant
+// is a combination of them.
+fn parametrized_fixture(test: &ItemFn) -> Errors<'_> {
+    Box::new(
+        test.attrs
+            .iter()
+            .chain(
+                test.sig
+                    .inputs
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::FnArg::Typed(arg) => Some(arg.attrs.iter()),
+                        _ => None,
+                    })
+                    .flatten(),
+            )
+            .filter(|&attr| attr_in(attr, &["values", "case", "cases_from"]))
+            .map(|attr| {
+                syn::Error::new_spanned(
+                    attr,
+                    "Cannot parametrize a fixture by this attribute: use #[values(...)] on \
+                    its arguments.",
+                )
+            }),
+    )
+}
+
+// The variants of a parametrized fixture are resolved by the tests that mark the argument
+// by `#[values]`: they cannot share a single value or have a default one.
+fn invalid_parametrized_fixture<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    let vlist = match info.data.list_values().next() {
+        Some(vlist) => vlist,
+        None => return Box::new(std::iter::empty()),
+    };
+    let message = if info.arguments.is_once() {
+        "Cannot apply #[once] on a parametrized fixture: each variant has its own value."
+    } else if info.attributes.provides().is_some() {
+        "Cannot use provides on a parametrized fixture: it doesn't have a default value."
+    } else if fn_args_idents(test).any(|arg| info.arguments.is_by_ref(arg)) {
+        "Cannot parametrize a fixture with #[by_ref] arguments: they should be given."
+    } else {
+        return Box::new(std::iter::empty());
+    };
+    Box::new(std::iter::once(syn::Error::new(vlist.arg.span(), message)))
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct ErrorsVec(Vec<syn::Error>);
 
@@ -225,21 +371,127 @@ fn case_args_without_cases(params: &RsTestData) -> Errors {
     Box::new(std::iter::empty())
 }
 
+fn invalid_group_arguments<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    let is_generic = test.sig.generics.type_params().next().is_some();
+    Box::new(
+        test.sig
+            .inputs
+            .iter()
+            .filter_map(MaybeIdent::maybe_ident)
+            .filter(move |&ident| info.arguments.is_group(ident))
+            .filter_map(move |ident| {
+                let message = if info.data.case_args().any(|a| a == ident) {
+                    "Cannot use #[scope(group)] on a case argument."
+                } else if info.data.list_values().any(|v| &v.arg == ident) {
+                    "Cannot use #[scope(group)] on a values list argument."
+                } else if info.arguments.is_future(ident) {
+                    "Cannot use #[scope(group)] on a future argument."
+                } else if is_generic {
+                    "Cannot use #[scope(group)] in generic tests."
+                } else if let Some(used) = test_argument_in_with(test, info, ident) {
+                    return Some(syn::Error::new(
+                        used.span(),
+                        format!(
+                            "Cannot use the test argument '{used}' in #[with(...)] of a \
+                            #[scope(group)] argument: its value is shared among all the tests \
+                            of the group."
+                        ),
+                    ));
+                } else {
+                    return None;
+                };
+                Some(syn::Error::new(ident.span(), message))
+            }),
+    )
+}
+
+/// The first test argument used by the `#[with(...)]` expressions of the `fixture`.
+fn test_argument_in_with<'a>(
+    test: &ItemFn,
+    info: &'a RsTestInfo,
+    fixture: &Ident,
+) -> Option<&'a Ident> {
+    struct UsedArgument<'a, 'b> {
+        arguments: Vec<&'b Ident>,
+        used: Option<&'a Ident>,
+    }
+
+    impl<'a> Visit<'a> for UsedArgument<'a, '_> {
+        fn visit_expr_path(&mut self, expr: &'a syn::ExprPath) {
+            match expr.path.get_ident() {
+                Some(ident) if self.used.is_none() && self.arguments.contains(&ident) => {
+                    self.used = Some(ident)
+                }
+                _ => visit::visit_expr_path(self, expr),
+            }
+        }
+    }
+
+    let mut visitor = UsedArgument {
+        arguments: fn_args_idents(test).collect(),
+        used: None,
+    };
+    for fixture in info.data.fixtures().filter(|f| &f.name == fixture) {
+        fixture
+            .positional
+            .0
+            .iter()
+            .chain(fixture.named.iter().map(|arg| &arg.expr))
+            .for_each(|expr| visitor.visit_expr(expr));
+    }
+    visitor.used
+}
+
+// The fixtures given by `#[with(...)]` on a case replace the ones injected in the test's
+// arguments just for that case.
+fn invalid_case_fixtures<'a>(test: &'a ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    Box::new(
+        info.data
+            .cases()
+            .flat_map(|case| case.fixtures.iter())
+            .filter_map(move |fixture| {
+                let ident = &fixture.name;
+                let message = if !fn_args_has_ident(test, ident) {
+                    format!("Missed argument: '{ident}' should be a test function argument.")
+                } else if info.data.case_args().any(|a| a == ident)
+                    || info.data.list_values().any(|v| &v.arg == ident)
+                {
+                    format!("Cannot give '{ident}' by #[with(...)] on a case: it's not a fixture.")
+                } else if info.arguments.is_group(ident) {
+                    format!(
+                        "Cannot give '{ident}' by #[with(...)] on a case: #[scope(group)] \
+                        arguments are shared among all cases."
+                    )
+                } else if info.arguments.is_by_type(ident) {
+                    format!("Cannot give '{ident}' by #[with(...)] on a case: it's resolved by type.")
+                } else {
+                    return None;
+                };
+                Some(syn::Error::new(ident.span(), message))
+            }),
+    )
+}
+
 #[cfg(test)]
 mod test {
+    use crate::parse::ExtendWithFunctionAttrs;
     use crate::test::{assert_eq, *};
     use rstest_test::assert_in;
 
     use super::*;
 
     #[rstest]
-    #[case::generics("fn f<G: SomeTrait>(){}")]
     #[case::const_generics("fn f<const N: usize>(){}")]
     #[case::lifetimes("fn f<'a>(){}")]
+    #[case::mixed_with_type_generics("fn f<'a, G: SomeTrait>(){}")]
     #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}")]
     #[case::use_impl_in_argumets("fn f(it: impl Iterator<Item=u32>){}")]
     #[should_panic]
     #[case::sanity_check_with_no_generics("fn f() {}")]
+    #[should_panic]
+    #[case::type_generics("fn f<G: SomeTrait>() -> G {}")]
+    #[should_panic]
+    #[case::type_generics_in_where_clause("fn f<G, H>(h: H) -> G where G: From<H> {}")]
     fn generics_once_should_return_error(#[case] f: &str) {
         let f: ItemFn = f.ast();
         let info = FixtureInfo::default().with_once();
@@ -254,6 +506,103 @@ mod test {
         assert_in!(out, "Cannot apply #[once] on generic fixture.");
     }
 
+    #[rstest]
+    #[case::case("fn f(#[case] backend: Backend) {}")]
+    #[case::function_values("#[values(Backend::Mem, Backend::Disk)] fn f() {}")]
+    #[case::cases_from(r#"#[cases_from("data.csv")] fn f() {}"#)]
+    fn parametrized_fixture_should_return_error(#[case] f: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = parametrized_fixture(&f).collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(format!("{:?}", errors[0]), "Cannot parametrize a fixture");
+    }
+
+    #[rstest]
+    #[case::once("#[once] fn f(#[values(1, 2)] a: u32) {}", "Cannot apply #[once]")]
+    #[case::by_ref(
+        "fn f(#[values(1, 2)] a: u32, #[by_ref] b: &u32) {}",
+        "#[by_ref] arguments"
+    )]
+    fn invalid_parametrized_fixture_should_return_error(#[case] f: &str, #[case] message: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = invalid_parametrized_fixture(&f, &info).collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(format!("{:?}", errors[0]), message);
+    }
+
+    #[test]
+    fn values_on_fixture_arguments_should_not_return_error() {
+        let mut f: ItemFn =
+            "fn f(#[values(Backend::Mem, Backend::Disk)] backend: Backend) {}".ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        assert!(parametrized_fixture(&f)
+            .chain(invalid_parametrized_fixture(&f, &info))
+            .next()
+            .is_none());
+    }
+
+    #[rstest]
+    #[case::no_result("#[fallible] fn f() -> Db {}", 1)]
+    #[case::no_return_type("#[fallible] fn f() {}", 1)]
+    #[case::result("#[fallible] fn f() -> Result<Db, Error> {}", 0)]
+    #[case::result_alias("#[fallible] fn f() -> io::Result<Db> {}", 0)]
+    #[case::not_fallible("fn f() -> Db {}", 0)]
+    fn fallible_without_result_should_return_error(#[case] f: &str, #[case] expected: usize) {
+        let mut f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = fallible_without_result(&f, &info).collect::<Vec<_>>();
+
+        assert_eq!(expected, errors.len());
+        if expected > 0 {
+            assert_in!(format!("{:?}", errors[0]), "Cannot apply #[fallible]");
+        }
+    }
+
+    #[rstest]
+    #[case::async_fixture("async fn f() -> Db {}", Some("async fixture"))]
+    #[case::generics("fn f<T>() -> T {}", Some("generic fixture"))]
+    #[case::impl_return("fn f() -> impl Db {}", Some("generic fixture"))]
+    #[case::no_return_type("fn f() {}", Some("doesn't return a value"))]
+    #[case::by_ref("fn f(#[by_ref] a: &A) -> Db {}", Some("#[by_ref] arguments"))]
+    #[case::concrete("fn f(a: u32) -> Db {}", None)]
+    #[case::primitive("fn f() -> u16 {}", Some("outside your crate"))]
+    #[case::std_type("fn f() -> std::path::PathBuf {}", Some("outside your crate"))]
+    #[case::prelude_generic("fn f() -> Vec<Db> {}", Some("outside your crate"))]
+    #[case::tuple("fn f() -> (Db, Db) {}", Some("outside your crate"))]
+    #[case::other_crate("fn f() -> ::serde::Value {}", Some("outside your crate"))]
+    #[case::boxed_foreign("fn f() -> Box<str> {}", Some("outside your crate"))]
+    #[case::boxed_local("fn f() -> Box<Db> {}", None)]
+    #[case::std_boxed_local("fn f() -> std::boxed::Box<Db> {}", None)]
+    #[case::local_reference("fn f() -> &'static Db {}", None)]
+    #[case::local_module("fn f() -> db::Db {}", None)]
+    fn provides_without_concrete_type_should_return_error(
+        #[case] f: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut f: ItemFn = f.ast();
+        let mut info: FixtureInfo = "provides".ast();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = provides_without_concrete_type(&f, &info).collect::<Vec<_>>();
+
+        match expected {
+            Some(message) => assert_in!(format!("{:?}", errors[0]), message),
+            None => assert!(errors.is_empty()),
+        }
+    }
+
     #[rstest]
     #[case::generics("fn f<G: SomeTrait>(){}")]
     #[case::const_generics("fn f<const N: usize>(){}")]
@@ -268,4 +617,122 @@ mod test {
 
         assert_eq!(0, errors.count());
     }
+
+    #[rstest]
+    #[case::case_arg(
+        "fn f(#[scope(group)] #[case] a: &u32) {}",
+        "Cannot use #[scope(group)] on a case argument."
+    )]
+    #[case::values_arg(
+        "fn f(#[scope(group)] #[values(&1, &2)] a: &u32) {}",
+        "Cannot use #[scope(group)] on a values list argument."
+    )]
+    #[case::future_arg(
+        "fn f(#[scope(group)] #[future] a: &u32) {}",
+        "Cannot use #[scope(group)] on a future argument."
+    )]
+    #[case::generic_test(
+        "fn f<T>(#[scope(group)] a: &u32, t: T) {}",
+        "Cannot use #[scope(group)] in generic tests."
+    )]
+    #[case::with_test_argument(
+        "#[case(40)] fn f(#[case] base: u32, #[scope(group)] #[with(base)] a: &u32) {}",
+        "Cannot use the test argument 'base' in #[with(...)] of a #[scope(group)] argument"
+    )]
+    #[case::with_test_argument_in_expression(
+        "#[case(40)] fn f(#[case] base: u32, #[scope(group)] #[with(2 * base.pow(2))] a: &u32) {}",
+        "Cannot use the test argument 'base'"
+    )]
+    fn invalid_group_arguments_should_return_error(#[case] f: &str, #[case] message: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = invalid_group_arguments(&f, &info)
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0], message);
+    }
+
+    #[rstest]
+    #[case::constant("fn f(#[scope(group)] #[with(42)] a: &u32) {}")]
+    #[case::not_an_argument("fn f(base: u32, #[scope(group)] #[with(BASE + 1)] a: &u32) {}")]
+    #[case::other_fixture("fn f(base: u32, #[scope(group)] #[with(other.base)] a: &u32) {}")]
+    fn group_arguments_with_values_not_from_the_test_should_not_return_error(#[case] f: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        assert_eq!(0, invalid_group_arguments(&f, &info).count());
+    }
+
+    #[rstest]
+    #[case::not_an_argument(
+        "#[with(b(42))] #[case(1)] fn f(#[case] a: u32) {}",
+        "Missed argument: 'b' should be a test function argument."
+    )]
+    #[case::case_arg(
+        "#[with(a(42))] #[case(1)] fn f(#[case] a: u32) {}",
+        "it's not a fixture"
+    )]
+    #[case::values_arg(
+        "#[with(b(42))] #[case(1)] fn f(#[case] a: u32, #[values(1, 2)] b: u32) {}",
+        "it's not a fixture"
+    )]
+    #[case::group_arg(
+        "#[with(b(42))] #[case(1)] fn f(#[case] a: u32, #[scope(group)] b: &u32) {}",
+        "arguments are shared among all cases"
+    )]
+    #[case::by_type_arg(
+        "#[with(b(42))] #[case(1)] fn f(#[case] a: u32, #[by_type] b: u32) {}",
+        "it's resolved by type"
+    )]
+    fn invalid_case_fixtures_should_return_error(#[case] f: &str, #[case] message: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = invalid_case_fixtures(&f, &info)
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0], message);
+    }
+
+    #[test]
+    fn invalid_case_fixtures_should_accept_fixtures() {
+        let mut f: ItemFn = "#[with(b(42))] #[case(1)] fn f(#[case] a: u32, b: u32) {}".ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        assert_eq!(0, invalid_case_fixtures(&f, &info).count());
+    }
+
+    #[rstest]
+    #[case::sync_by_ref(
+        "fn f(#[by_ref] a: &u32) -> u32 { *a }",
+        Some("Cannot use #[timeout] on a sync fixture with #[by_ref] arguments")
+    )]
+    #[case::async_by_ref("async fn f(#[by_ref] a: &u32) -> u32 { *a }", None)]
+    #[case::sync("fn f(a: u32) -> u32 { a }", None)]
+    fn timeout_with_borrowed_arguments_should_return_error(
+        #[case] f: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+        // Async timeouts need the `async-timeout` feature: don't parse them
+        info.arguments.set_timeout(Some(expr("d")));
+
+        let errors = timeout_with_borrowed_arguments(&f, &info).collect::<Vec<_>>();
+
+        match expected {
+            Some(message) => assert_in!(format!("{:?}", errors[0]), message),
+            None => assert!(errors.is_empty()),
+        }
+    }
 }
@@ -15,9 +15,9 @@ mod render;
 mod resolver;
 mod utils;
 
-use syn::{parse_macro_input, ItemFn};
+use syn::{parse_macro_input, DeriveInput, Item, ItemFn};
 
-use crate::parse::{fixture::FixtureInfo, rstest::RsTestInfo};
+use crate::parse::{autouse::Autouse, bundle::Bundle, fixture::FixtureInfo, rstest::RsTestInfo};
 use parse::ExtendWithFunctionAttrs;
 use quote::ToTokens;
 
@@ -170,13 +170,185 @@ use quote::ToTokens;
 /// }
 /// ```
 ///
-/// There are some limitations when you use `#[once]` fixture. `rstest` forbid to use once fixture
-/// for:
+/// `#[once]` works also with `async` fixtures: the fixture is resolved just once for all
+/// the tests, whatever runtime each test uses, and the tests get a `&'static` reference
+/// to its value. The value should be `Sync` because it's shared among the test threads.
 ///
-/// - `async` function
-/// - Generic function (both with generic types or use `impl` trait)
+/// ```ignore
+/// use rstest::*;
+///
+/// #[fixture]
+/// #[once]
+/// async fn server_address() -> String {
+///     // Start the server just once...
+///     "127.0.0.1:8080".to_string()
+/// }
+///
+/// #[rstest]
+/// async fn ping(#[future(awt)] server_address: &String) {
+///     assert_eq!("127.0.0.1:8080", server_address)
+/// }
+/// ```
+///
+/// Generic `#[once]` fixtures are resolved just once for every concrete type used by
+/// your tests:
+///
+/// ```ignore
+/// use rstest::*;
+///
+/// #[fixture]
+/// #[once]
+/// fn codec<C: Default>() -> C {
+///     // Called once for `u32` and once for `String`
+///     C::default()
+/// }
+///
+/// #[rstest]
+/// fn use_codecs(codec: &u32, #[from(codec)] other: &String) {
+///     assert_eq!(&0, codec);
+///     assert!(other.is_empty());
+/// }
+/// ```
+///
+/// Generic types should be `'static` and the fixture's value should be `Send + Sync`:
+/// `rstest` adds these bounds for you. There are some limitations when you use `#[once]`
+/// fixture: `rstest` forbid to use once fixture for functions with lifetimes, const
+/// generics or that use `impl` trait.
+///
+/// The `#[once]` fixture's value is shared among all test threads, so it should be `Sync`.
+/// Take care that by default the `#[once]` fixture value will **never be dropped**: if it
+/// holds some resources that should be released (temporary folders, child processes...)
+/// you can use `#[teardown(expression)]` to tear it down when the test process exits.
+///
+/// ```ignore
+/// use rstest::*;
+/// # struct Server;
+/// # impl Server {
+/// #     fn start() -> Self { Server }
+/// #     fn stop(&self) {}
+/// # }
 ///
-/// Take care that the `#[once]` fixture value will **never be dropped**.
+/// #[fixture]
+/// #[once]
+/// #[teardown(move || server.stop())]
+/// fn server() -> Server {
+///     Server::start()
+/// }
+/// ```
+///
+/// `#[once]` teardowns run in the reverse order of their fixture's creation and should be
+/// `Send`. The value itself is never dropped, even after its teardown: threads of tests
+/// that timed out can still use it. Teardown failures at exit can just be printed on stderr.
+///
+/// If the `#[once]` fixture's initialization panics, the test that tried to initialize it
+/// fails with the original panic. The fixture is not initialized again: all the other tests
+/// that use it fail with a message that points to the first failure, like
+/// ``fixture `db` failed to initialize in test `x::case_1`: connection refused``.
+///
+/// # Teardown
+///
+/// Fixtures that create resources (temporary files, child processes, sockets...) can
+/// define how to release them with the `#[teardown(expression)]` attribute. `expression`
+/// should produce a [`TearDown`](https://docs.rs/rstest/latest/rstest/trait.TearDown.html)
+/// implementation (any `FnOnce()` closure is fine) and can access the fixture's value by
+/// the fixture name. The teardown is executed when the test that used the fixture ends,
+/// even if the test fails.
+///
+/// ```ignore
+/// use rstest::*;
+/// # use std::path::PathBuf;
+///
+/// #[fixture]
+/// #[teardown({
+///     let path = temp_file.clone();
+///     move || std::fs::remove_file(path).unwrap()
+/// })]
+/// fn temp_file() -> PathBuf {
+///     let path = std::env::temp_dir().join("rstest_teardown_doc");
+///     std::fs::write(&path, "some data").unwrap();
+///     path
+/// }
+///
+/// #[rstest]
+/// fn the_test(temp_file: PathBuf) {
+///     assert!(temp_file.exists())
+/// }
+/// ```
+///
+/// Teardowns run in the reverse order of their fixtures creation: a fixture is always
+/// torn down before the fixtures it depends on. A teardown that panics doesn't stop the
+/// others and makes the test fail with a `teardown of fixture `name` failed: <message>`
+/// error; if the test had already failed the teardown errors are just printed on stderr.
+///
+/// Async tests collect their teardowns in the test's future, so it doesn't matter which
+/// thread polls it. Otherwise teardowns are collected per thread, so a fixture resolved
+/// outside a `#[rstest]` test (i.e. by calling `temp_file::default()` by hand) is torn
+/// down when its thread exits. `#[teardown]` on a `#[once]` fixture is executed when the test process exits
+/// (see [`#[once]` Fixture](#once-fixture)).
+///
+/// # Fallible Fixtures
+///
+/// A fixture that can fail can return a `Result<T, E>` (or an alias like `io::Result<T>`)
+/// and use the `#[fallible]` attribute: the fixture resolves to the `Ok` value, so tests
+/// and other fixtures take a `T` argument. If the fixture returns an error the test fails
+/// with a ``setup of fixture `name` failed: <error>`` message followed by a `caused by: `
+/// line for every error in its `source()` chain, so a broken setup is not mistaken for
+/// a failing test.
+///
+/// ```ignore
+/// use rstest::*;
+/// # use std::io;
+///
+/// #[fixture]
+/// #[fallible]
+/// fn config() -> io::Result<String> {
+///     std::fs::read_to_string("not_existing.toml")
+/// }
+///
+/// #[rstest]
+/// fn the_test(config: String) {
+///     assert!(config.contains("[server]"))
+/// }
+/// ```
+///
+/// Any error type that can be converted in a `Box<dyn std::error::Error>` is reported
+/// with its chain (i.e. `anyhow::Error`, `String`...); other error types should implement
+/// `Debug`.
+///
+/// # Timeout
+///
+/// A fixture can take a `#[timeout(<duration>)]` attribute like tests do: if the
+/// fixture is not resolved in time the test fails with a
+/// ``Timeout <duration> expired resolving fixture `name` `` message, so a hanging
+/// setup is not reported as a slow test.
+///
+/// ```ignore
+/// use rstest::*;
+/// # use std::time::Duration;
+/// # struct Server;
+/// # impl Server { fn start() -> Self { Server } }
+///
+/// #[fixture]
+/// #[timeout(Duration::from_millis(500))]
+/// fn server() -> Server {
+///     Server::start()
+/// }
+/// ```
+///
+/// A sync fixture is resolved in another thread, so its arguments and its value
+/// should be `Send + 'static` and it cannot take `#[by_ref]` arguments. Async
+/// fixtures need the `async-timeout` feature (enabled by default).
+///
+/// # Profiling
+///
+/// If the test binary runs with the `RSTEST_PROFILE` environment variable set (to
+/// anything but `0`) every fixture's resolution is timed. When the binary ends a report
+/// with the calls, the `#[once]` cache hits, the total and mean setup time and the slowest
+/// test of every fixture is written in `rstest-profile/<test binary>.txt` in the target's
+/// profile directory (i.e. `target/debug`), and its path is printed on stderr.
+///
+/// The times are self times: a fixture's time doesn't include the setup of the fixtures
+/// it depends on.
 ///
 /// # Partial Injection
 ///
@@ -206,6 +378,25 @@ use quote::ToTokens;
 /// attribute will inject `v1, ..., vn` expression as fixture arguments: all remaining arguments
 /// will be resolved as fixtures.
 ///
+/// You can also give the arguments by name with `#[with(name1 = v1, name2 = v2, ..)]`:
+/// any subset of them, in any order, while the other ones are resolved by their
+/// `#[default(...)]` values or as fixtures. You cannot mix positional and named arguments
+/// in the same `#[with(...)]`.
+///
+/// ```
+/// use rstest::*;
+///
+/// #[fixture]
+/// fn user(#[default("Alice")] name: &str, #[default(22)] age: u8, #[default("US")] country: &str) -> String {
+///     format!("{name} ({age}) from {country}")
+/// }
+///
+/// #[rstest]
+/// fn the_test(#[with(country = "IT", age = 42)] user: String) {
+///     assert_eq!("Alice (42) from IT", user)
+/// }
+/// ```
+///
 /// Sometimes the return type cannot be inferred so you must define it: For the few times you may
 /// need to do it, you can use the `#[default(type)]`, `#[partial_n(type)]` function attribute
 /// to define it:
@@ -243,6 +434,108 @@ use quote::ToTokens;
 /// ```
 /// `partial_i` is the fixture used when you inject the first `i` arguments in test call.
 ///
+/// # Builder
+///
+/// Every fixture has also a builder with a setter for each argument: you can give just
+/// the arguments that you need, in any order, and the other ones are resolved by
+/// injection or by their `#[default(...)]` values when you call `build()`. It's what
+/// `#[with(name = value)]` uses, but you can use it everywhere, i.e. in examples or
+/// benches, without depending on the arguments' order.
+///
+/// ```ignore
+/// use rstest::*;
+///
+/// #[fixture]
+/// pub fn name() -> &'static str { "Alice" }
+///
+/// #[fixture]
+/// pub fn user(name: &str, #[default(22)] age: u8) -> (String, u8) {
+///     (name.to_owned(), age)
+/// }
+///
+/// fn main() {
+///     assert_eq!(("Bob".to_owned(), 3), user::builder().name("Bob").age(3).build());
+///     assert_eq!(("Alice".to_owned(), 3), user::builder().age(3).build());
+/// }
+/// ```
+///
+/// `build()` is `async` for `async` fixtures and returns the `&'static` value for
+/// `#[once]` fixtures.
+///
+/// # Injection by Type
+///
+/// `#[fixture(provides)]` implements [`rstest::Provide`] for the fixture's return
+/// type, so any argument of that type annotated by `#[by_type]` gets it, whatever its
+/// name: no need to rename it by `#[from(...)]`. The fixture should return a concrete
+/// type defined in your crate and cannot be `async`. If no fixture provides the type, or
+/// two fixtures provide the same one, the compiler reports it.
+///
+/// Rust's orphan rule forbids implementing `Provide` for the types of other crates:
+/// wrap them in a newtype. `provides` reports the primitive and the standard library
+/// types; for the other crates' ones you get the compiler's `E0117` error.
+///
+/// ```ignore
+/// use rstest::*;
+///
+/// pub struct Config { port: u16 }
+///
+/// #[fixture(provides)]
+/// fn config() -> Config {
+///     Config { port: 8080 }
+/// }
+///
+/// #[rstest]
+/// fn server(#[by_type] cfg: Config) {
+///     assert_eq!(8080, cfg.port);
+/// }
+/// ```
+///
+/// # Parametrized Fixtures
+///
+/// A fixture's arguments annotated by `#[values(...)]` make it parametrized: it has a
+/// variant for each value (for each combination of values if there are more lists).
+/// Every `#[rstest]` test that marks the fixture's argument by a bare `#[values]` runs
+/// once for each variant and the test names contain the variant's values.
+///
+/// ```ignore
+/// use rstest::*;
+/// # #[derive(Clone, Copy)]
+/// # enum Backend { Mem, Disk }
+/// # struct Store(Backend);
+/// # impl Store {
+/// #     fn new(backend: Backend) -> Self { Self(backend) }
+/// #     fn is_empty(&self) -> bool { true }
+/// # }
+///
+/// #[fixture]
+/// fn store(#[values(Backend::Mem, Backend::Disk)] backend: Backend) -> Store {
+///     Store::new(backend)
+/// }
+///
+/// #[rstest]
+/// fn start_empty(#[values] store: Store) {
+///     assert!(store.is_empty())
+/// }
+/// ```
+///
+/// runs `start_empty::store_1_Backend__Mem` and `start_empty::store_2_Backend__Disk`.
+/// The variants are a values list of the test: they mix with the other ones, with
+/// `#[case]`s and with the variants of other fixtures.
+///
+/// Every `#[rstest]` is expanded alone and cannot see the fixtures that it uses, so the
+/// test should mark the argument: the fixture defines a macro with its own name that
+/// gives back its variants. That has some consequences:
+///
+/// - tests are not multiplied automatically: each one should mark the argument by a
+///   bare `#[values]`
+/// - the fixture can be used just in its crate, where its name is visible (also by
+///   `#[from(name)]`): tests in other crates fail with ``cannot find macro `name` ``
+/// - a parametrized fixture has no `default()`, `partial_N()` or `builder()`: other
+///   fixtures and tests without the `#[values]` marker cannot inject it
+/// - `#[once]`, `provides` and `#[by_ref]` arguments cannot be used on it
+/// - a module that glob imports a parametrized fixture cannot define another one with
+///   the same name
+///
 /// # Old _compact_ syntax
 ///
 /// There is also a compact form for all previous features. This will maintained for a long time
@@ -354,6 +647,7 @@ pub fn fixture(
 /// - [injecting fixtures](#injecting-fixtures)
 /// - Generate [parametrized test cases](#test-parametrized-cases)
 /// - Generate tests for each combination of [value lists](#values-lists)
+/// - Share a fixture among the tests of a [group](#group-scoped-fixtures)
 ///
 /// ## Injecting Fixtures
 ///
@@ -593,6 +887,64 @@ pub fn fixture(
 /// fn fail(#[case] v: u32) { assert_eq!(0, v) }
 /// ```
 ///
+/// ### Use specific fixtures in a `case`
+///
+/// A `#[with(fixture(args...))]` that precedes a `#[case]` attribute resolves the
+/// fixture by the given arguments just in that case: the syntax is the same of the
+/// fixtures given in the [old _compact_ syntax](#old-compact-syntax), named arguments
+/// included, and it takes the place of any `#[with(...)]` on the test's argument.
+///
+/// ```
+/// # use rstest::*;
+/// # #[derive(PartialEq, Debug)]
+/// # enum Role { Admin, Guest }
+/// # struct User(Role);
+/// # impl User { fn can_delete(&self) -> bool { self.0 == Role::Admin } }
+/// #[fixture]
+/// fn user(#[default(Role::Guest)] role: Role) -> User {
+///     User(role)
+/// }
+///
+/// #[rstest]
+/// #[with(user(Role::Admin))]
+/// #[case::admin(true)]
+/// #[case::guest(false)]
+/// fn delete(#[case] allowed: bool, user: User) {
+///     assert_eq!(allowed, user.can_delete());
+/// }
+/// ```
+///
+/// ### Load cases from a data file
+///
+/// Large case tables can live in a data file: `#[cases_from("path")]` reads the file
+/// at compile time, relative to the crate root like `#[files]`, and adds a case for
+/// each of its rows after the `#[case]` attributes. The file's columns give the
+/// `#[case]` arguments by name, and every cell is a string literal, so the
+/// [magic conversion](#magic-conversion) applies.
+///
+/// ```rust,ignore
+/// #[rstest]
+/// #[cases_from("tests/data/sum.csv")]
+/// fn sum(#[case] a: u32, #[case] b: u32, #[case] expected: u32) {
+///     assert_eq!(expected, a + b)
+/// }
+/// ```
+///
+/// The file's format is given by its extension:
+///
+/// - `.csv`: a header line names the columns and each line is a case;
+/// - `.json`: an array of objects, one for each case;
+/// - `.toml`: a `[[case]]` array of tables;
+/// - `.txt` or no extension: each non-empty line is a case for the only `#[case]`
+///   argument.
+///
+/// Files with any other extension are reported as errors.
+///
+/// The CSV, JSON and TOML parsers are behind the `cases-csv`, `cases-json` and
+/// `cases-toml` optional features of `rstest`: enable the ones you need.
+///
+/// The test is compiled again when the data file changes.
+///
 /// ## Values Lists
 ///
 /// Another useful way to write a test and execute it for some values
@@ -644,6 +996,10 @@ pub fn fixture(
 /// a valid Rust identifier name. This should help to identify which case fails.
 ///
 ///
+/// A bare `#[values]` takes the values list from a
+/// [parametrized fixture](attr.fixture.html#parametrized-fixtures): the test runs for
+/// each of its variants.
+///
 /// Also value list implements the magic conversion feature: every time the value type
 /// implements `FromStr` trait you can use a literal string to define it.
 ///
@@ -656,6 +1012,149 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// ## Group Scoped Fixtures
+///
+/// A fixture argument annotated by `#[scope(group)]` is resolved just once for a group of
+/// tests and all the tests of the group share it by reference. The group is the whole
+/// `#[rstest]` function or, for a value lists matrix with cases, every `case_N` module.
+/// The value is created lazily by the first test that needs it and is torn down (its
+/// [`#[teardown]`](attr.fixture.html#teardown) is executed and then the value is dropped)
+/// when the last test of the group ends.
+///
+/// ```
+/// # use rstest::*;
+/// # struct Database;
+/// # impl Database {
+/// #     fn connect() -> Self { Database }
+/// #     fn count(&self, _t: &str) -> usize { 3 }
+/// # }
+/// #[fixture]
+/// fn db() -> Database {
+///     Database::connect()
+/// }
+///
+/// #[rstest]
+/// #[case("users")]
+/// #[case("items")]
+/// fn table_is_not_empty(#[scope(group)] db: &Database, #[case] table: &str) {
+///     assert!(db.count(table) > 0)
+/// }
+/// ```
+///
+/// Group scoped values are shared among threads, so they should be `Send` and `Sync`. The
+/// fixture is resolved by a thread that waits for the end of the group and then executes
+/// its teardowns, so they don't need to be `Send`.
+/// You cannot use `#[scope(group)]` in generic tests or on `#[case]`, `#[values]`
+/// and `#[future]` arguments, and its `#[with(...)]` cannot use the test's arguments
+/// because all the tests share the same value. If some tests of the group don't run
+/// (i.e. they're filtered out) the value is torn down when the test process exits.
+///
+/// ## Autouse Fixtures
+///
+/// Fixtures that every test needs just for their side effects (i.e. init logging) can be
+/// listed by `#[autouse(...)]` instead of adding an unused argument to every test: they
+/// are resolved in order before the test's arguments and live till the end of the test.
+/// Use [`#[autouse(...)]`](macro@autouse) on a module to apply it to all its `#[rstest]`
+/// tests.
+///
+/// ```
+/// # use rstest::*;
+/// #[fixture]
+/// fn logging() {
+///     // Init your logger here
+/// }
+///
+/// #[rstest]
+/// #[case(1)]
+/// #[case(2)]
+/// #[autouse(logging)]
+/// fn the_test(#[case] value: u32) {
+///     assert!(value > 0)
+/// }
+/// ```
+///
+/// Like the other attributes, `#[autouse(...)]` before a `#[case]` applies just to that case.
+///
+/// ## Test Context
+///
+/// An argument annotated by `#[context]` gets the [`rstest::Context`] of the running
+/// test: its `name`, its full `path` (i.e. `the_test::case_2_big`), the `case`
+/// number and its `description`, the `values` labels of a matrix and the `start` instant.
+///
+/// ```ignore
+/// use rstest::*;
+///
+/// #[rstest]
+/// #[case::small(1)]
+/// #[case::big(1000)]
+/// fn the_test(#[case] value: u32, #[context] ctx: Context) {
+///     assert_eq!("the_test", ctx.name);
+///     assert!(ctx.case.is_some());
+///     assert!(ctx.description.is_some());
+///     println!("{}: {value}", ctx.path);
+/// }
+/// ```
+///
+/// ## Lazy Fixtures
+///
+/// An argument annotated by `#[lazy]` is resolved just the first time the test uses
+/// it: declare it as [`rstest::Lazy<T>`] and dereference it, or as
+/// [`rstest::AsyncLazy<T>`] for async fixtures and await `get()`. Fixtures that
+/// are never used are never built. `#[with(...)]` works as usual: its arguments are
+/// evaluated when the test starts, so they can use the test's other arguments, even
+/// when they are not `Send`.
+///
+/// ```ignore
+/// use rstest::*;
+/// # struct Db;
+/// # impl Db { fn query(&self) -> u32 { 42 } }
+/// #[fixture]
+/// fn db() -> Db {
+///     // Expensive setup
+///     Db
+/// }
+///
+/// #[rstest]
+/// #[case(false)]
+/// #[case(true)]
+/// fn the_test(#[case] need_db: bool, #[lazy] db: Lazy<Db>) {
+///     if need_db {
+///         assert_eq!(42, db.query());
+///     }
+/// }
+/// ```
+///
+/// ## Borrowed Fixtures
+///
+/// A fixture can borrow the value of another one: annotate its argument by `#[by_ref]`
+/// and use a reference as type. Such fixture cannot resolve the borrowed argument by
+/// itself, so the test should own the value by a `#[by_ref]` argument too and give it
+/// by `#[with(...)]`. The owned value lives till the end of the test.
+///
+/// ```ignore
+/// # use rstest::*;
+/// # struct Connection;
+/// # struct Transaction<'a>(&'a Connection);
+/// # impl Connection { fn transaction(&self) -> Transaction<'_> { Transaction(self) } }
+/// #[fixture]
+/// fn connection() -> Connection {
+///     Connection
+/// }
+///
+/// #[fixture]
+/// fn transaction(#[by_ref] connection: &Connection) -> Transaction<'_> {
+///     connection.transaction()
+/// }
+///
+/// #[rstest]
+/// fn the_test(
+///     #[by_ref] connection: &Connection,
+///     #[with(connection)] transaction: Transaction,
+/// ) {
+///     // ...
+/// }
+/// ```
+///
 /// ## Files path as input arguments
 ///
 /// If you need to create a test for each file in a given location you can use
@@ -677,6 +1176,67 @@ pub fn fixture(
 /// exclusion rules with the `#[exclude("regex")]` attributes that filter out all
 /// paths that verify the regular expression.
 ///
+/// By default the argument is the file's absolute `PathBuf`, but `#[mode = str]` injects
+/// the file's contents as `&'static str` (by `include_str!()`) and `#[mode = bytes]` as
+/// `&'static [u8]` (by `include_bytes!()`): the contents are embedded in the test binary
+/// and the test is compiled again when a file changes.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// fn is_utf8(#[files("src/**/*.rs")] #[mode = bytes] contents: &[u8]) {
+///     assert!(std::str::from_utf8(contents).is_ok())
+/// }
+/// ```
+///
+/// The `PathBuf`s are absolute paths on the machine that builds the tests: if you run the
+/// tests elsewhere (i.e. in another container or from a `cargo nextest archive`) use
+/// `#[mode = relocatable]`. The crate relative paths are joined at runtime to the
+/// `RSTEST_FILES_BASE` environment variable if it's defined, otherwise to the
+/// `CARGO_MANIFEST_DIR` where the test runs (cargo and nextest define it) and, as last
+/// resort, to the crate root where the test was built.
+///
+/// ```
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// #[rstest]
+/// fn exists(#[files("src/**/*.rs")] #[mode = relocatable] path: PathBuf) {
+///     assert!(path.exists())
+/// }
+/// ```
+///
+/// When each file comes with some companion files, like in golden tests where `foo.input`
+/// has its `foo.expected`, use `#[sibling("name")]` on another argument to inject the
+/// companion of each file matched by the previous `#[files(...)]` argument. The name is
+/// relative to the matched file's folder, where `{stem}` and `{name}` are replaced by the
+/// file's stem and name, and the `#[mode = ...]` attribute works on siblings too. The tests
+/// are named by the shared stem (i.e. `input_1_cases_foo`) and a missing sibling is a
+/// compile error.
+///
+/// ```ignore
+/// # use rstest::rstest;
+/// #[rstest]
+/// fn golden(
+///     #[files("tests/cases/*.input")] #[mode = str] input: &str,
+///     #[sibling("{stem}.expected")] #[mode = str] expected: &str,
+/// ) {
+///     assert_eq!(expected, parse(input).to_string())
+/// }
+/// ```
+///
+/// The globs are expanded at compile time, so cargo doesn't build the tests again when
+/// a matching file is added or removed: a procedural macro can make the compiler track the
+/// matched files but not their folder, because `proc_macro::tracked_path` is unstable.
+/// Call `rstest::build::track_files()` from your build script (with `rstest` and its
+/// `build` feature in your `[build-dependencies]`) to track the globs' folders:
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     rstest::build::track_files();
+/// }
+/// ```
+///
 /// Sometime is useful to have test files in a workspace folder to share them between the
 /// crates in your workspace. You can do that by use the usual parent folders `..` in
 /// the glob path. In this case the test names will be the relative path from the crate root
@@ -782,6 +1342,35 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// The awaited inputs are awaited one after the other in the arguments order. If they
+/// are independent, annotate the function with `#[concurrent]` to await them all
+/// concurrently before the test body runs:
+///
+/// ```
+/// use rstest::*;
+/// # struct Server; struct Store;
+/// #[fixture]
+/// async fn server() -> Server {
+///     // Slow spawn
+///     Server
+/// }
+///
+/// #[fixture]
+/// async fn store() -> Store {
+///     // Slow seed
+///     Store
+/// }
+///
+/// #[rstest]
+/// #[concurrent]
+/// #[awt]
+/// async fn the_test(#[future] server: Server, #[future] store: Store) {
+///     // ...
+/// }
+/// ```
+///
+/// `#[concurrent]` works in the same way on `async` fixtures.
+///
 /// ### Default timeout
 ///
 /// You can set a default timeout for test using the `RSTEST_TIMEOUT` environment variable.
@@ -793,7 +1382,7 @@ pub fn fixture(
 /// works both for sync and async tests and is runtime agnostic. `#[timeout(<duration>)]` take an
 /// expression that should return a `std::time::Duration`. Follow a simple async example:
 ///
-/// ```rust
+/// ```rust,ignore
 /// use rstest::*;
 /// use std::time::Duration;
 ///
@@ -817,7 +1406,7 @@ pub fn fixture(
 /// Another valuable point in this example is to use an expression to compute the
 /// duration.
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use rstest::*;
 /// # use std::time::Duration;
 /// #
@@ -843,6 +1432,10 @@ pub fn fixture(
 /// If you want to use `timeout` for `async` test you need to use `async-timeout`
 /// feature (enabled by default).
 ///
+/// The timeout covers the fixtures' resolution too: a test with a slow or hanging
+/// fixture fails by timeout like a slow test body. You can also set a timeout for a
+/// single fixture (see [fixture's timeout](attr.fixture.html#timeout)).
+///
 /// ## Inject Test Attribute
 ///
 /// If you would like to use another `test` attribute for your test you can simply
@@ -1085,6 +1678,7 @@ pub fn rstest(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let (orig_args, orig_test) = (args.clone(), input.clone());
     let mut test = parse_macro_input!(input as ItemFn);
     let mut info = parse_macro_input!(args as RsTestInfo);
 
@@ -1097,7 +1691,9 @@ pub fn rstest(
     }
 
     if errors.is_empty() {
-        if info.data.has_list_values() {
+        if let Some(variants) = info.data.unresolved_variants() {
+            render::variants::callback(orig_args.into(), orig_test.into(), variants)
+        } else if info.data.has_list_values() {
             render::matrix(test, info)
         } else if info.data.has_cases() {
             render::parametrize(test, info)
@@ -1109,3 +1705,131 @@ pub fn rstest(
     }
     .into()
 }
+
+/// Resolve the given fixtures in every `#[rstest]` test of the annotated module (also in
+/// its nested modules), even if the tests don't take them as arguments. That's useful for
+/// setup steps that all the tests need, like init logging, setting a locale or resetting a
+/// global registry.
+///
+/// ```ignore
+/// use rstest::*;
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # static LOGGING: AtomicBool = AtomicBool::new(false);
+///
+/// #[fixture]
+/// fn logging() {
+///     LOGGING.store(true, Ordering::SeqCst);
+/// }
+///
+/// #[autouse(logging)]
+/// mod tests {
+///     use super::*;
+///
+///     #[rstest]
+///     fn log_is_on() {
+///         assert!(LOGGING.load(Ordering::SeqCst))
+///     }
+///
+///     #[rstest]
+///     #[case(1)]
+///     #[case(2)]
+///     fn also_here(#[case] value: u32) {
+///         assert!(value > 0 && LOGGING.load(Ordering::SeqCst))
+///     }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The fixtures are resolved by `default()` in the given order, before the test's arguments,
+/// and their values (and [teardowns](attr.fixture.html#teardown)) live till the end of the
+/// test. A fixture listed more than once or also taken as argument by the test is resolved
+/// just once. Async tests await the `async` autouse fixtures, while a sync test that
+/// lists one of them doesn't compile (the error mentions `AsyncFixtureInSyncTest`).
+///
+/// You can list the fixtures by path (i.e. `#[autouse(crate::setup::logging)]`) and
+/// also use `#[autouse(...)]` directly on a `#[rstest]` test. `#[autouse]` cannot see
+/// the tests of a module defined in another file: use it on the module's content
+/// instead.
+#[proc_macro_attribute]
+pub fn autouse(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let autouse = parse_macro_input!(args as Autouse);
+    let item = parse_macro_input!(input as Item);
+
+    render::autouse::apply(autouse, item).into()
+}
+
+/// Bundle some fixtures in a struct that you can inject as a single fixture: each field
+/// is resolved as a fixture argument, by its name, and the struct's fixture is named
+/// like the struct in snake case (i.e. `TestEnv` becomes `test_env`).
+///
+/// ```ignore
+/// use rstest::*;
+/// # pub struct Db;
+/// # pub struct User(&'static str);
+///
+/// #[fixture]
+/// fn db() -> Db { Db }
+///
+/// #[fixture]
+/// fn user(#[default("guest")] role: &'static str) -> User { User(role) }
+///
+/// #[derive(Fixtures)]
+/// struct TestEnv {
+///     db: Db,
+///     #[with("admin")]
+///     user: User,
+///     #[from(user)]
+///     guest: User,
+/// }
+///
+/// #[rstest]
+/// fn the_test(test_env: TestEnv) {
+///     assert_eq!("admin", test_env.user.0);
+///     assert_eq!("guest", test_env.guest.0);
+/// }
+/// ```
+///
+/// Fields support `#[with(...)]`, `#[from(...)]` and `#[by_type]` like fixture arguments.
+/// A `#[future]` field holds the awaited value of an async fixture: in this case the
+/// struct's fixture is `async` too and the tests should await it (i.e. by
+/// `#[future(awt)]`).
+///
+/// The struct's fixture is a normal fixture: `get()` takes all the fields, `partial_N()`
+/// the first `N` ones, and tests can give them by `#[with(...)]`. If its snake case name
+/// collides with another item (i.e. a `test_env` function) give it an explicit name by
+/// `#[fixture_name(name)]`:
+///
+/// ```ignore
+/// # use rstest::*;
+/// # pub struct Db;
+/// # #[fixture]
+/// # fn db() -> Db { Db }
+/// #[derive(Fixtures)]
+/// #[fixture_name(env)]
+/// struct TestEnv {
+///     db: Db,
+/// }
+///
+/// #[rstest]
+/// fn the_test(env: TestEnv) {}
+/// ```
+#[proc_macro_derive(Fixtures, attributes(with, from, future, by_type, fixture_name))]
+pub fn fixtures(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match Bundle::new(input) {
+        Ok(bundle) => {
+            let errors = error::fixture(&bundle.function, &bundle.info);
+            if errors.is_empty() {
+                render::fixture(bundle.function, bundle.info)
+            } else {
+                errors
+            }
+        }
+        Err(errors) => errors.to_token_stream(),
+    }
+    .into()
+}
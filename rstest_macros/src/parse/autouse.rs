@@ -0,0 +1,86 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, ItemFn, Path, Token,
+};
+
+use crate::{error::ErrorsVec, utils::attr_is};
+
+/// The fixtures listed by `#[autouse(...)]`: they are resolved before the test body
+/// even if the test doesn't take them as arguments.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct Autouse(Punctuated<Path, Token![,]>);
+
+impl Autouse {
+    pub(crate) fn fixtures(&self) -> impl Iterator<Item = (&Path, &Ident)> {
+        self.0
+            .iter()
+            .filter_map(|path| path.segments.last().map(|s| (path, &s.ident)))
+    }
+}
+
+impl Parse for Autouse {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fixtures = Punctuated::<Path, Token![,]>::parse_terminated(input)?;
+        if fixtures.is_empty() {
+            return Err(input.error("#[autouse(...)] should list at least one fixture"));
+        }
+        Ok(Self(fixtures))
+    }
+}
+
+impl quote::ToTokens for Autouse {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
+
+/// The `#[autouse(...)]` attributes are rendered with the test, so here they are just
+/// checked.
+pub(crate) fn check_autouse_attrs(item_fn: &ItemFn) -> Result<(), ErrorsVec> {
+    let errors = item_fn
+        .attrs
+        .iter()
+        .filter(|&a| attr_is(a, "autouse"))
+        .filter_map(|a| a.parse_args::<Autouse>().err())
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::one("logging", &["logging"])]
+    #[case::many("logging, locale", &["logging", "locale"])]
+    #[case::paths("crate::setup::logging, locale,", &["logging", "locale"])]
+    fn parse_fixtures(#[case] args: &str, #[case] expected: &[&str]) {
+        let autouse: Autouse = syn::parse_str(args).unwrap();
+
+        assert_eq!(
+            expected.iter().copied().map(ident).collect::<Vec<_>>(),
+            autouse
+                .fixtures()
+                .map(|(_, name)| name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::empty("#[autouse()] fn f() {}", "at least one fixture")]
+    #[case::not_a_path("#[autouse(logging())] fn f() {}", "expected `,`")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let item_fn: ItemFn = item_fn.ast();
+
+        let err = check_autouse_attrs(&item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -0,0 +1,202 @@
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, DeriveInput, Fields, Ident, ItemFn};
+
+use super::{fixture::FixtureInfo, ExtendWithFunctionAttrs};
+use crate::{error::ErrorsVec, utils::attr_is};
+
+/// The struct's attribute that gives the bundle's fixture an explicit name.
+const FIXTURE_NAME_ATTR: &str = "fixture_name";
+
+/// A `#[derive(Fixtures)]` struct: its fields are resolved as the arguments of a
+/// fixture named like the struct in snake case (or by `#[fixture_name(name)]`).
+pub(crate) struct Bundle {
+    /// The fixture's function: it takes the struct's fields as arguments, without their
+    /// attributes, and builds the struct.
+    pub(crate) function: ItemFn,
+    pub(crate) info: FixtureInfo,
+}
+
+impl Bundle {
+    pub(crate) fn new(input: DeriveInput) -> Result<Self, ErrorsVec> {
+        let fields = match input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => fields.named,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "#[derive(Fixtures)] needs a struct with named fields.",
+                    )
+                    .into())
+                }
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "#[derive(Fixtures)] can be used just on structs.",
+                )
+                .into())
+            }
+        };
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                "#[derive(Fixtures)] doesn't support generic structs.",
+            )
+            .into());
+        }
+        let fixture = fixture_name(&input.attrs)?.unwrap_or_else(|| {
+            format_ident!(
+                "{}",
+                snake_case(&input.ident.to_string()),
+                span = input.ident.span()
+            )
+        });
+        // The struct holds the values: the `#[future]` fields are awaited
+        let future = |a: &Attribute| attr_is(a, "future");
+        let asyncness = fields
+            .iter()
+            .any(|f| f.attrs.iter().any(future))
+            .then(|| quote! { async });
+        let args = fields.iter().map(|f| {
+            let attrs = f.attrs.iter().map(|a| match a.meta {
+                syn::Meta::Path(_) if future(a) => parse_quote! { #[future(awt)] },
+                _ => a.clone(),
+            });
+            let attrs = attrs.collect::<Vec<Attribute>>();
+            let ident = &f.ident;
+            let ty = &f.ty;
+            quote! { #(#attrs)* #ident: #ty }
+        });
+        let fields = fields.iter().map(|f| &f.ident);
+        let name = &input.ident;
+        let vis = &input.vis;
+        let mut function: ItemFn = parse_quote! {
+            #vis #asyncness fn #fixture(#(#args),*) -> #name {
+                #name { #(#fields),* }
+            }
+        };
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut function)?;
+        Ok(Self { function, info })
+    }
+}
+
+/// The name given by `#[fixture_name(name)]`, if any.
+fn fixture_name(attrs: &[Attribute]) -> Result<Option<Ident>, ErrorsVec> {
+    let mut names = attrs.iter().filter(|a| attr_is(a, FIXTURE_NAME_ATTR));
+    let name = names.next().map(|a| a.parse_args::<Ident>()).transpose()?;
+    match names.next() {
+        Some(other) => {
+            Err(syn::Error::new_spanned(other, "Cannot use #[fixture_name] more than once.").into())
+        }
+        None => Ok(name),
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_uppercase() {
+            if i > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case("Env", "env")]
+    #[case("TestEnv", "test_env")]
+    #[case("Test_Env", "test_env")]
+    #[case("testEnv", "test_env")]
+    fn name_the_fixture_in_snake_case(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(expected, snake_case(name));
+    }
+
+    #[test]
+    fn extract_the_fields_as_fixture_arguments() {
+        let bundle = Bundle::new(
+            r#"
+            pub struct TestEnv {
+                db: Db,
+                #[with("admin")]
+                user: User,
+                #[from(other)]
+                renamed: u32,
+                #[future]
+                fut: u32,
+            }
+        "#
+            .ast(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            bundle.function,
+            r#"
+            pub async fn test_env(db: Db, user: User, renamed: u32, fut: u32) -> TestEnv {
+                TestEnv { db, user, renamed, fut }
+            }
+            "#
+            .ast()
+        );
+        assert_eq!(
+            vec!["user", "renamed"],
+            bundle
+                .info
+                .data
+                .fixtures()
+                .map(|f| f.name.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert!(bundle.info.arguments.is_future_await(&ident("fut")));
+        assert!(!bundle.info.arguments.is_future(&ident("db")));
+    }
+
+    #[test]
+    fn name_the_fixture_by_fixture_name() {
+        let bundle = Bundle::new(
+            r#"
+            #[fixture_name(env)]
+            struct TestEnv {
+                db: Db,
+            }
+        "#
+            .ast(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            bundle.function,
+            "fn env(db: Db) -> TestEnv { TestEnv { db } }".ast()
+        );
+    }
+
+    #[rstest]
+    #[case::tuple("struct Env(Db);", "named fields")]
+    #[case::enumeration("enum Env { A }", "just on structs")]
+    #[case::generics("struct Env<T> { t: T }", "generic structs")]
+    #[case::fixture_name_twice(
+        "#[fixture_name(a)] #[fixture_name(b)] struct Env { db: Db }",
+        "more than once"
+    )]
+    #[case::invalid_fixture_name(
+        "#[fixture_name(\"a\")] struct Env { db: Db }",
+        "expected identifier"
+    )]
+    fn raise_error(#[case] input: &str, #[case] message: &str) {
+        let err = Bundle::new(input.ast()).err().unwrap();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
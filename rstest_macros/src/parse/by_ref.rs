@@ -0,0 +1,120 @@
+use quote::ToTokens;
+use syn::{visit_mut::VisitMut, FnArg, Ident, ItemFn, Type};
+
+use crate::{error::ErrorsVec, refident::MaybeType, utils::attr_is};
+
+use super::extract_argument_attrs;
+
+/// Extract the arguments annotated by `#[by_ref]`.
+pub(crate) fn extract_by_refs(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = ByRefsFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the borrowed ones.
+#[derive(Default)]
+struct ByRefsFunctionExtractor {
+    by_refs: Vec<Ident>,
+    errors: Vec<syn::Error>,
+}
+
+impl ByRefsFunctionExtractor {
+    pub(crate) fn take(self) -> Result<Vec<Ident>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.by_refs)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for ByRefsFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        match extract_argument_attrs(
+            node,
+            |a| attr_is(a, "by_ref"),
+            |arg, name| match arg.meta {
+                syn::Meta::Path(_) => Ok((arg, name.clone())),
+                _ => Err(syn::Error::new_spanned(
+                    arg.into_token_stream(),
+                    "#[by_ref] doesn't take any argument.",
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(by_refs) => match by_refs.len().cmp(&1) {
+                std::cmp::Ordering::Equal => match node.maybe_type() {
+                    Some(Type::Reference(r)) if r.mutability.is_none() => {
+                        self.by_refs.push(by_refs[0].1.clone())
+                    }
+                    _ => self.errors.push(syn::Error::new_spanned(
+                        node.maybe_type().unwrap().into_token_stream(),
+                        "#[by_ref] arguments borrow the fixture's value: use a reference like `&T` as type.",
+                    )),
+                },
+                std::cmp::Ordering::Greater => {
+                    self.errors.extend(by_refs.iter().skip(1).map(|(attr, _)| {
+                        syn::Error::new_spanned(
+                            attr.into_token_stream(),
+                            "Cannot use #[by_ref] more than once.",
+                        )
+                    }));
+                }
+                std::cmp::Ordering::Less => {}
+            },
+            Err(e) => {
+                self.errors.push(e);
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[by_ref] db: &Db) {}", "fn f(db: &Db) {}", &["db"])]
+    #[case::among_others(
+        "fn f(a: u32, #[by_ref] db: &Db, #[by_ref] b: &'a B) {}",
+        "fn f(a: u32, db: &Db, b: &'a B) {}",
+        &["db", "b"]
+    )]
+    #[case::no_one("fn f(db: &Db) {}", "fn f(db: &Db) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_by_refs: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let by_refs = extract_by_refs(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            by_refs,
+            expected_by_refs
+                .iter()
+                .copied()
+                .map(ident)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[by_ref] #[by_ref] db: &Db) {}", "more than once")]
+    #[case::no_arguments("fn f(#[by_ref(db)] db: &Db) {}", "doesn't take any argument")]
+    #[case::not_a_reference("fn f(#[by_ref] db: Db) {}", "use a reference like `&T`")]
+    #[case::mutable_reference("fn f(#[by_ref] db: &mut Db) {}", "use a reference like `&T`")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_by_refs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -0,0 +1,119 @@
+use quote::ToTokens;
+use syn::{visit_mut::VisitMut, FnArg, Ident, ItemFn, Type};
+
+use crate::{error::ErrorsVec, refident::MaybeType, utils::attr_is};
+
+use super::extract_argument_attrs;
+
+/// Extract the arguments annotated by `#[by_type]`.
+pub(crate) fn extract_by_types(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = ByTypesFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the ones resolved by type.
+#[derive(Default)]
+struct ByTypesFunctionExtractor {
+    by_types: Vec<Ident>,
+    errors: Vec<syn::Error>,
+}
+
+impl ByTypesFunctionExtractor {
+    pub(crate) fn take(self) -> Result<Vec<Ident>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.by_types)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for ByTypesFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        match extract_argument_attrs(
+            node,
+            |a| attr_is(a, "by_type"),
+            |arg, name| match arg.meta {
+                syn::Meta::Path(_) => Ok((arg, name.clone())),
+                _ => Err(syn::Error::new_spanned(
+                    arg.into_token_stream(),
+                    "#[by_type] doesn't take any argument.",
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(by_types) => match by_types.len().cmp(&1) {
+                std::cmp::Ordering::Equal => match node.maybe_type() {
+                    Some(Type::ImplTrait(_) | Type::Infer(_)) => {
+                        self.errors.push(syn::Error::new_spanned(
+                            node.maybe_type().unwrap().into_token_stream(),
+                            "#[by_type] needs a concrete type to find its provider.",
+                        ))
+                    }
+                    _ => self.by_types.push(by_types[0].1.clone()),
+                },
+                std::cmp::Ordering::Greater => {
+                    self.errors.extend(by_types.iter().skip(1).map(|(attr, _)| {
+                        syn::Error::new_spanned(
+                            attr.into_token_stream(),
+                            "Cannot use #[by_type] more than once.",
+                        )
+                    }));
+                }
+                std::cmp::Ordering::Less => {}
+            },
+            Err(e) => {
+                self.errors.push(e);
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[by_type] db: Db) {}", "fn f(db: Db) {}", &["db"])]
+    #[case::among_others(
+        "fn f(a: u32, #[by_type] db: Db, #[by_type] b: &'static B) {}",
+        "fn f(a: u32, db: Db, b: &'static B) {}",
+        &["db", "b"]
+    )]
+    #[case::no_one("fn f(db: Db) {}", "fn f(db: Db) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_by_types: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let by_types = extract_by_types(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            by_types,
+            expected_by_types
+                .iter()
+                .copied()
+                .map(ident)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[by_type] #[by_type] db: Db) {}", "more than once")]
+    #[case::no_arguments("fn f(#[by_type(Db)] db: Db) {}", "doesn't take any argument")]
+    #[case::no_impl("fn f(#[by_type] db: impl Db) {}", "needs a concrete type")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_by_types(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -0,0 +1,109 @@
+use quote::ToTokens;
+use syn::{visit_mut::VisitMut, FnArg, Ident, ItemFn};
+
+use crate::{error::ErrorsVec, utils::attr_is};
+
+use super::extract_argument_attrs;
+
+/// Extract the arguments annotated by `#[context]`.
+pub(crate) fn extract_contexts(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = ContextsFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the context ones.
+#[derive(Default)]
+struct ContextsFunctionExtractor {
+    contexts: Vec<Ident>,
+    errors: Vec<syn::Error>,
+}
+
+impl ContextsFunctionExtractor {
+    pub(crate) fn take(self) -> Result<Vec<Ident>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.contexts)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for ContextsFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        match extract_argument_attrs(
+            node,
+            |a| attr_is(a, "context"),
+            |arg, name| match arg.meta {
+                syn::Meta::Path(_) => Ok((arg, name.clone())),
+                _ => Err(syn::Error::new_spanned(
+                    arg.into_token_stream(),
+                    "#[context] doesn't take any argument.",
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(contexts) => {
+                if let Some((_, name)) = contexts.first() {
+                    self.contexts.push(name.clone());
+                }
+                self.errors.extend(contexts.iter().skip(1).map(|(attr, _)| {
+                    syn::Error::new_spanned(
+                        attr.into_token_stream(),
+                        "Cannot use #[context] more than once.",
+                    )
+                }));
+            }
+            Err(e) => {
+                self.errors.push(e);
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[context] ctx: Context) {}", "fn f(ctx: Context) {}", &["ctx"])]
+    #[case::among_others(
+        "fn f(a: u32, #[context] ctx: rstest::Context, b: u32) {}",
+        "fn f(a: u32, ctx: rstest::Context, b: u32) {}",
+        &["ctx"]
+    )]
+    #[case::no_one("fn f(ctx: Context) {}", "fn f(ctx: Context) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_contexts: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let contexts = extract_contexts(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            contexts,
+            expected_contexts
+                .iter()
+                .copied()
+                .map(ident)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[context] #[context] ctx: Context) {}", "more than once")]
+    #[case::no_arguments("fn f(#[context(all)] ctx: Context) {}", "doesn't take any argument")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_contexts(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -8,12 +8,24 @@ use syn::{
 
 use super::{
     arguments::ArgumentsInfo,
+    by_ref::extract_by_refs,
+    by_type::extract_by_types,
     extract_argument_attrs, extract_default_return_type, extract_defaults, extract_fixtures,
-    extract_partials_return_type,
+    extract_partials_return_type, extract_value_list,
     future::{extract_futures, extract_global_awt},
-    parse_vector_trailing_till_double_comma, Attributes, ExtendWithFunctionAttrs, Fixture,
+    parse_vector_trailing_till_double_comma,
+    vlist::ValueList,
+    Attributes, ExtendWithFunctionAttrs, Fixture,
+};
+use crate::{
+    error::ErrorsVec,
+    parse::{
+        check_timeout_attrs, extract_concurrent, extract_fallible, extract_fixture_timeout,
+        extract_once, extract_teardown,
+    },
+    refident::RefIdent,
+    utils::attr_is,
 };
-use crate::{error::ErrorsVec, parse::extract_once, refident::RefIdent, utils::attr_is};
 use crate::{parse::Attribute, utils::attr_in};
 use proc_macro2::TokenStream;
 use quote::{format_ident, ToTokens};
@@ -36,18 +48,42 @@ impl Parse for FixtureInfo {
         Ok(if input.is_empty() {
             Default::default()
         } else {
-            Self {
+            let provides = parse_provides(input)?;
+            let mut info = Self {
                 data: input.parse()?,
                 attributes: input
                     .parse::<Token![::]>()
                     .or_else(|_| Ok(Default::default()))
                     .and_then(|_| input.parse())?,
                 arguments: Default::default(),
+            };
+            if let Some(provides) = provides {
+                info.attributes.append(Attribute::Attr(provides));
             }
+            info
         })
     }
 }
 
+/// `provides` is a modifier that can be written as the first argument without the
+/// leading `::` (i.e. `#[fixture(provides)]`).
+fn parse_provides(input: ParseStream) -> syn::Result<Option<Ident>> {
+    let fork = input.fork();
+    match fork.parse::<Ident>() {
+        Ok(ident)
+            if ident == FixtureModifiers::PROVIDES_ATTR
+                && (fork.is_empty() || fork.peek(Token![,]) || fork.peek(Token![::])) =>
+        {
+            let ident = input.parse::<Ident>()?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            Ok(Some(ident))
+        }
+        _ => Ok(None),
+    }
+}
+
 impl ExtendWithFunctionAttrs for FixtureInfo {
     fn extend_with_function_attrs(
         &mut self,
@@ -59,22 +95,39 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             default_return_type,
             partials_return_type,
             once,
+            teardown,
+            _check_timeout,
+            timeout,
+            fallible,
             futures,
-            global_awt
+            global_awt,
+            concurrent,
+            by_types,
+            by_refs,
+            value_list
         ) = merge_errors!(
             extract_fixtures(item_fn),
             extract_defaults(item_fn),
             extract_default_return_type(item_fn),
             extract_partials_return_type(item_fn),
             extract_once(item_fn),
+            extract_teardown(item_fn),
+            check_timeout_attrs(item_fn),
+            extract_fixture_timeout(item_fn),
+            extract_fallible(item_fn),
             extract_futures(item_fn),
-            extract_global_awt(item_fn)
+            extract_global_awt(item_fn),
+            extract_concurrent(item_fn),
+            extract_by_types(item_fn),
+            extract_by_refs(item_fn),
+            extract_value_list(item_fn)
         )?;
         self.data.items.extend(
             fixtures
                 .into_iter()
                 .map(|f| f.into())
-                .chain(defaults.into_iter().map(|d| d.into())),
+                .chain(defaults.into_iter().map(|d| d.into()))
+                .chain(value_list.into_iter().map(|v| v.into())),
         );
         if let Some(return_type) = default_return_type {
             self.attributes.set_default_return_type(return_type);
@@ -83,8 +136,14 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             self.attributes.set_partial_return_type(id, return_type);
         }
         self.arguments.set_once(once);
+        self.arguments.set_teardown(teardown);
+        self.arguments.set_timeout(timeout);
+        self.arguments.set_fallible(fallible);
         self.arguments.set_global_await(global_awt);
+        self.arguments.set_concurrent(concurrent);
         self.arguments.set_futures(futures.into_iter());
+        self.arguments.set_by_types(by_types.into_iter());
+        self.arguments.set_by_refs(by_refs.into_iter());
         Ok(())
     }
 }
@@ -139,7 +198,7 @@ impl VisitMut for FixturesFunctionExtractor {
             self.1.extend(errors.into_iter());
             if pos.is_some() || resolve.is_some() {
                 self.0
-                    .push(Fixture::new(name, resolve, pos.unwrap_or_default()))
+                    .push(Fixture::with_args(name, resolve, pos.unwrap_or_default()))
             }
         }
     }
@@ -190,6 +249,19 @@ impl FixtureData {
             _ => None,
         })
     }
+
+    /// The values lists of a parametrized fixture: it has a variant for each combination
+    /// of them.
+    pub(crate) fn list_values(&self) -> impl Iterator<Item = &ValueList> {
+        self.items.iter().filter_map(|f| match f {
+            FixtureItem::ValueList(ref value_list) => Some(value_list),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn has_list_values(&self) -> bool {
+        self.list_values().next().is_some()
+    }
 }
 
 impl Parse for FixtureData {
@@ -204,7 +276,7 @@ impl Parse for FixtureData {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ArgumentValue {
     pub name: Ident,
     pub expr: Expr,
@@ -220,6 +292,7 @@ impl ArgumentValue {
 pub(crate) enum FixtureItem {
     Fixture(Fixture),
     ArgumentValue(Box<ArgumentValue>),
+    ValueList(ValueList),
 }
 
 impl From<Fixture> for FixtureItem {
@@ -228,9 +301,17 @@ impl From<Fixture> for FixtureItem {
     }
 }
 
+impl From<ValueList> for FixtureItem {
+    fn from(value_list: ValueList) -> Self {
+        FixtureItem::ValueList(value_list)
+    }
+}
+
 impl Parse for FixtureItem {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.peek2(Token![=]) {
+        if input.peek2(Token![=>]) {
+            input.parse::<ValueList>().map(|v| v.into())
+        } else if input.peek2(Token![=]) {
             input.parse::<ArgumentValue>().map(|v| v.into())
         } else {
             input.parse::<Fixture>().map(|v| v.into())
@@ -243,6 +324,7 @@ impl RefIdent for FixtureItem {
         match self {
             FixtureItem::Fixture(Fixture { ref name, .. }) => name,
             FixtureItem::ArgumentValue(ref av) => &av.name,
+            FixtureItem::ValueList(ref value_list) => value_list.ident(),
         }
     }
 }
@@ -273,6 +355,16 @@ wrap_attributes!(FixtureModifiers);
 impl FixtureModifiers {
     pub(crate) const DEFAULT_RET_ATTR: &'static str = "default";
     pub(crate) const PARTIAL_RET_ATTR: &'static str = "partial_";
+    pub(crate) const PROVIDES_ATTR: &'static str = "provides";
+
+    /// The `provides` modifier, if the fixture should implement `rstest::Provide` for its
+    /// type.
+    pub(crate) fn provides(&self) -> Option<&Ident> {
+        self.iter().find_map(|m| match m {
+            Attribute::Attr(ident) if ident == Self::PROVIDES_ATTR => Some(ident),
+            _ => None,
+        })
+    }
 
     pub(crate) fn extract_default_type(&self) -> Option<syn::ReturnType> {
         self.extract_type(Self::DEFAULT_RET_ATTR)
@@ -385,6 +477,25 @@ mod should {
             assert_eq!(expected, data);
         }
 
+        #[rstest]
+        #[case::alone("provides")]
+        #[case::as_modifier("::provides")]
+        #[case::before_fixtures("provides, my_fixture(42)")]
+        #[case::before_modifiers("provides::trace")]
+        fn provides(#[case] input: &str) {
+            let info = parse_fixture(input);
+
+            assert_eq!(Some(&ident("provides")), info.attributes.provides());
+            assert!(info.data.fixtures().all(|f| f.name != "provides"));
+        }
+
+        #[test]
+        fn not_provides() {
+            let info = parse_fixture("my_fixture(42)::trace");
+
+            assert!(info.attributes.provides().is_none());
+        }
+
         #[rstest]
         #[case("first(42),", 1)]
         #[case("first(42), second=42,", 2)]
@@ -434,6 +545,30 @@ mod extend {
             assert_eq!(expected, info);
         }
 
+        #[test]
+        fn use_named_with_attributes() {
+            let mut item_fn: ItemFn = r#"
+                fn my_fix(#[with(age = 42, country = "IT")] user: User, #[with(2)] f: u32) {}
+            "#
+            .ast();
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("user", &[])
+                        .with_named(&[("age", "42"), ("country", r#""IT""#)])
+                        .into(),
+                    fixture("f", &["2"]).into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert_eq!(expected, info);
+        }
+
         #[test]
         fn rename_with_attributes() {
             let mut item_fn = r#"
@@ -578,6 +713,77 @@ mod extend {
             assert!(!info.arguments.is_once());
         }
 
+        #[test]
+        fn find_timeout_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[timeout(std::time::Duration::from_millis(10))]
+                fn server() -> Server {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                info.arguments.get_timeout(),
+                Some(&"std::time::Duration::from_millis(10)".ast())
+            );
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_fallible_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[fallible]
+                fn db() -> Result<Db, Error> {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.arguments.is_fallible());
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_teardown_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[teardown(|| std::fs::remove_file("some").unwrap())]
+                #[last::more]
+                fn my_fix() -> u32 {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                info.arguments.get_teardown(),
+                Some(&expr(r#"|| std::fs::remove_file("some").unwrap()"#))
+            );
+            assert_eq!(attrs("#[simple]#[last::more]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn raise_error_for_bare_teardown_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[teardown]
+                fn my_fix() -> u32 {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            assert!(info.extend_with_function_attrs(&mut item_fn).is_err());
+        }
+
         #[rstest]
         fn extract_future() {
             let mut item_fn = "fn f(#[future] a: u32, b: u32) {}".ast();
@@ -625,6 +831,19 @@ mod extend {
                 assert_eq!(1, errors.len());
             }
 
+            #[rstest]
+            #[case::mix_positional_and_named("#[with(1, age = 42)]", "Cannot mix")]
+            #[case::named_more_than_once("#[with(age = 1, age = 2)]", "Duplicate argument")]
+            fn for_invalid_named_arguments(#[case] attr: &str, #[case] message: &str) {
+                let mut item_fn: ItemFn = format!("fn my_fix({attr} user: User) {{}}").ast();
+
+                let errors = FixtureInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_in!(format!("{:?}", errors), message);
+            }
+
             #[test]
             fn with_used_more_than_once() {
                 let mut item_fn: ItemFn = r#"
@@ -674,6 +893,55 @@ mod extend {
                 );
             }
 
+            #[test]
+            fn if_teardown_is_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    #[teardown(|| {})]
+                    #[teardown(|| {})]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[teardown] more than once"
+                );
+            }
+
+            #[rstest]
+            #[case::more_than_once(
+                "#[timeout(d)] #[timeout(d)] fn my_fix() -> u32 {}",
+                "cannot use #[timeout] more than once"
+            )]
+            #[case::invalid_expression("#[timeout(<d>)] fn my_fix() -> u32 {}", "expected")]
+            fn if_timeout_is_invalid(#[case] item_fn: &str, #[case] message: &str) {
+                let mut item_fn: ItemFn = item_fn.ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_eq!(1, error.len());
+                assert_in!(format!("{:?}", error).to_lowercase(), message);
+            }
+
+            #[cfg(not(feature = "async-timeout"))]
+            #[test]
+            fn if_async_timeout_feature_is_disabled() {
+                let mut item_fn: ItemFn = "#[timeout(d)] async fn my_fix() -> u32 {}".ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_eq!(1, error.len());
+                assert_in!(format!("{:?}", error), "async-timeout feature");
+            }
+
             #[test]
             fn if_default_is_defined_more_than_once() {
                 let mut item_fn: ItemFn = r#"
@@ -0,0 +1,143 @@
+use quote::ToTokens;
+use syn::{
+    parse_quote, visit_mut::VisitMut, FnArg, GenericArgument, Ident, ItemFn, PathArguments, Type,
+};
+
+use crate::{error::ErrorsVec, utils::attr_is};
+
+use super::extract_argument_attrs;
+
+/// Extract the arguments annotated by `#[lazy]`.
+pub(crate) fn extract_lazies(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = LaziesFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the lazy ones.
+#[derive(Default)]
+struct LaziesFunctionExtractor {
+    lazies: Vec<Ident>,
+    errors: Vec<syn::Error>,
+}
+
+impl LaziesFunctionExtractor {
+    pub(crate) fn take(self) -> Result<Vec<Ident>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.lazies)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for LaziesFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        match extract_argument_attrs(
+            node,
+            |a| attr_is(a, "lazy"),
+            |arg, name| match arg.meta {
+                syn::Meta::Path(_) => Ok((arg, name.clone())),
+                _ => Err(syn::Error::new_spanned(
+                    arg.into_token_stream(),
+                    "#[lazy] doesn't take any argument.",
+                )),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(lazies) => {
+                if let Some((_, name)) = lazies.first() {
+                    self.lazies.push(name.clone());
+                    if let FnArg::Typed(arg) = node {
+                        elide_lifetime(&mut arg.ty);
+                    }
+                }
+                self.errors.extend(lazies.iter().skip(1).map(|(attr, _)| {
+                    syn::Error::new_spanned(
+                        attr.into_token_stream(),
+                        "Cannot use #[lazy] more than once.",
+                    )
+                }));
+            }
+            Err(e) => {
+                self.errors.push(e);
+            }
+        };
+    }
+}
+
+/// `Lazy` and `AsyncLazy` borrow the test's values: write their lifetime as `'_` if
+/// it's missing, because `async` functions' arguments cannot leave it implicit.
+fn elide_lifetime(ty: &mut Type) {
+    let Type::Path(path) = ty else {
+        return;
+    };
+    let Some(PathArguments::AngleBracketed(arguments)) = path
+        .path
+        .segments
+        .last_mut()
+        .map(|segment| &mut segment.arguments)
+    else {
+        return;
+    };
+    if !arguments
+        .args
+        .iter()
+        .any(|a| matches!(a, GenericArgument::Lifetime(_)))
+    {
+        arguments.args.insert(0, parse_quote! { '_ });
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[lazy] db: Lazy<Db>) {}", "fn f(db: Lazy<'_, Db>) {}", &["db"])]
+    #[case::among_others(
+        "fn f(a: u32, #[lazy] db: rstest::AsyncLazy<Db>, #[lazy] b: Lazy<u32>) {}",
+        "fn f(a: u32, db: rstest::AsyncLazy<'_, Db>, b: Lazy<'_, u32>) {}",
+        &["db", "b"]
+    )]
+    #[case::explicit_lifetime(
+        "fn f(#[lazy] db: Lazy<'static, Db>) {}",
+        "fn f(db: Lazy<'static, Db>) {}",
+        &["db"]
+    )]
+    #[case::alias("fn f(#[lazy] db: LazyDb) {}", "fn f(db: LazyDb) {}", &["db"])]
+    #[case::no_one("fn f(db: Lazy<Db>) {}", "fn f(db: Lazy<Db>) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_lazies: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let lazies = extract_lazies(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            lazies,
+            expected_lazies
+                .iter()
+                .copied()
+                .map(ident)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[lazy] #[lazy] db: Lazy<Db>) {}", "more than once")]
+    #[case::no_arguments("fn f(#[lazy(all)] db: Lazy<Db>) {}", "doesn't take any argument")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_lazies(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -25,11 +25,19 @@ use self::{expressions::Expressions, vlist::ValueList};
 #[macro_use]
 pub(crate) mod macros;
 
+pub(crate) mod autouse;
+pub(crate) mod bundle;
+pub(crate) mod by_ref;
+pub(crate) mod by_type;
+pub(crate) mod context;
 pub(crate) mod expressions;
 pub(crate) mod fixture;
 pub(crate) mod future;
+pub(crate) mod lazy;
 pub(crate) mod rstest;
+pub(crate) mod scope;
 pub(crate) mod testcase;
+pub(crate) mod variants;
 pub(crate) mod vlist;
 
 pub(crate) trait ExtendWithFunctionAttrs {
@@ -130,11 +138,65 @@ impl Parse for Positional {
     }
 }
 
+/// Fixture's arguments: just positional (`f(42, "IT")`) or just named
+/// (`f(age = 42, country = "IT")`).
+#[derive(PartialEq, Debug, Clone, Default)]
+pub(crate) struct FixtureArgs {
+    pub(crate) positional: Positional,
+    pub(crate) named: Vec<ArgumentValue>,
+}
+
+impl Parse for FixtureArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        for expr in Punctuated::<syn::Expr, Token![,]>::parse_terminated(input)? {
+            match named_argument(&expr) {
+                Some(named) => {
+                    if args.named.iter().any(|n| n.name == named.name) {
+                        return Err(syn::Error::new(
+                            named.name.span(),
+                            format!("Duplicate argument: '{}' is already given", named.name),
+                        ));
+                    }
+                    args.named.push(named)
+                }
+                None => args.positional.0.push(expr),
+            }
+        }
+        if !args.named.is_empty() && !args.positional.0.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &args.positional.0[0],
+                "Cannot mix positional and named fixture arguments",
+            ));
+        }
+        Ok(args)
+    }
+}
+
+fn named_argument(expr: &syn::Expr) -> Option<ArgumentValue> {
+    match expr {
+        syn::Expr::Assign(syn::ExprAssign {
+            attrs, left, right, ..
+        }) if attrs.is_empty() => match left.as_ref() {
+            syn::Expr::Path(syn::ExprPath {
+                attrs,
+                qself: None,
+                path,
+            }) if attrs.is_empty() => path
+                .get_ident()
+                .map(|name| ArgumentValue::new(name.clone(), right.as_ref().clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct Fixture {
     pub(crate) name: Ident,
     pub(crate) resolve: Option<Ident>,
     pub(crate) positional: Positional,
+    pub(crate) named: Vec<ArgumentValue>,
 }
 
 impl Fixture {
@@ -143,6 +205,14 @@ impl Fixture {
             name,
             resolve,
             positional,
+            named: Default::default(),
+        }
+    }
+
+    pub(crate) fn with_args(name: Ident, resolve: Option<Ident>, args: FixtureArgs) -> Self {
+        Self {
+            named: args.named,
+            ..Self::new(name, resolve, args.positional)
         }
     }
 }
@@ -151,7 +221,7 @@ impl Parse for Fixture {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let resolve = input.parse()?;
         if input.peek(Paren) || input.peek(Token![as]) {
-            let positional = if input.peek(Paren) {
+            let args = if input.peek(Paren) {
                 let content;
                 let _ = syn::parenthesized!(content in input);
                 content.parse()?
@@ -161,9 +231,9 @@ impl Parse for Fixture {
 
             if input.peek(Token![as]) {
                 let _: Token![as] = input.parse()?;
-                Ok(Self::new(input.parse()?, Some(resolve), positional))
+                Ok(Self::with_args(input.parse()?, Some(resolve), args))
             } else {
-                Ok(Self::new(resolve, None, positional))
+                Ok(Self::with_args(resolve, None, args))
             }
         } else {
             Err(syn::Error::new(
@@ -225,11 +295,53 @@ pub(crate) fn extract_partials_return_type(
 }
 
 pub(crate) fn extract_once(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
-    let mut extractor = IsOnceAttributeFunctionExtractor::default();
+    let mut extractor = FlagAttributeFunctionExtractor::new("once");
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_fallible(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+    let mut extractor = FlagAttributeFunctionExtractor::new("fallible");
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_concurrent(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+    let mut extractor = FlagAttributeFunctionExtractor::new("concurrent");
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_teardown(item_fn: &mut ItemFn) -> Result<Option<syn::Expr>, ErrorsVec> {
+    let mut extractor = TeardownFunctionExtractor::default();
     extractor.visit_item_fn_mut(item_fn);
     extractor.take()
 }
 
+/// Extract the `#[timeout(expr)]` of a fixture: unlike the tests' ones it's consumed
+/// and used to resolve the fixture.
+pub(crate) fn extract_fixture_timeout(
+    item_fn: &mut ItemFn,
+) -> Result<Option<syn::Expr>, ErrorsVec> {
+    let attrs = std::mem::take(&mut item_fn.attrs);
+    let (timeouts, remain): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|attr| attr_is(attr, "timeout"));
+    item_fn.attrs = remain;
+    let mut timeouts = timeouts.into_iter();
+    // Invalid expressions are reported by `check_timeout_attrs`
+    let timeout = timeouts
+        .next()
+        .and_then(|attr| attr.parse_args::<syn::Expr>().ok());
+    let errors = timeouts
+        .map(|attr| syn::Error::new_spanned(attr, "You cannot use #[timeout] more than once"))
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(timeout)
+    } else {
+        Err(errors.into())
+    }
+}
+
 pub(crate) fn extract_argument_attrs<'a, B: 'a + std::fmt::Debug>(
     node: &mut FnArg,
     is_valid_attr: fn(&syn::Attribute) -> bool,
@@ -355,42 +467,96 @@ impl VisitMut for PartialsTypeFunctionExtractor {
     }
 }
 
-/// Simple struct used to visit function attributes and extract once
-/// type
-struct IsOnceAttributeFunctionExtractor(Result<Option<Ident>, ErrorsVec>);
+/// Simple struct used to visit function attributes and extract a flag attribute
+/// like `#[once]`
+struct FlagAttributeFunctionExtractor {
+    name: &'static str,
+    result: Result<Option<Ident>, ErrorsVec>,
+}
 
-impl IsOnceAttributeFunctionExtractor {
-    fn take(self) -> Result<Option<Ident>, ErrorsVec> {
-        self.0
+impl FlagAttributeFunctionExtractor {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            result: Ok(None),
+        }
     }
-}
 
-impl Default for IsOnceAttributeFunctionExtractor {
-    fn default() -> Self {
-        Self(Ok(None))
+    fn take(self) -> Result<Option<Ident>, ErrorsVec> {
+        self.result
     }
 }
 
-impl VisitMut for IsOnceAttributeFunctionExtractor {
+impl VisitMut for FlagAttributeFunctionExtractor {
     fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
         let attrs = std::mem::take(&mut node.attrs);
-        let (onces, remain): (Vec<_>, Vec<_>) =
-            attrs.into_iter().partition(|attr| attr_is(attr, "once"));
+        let (flags, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(attr, self.name));
 
         node.attrs = remain;
-        self.0 = match onces.len() {
-            1 => Ok(onces[0].path().get_ident().cloned()),
+        self.result = match flags.len() {
+            1 => Ok(flags[0].path().get_ident().cloned()),
             0 => Ok(None),
-            _ => Err(onces
+            _ => Err(flags
                 .into_iter()
                 .skip(1)
-                .map(|attr| syn::Error::new_spanned(attr, "You cannot use #[once] more than once"))
+                .map(|attr| {
+                    syn::Error::new_spanned(
+                        attr,
+                        format!("You cannot use #[{}] more than once", self.name),
+                    )
+                })
                 .collect::<Vec<_>>()
                 .into()),
         };
     }
 }
 
+/// Simple struct used to visit function attributes and extract the teardown
+/// expression
+struct TeardownFunctionExtractor(Result<Option<syn::Expr>, ErrorsVec>);
+
+impl TeardownFunctionExtractor {
+    fn take(self) -> Result<Option<syn::Expr>, ErrorsVec> {
+        self.0
+    }
+}
+
+impl Default for TeardownFunctionExtractor {
+    fn default() -> Self {
+        Self(Ok(None))
+    }
+}
+
+impl VisitMut for TeardownFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (teardowns, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(attr, "teardown"));
+
+        node.attrs = remain;
+        let mut teardowns = teardowns.into_iter();
+        let mut errors = ErrorsVec::default();
+        let data = match teardowns.next().map(|attr| attr.parse_args::<syn::Expr>()) {
+            Some(Ok(expr)) => Some(expr),
+            Some(Err(e)) => {
+                errors.push(e);
+                None
+            }
+            None => None,
+        };
+        errors.extend(teardowns.map(|attr| {
+            syn::Error::new_spanned(attr, "You cannot use #[teardown] more than once")
+        }));
+        self.0 = if errors.is_empty() {
+            Ok(data)
+        } else {
+            Err(errors)
+        };
+    }
+}
+
 /// Simple struct used to visit function attributes and extract case arguments and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -429,6 +595,8 @@ impl VisitMut for CasesFunctionExtractor {
     fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
         let attrs = std::mem::take(&mut node.attrs);
         let mut attrs_buffer = Default::default();
+        let mut fixtures_buffer = Vec::new();
+        let mut with_attrs = Vec::new();
         let case: syn::PathSegment = parse_quote! { case };
         for attr in attrs.into_iter() {
             if attr_starts_with(&attr, &case) {
@@ -440,14 +608,28 @@ impl VisitMut for CasesFunctionExtractor {
                             args: expressions.into(),
                             attrs: std::mem::take(&mut attrs_buffer),
                             description,
+                            fixtures: std::mem::take(&mut fixtures_buffer),
                         });
+                        with_attrs.clear();
                     }
                     Err(err) => self.1.push(err),
                 };
+            } else if attr_is(&attr, "with") {
+                match attr.parse_args_with(Punctuated::<Fixture, Token![,]>::parse_terminated) {
+                    Ok(fixtures) => fixtures_buffer.extend(fixtures),
+                    Err(err) => self.1.push(err),
+                };
+                with_attrs.push(attr);
             } else {
                 attrs_buffer.push(attr)
             }
         }
+        self.1.extend(with_attrs.into_iter().map(|attr| {
+            syn::Error::new_spanned(
+                attr,
+                "#[with(...)] on a test should be followed by the #[case(...)] it applies to.",
+            )
+        }));
         node.attrs = std::mem::take(&mut attrs_buffer);
     }
 }
@@ -580,7 +762,7 @@ impl CheckTimeoutAttributesFunction {
             asyncness.map(|a| {
                 syn::Error::new(
                     a.span,
-                    "Enable async-timeout feature to use timeout in async tests",
+                    "Enable async-timeout feature to use timeout in async tests and fixtures",
                 )
             })
         }
@@ -638,16 +820,22 @@ pub(crate) mod arguments {
         Await,
     }
 
-    
-
     #[derive(PartialEq, Default, Debug)]
     pub(crate) struct ArgumentInfo {
         future: FutureArg,
+        group: bool,
+        context: bool,
+        lazy: bool,
+        by_type: bool,
+        by_ref: bool,
     }
 
     impl ArgumentInfo {
         fn future(future: FutureArg) -> Self {
-            Self { future }
+            Self {
+                future,
+                ..Default::default()
+            }
         }
 
         fn is_future(&self) -> bool {
@@ -668,6 +856,10 @@ pub(crate) mod arguments {
         args: HashMap<Ident, ArgumentInfo>,
         is_global_await: bool,
         once: Option<Ident>,
+        teardown: Option<syn::Expr>,
+        timeout: Option<syn::Expr>,
+        fallible: Option<Ident>,
+        concurrent: Option<Ident>,
     }
 
     impl ArgumentsInfo {
@@ -709,6 +901,69 @@ pub(crate) mod arguments {
             self.is_global_await
         }
 
+        pub(crate) fn set_group(&mut self, ident: Ident) {
+            self.args.entry(ident).or_default().group = true;
+        }
+
+        pub(crate) fn set_groups(&mut self, groups: impl Iterator<Item = Ident>) {
+            groups.for_each(|ident| self.set_group(ident));
+        }
+
+        pub(crate) fn is_group(&self, id: &Ident) -> bool {
+            self.args.get(id).map(|arg| arg.group).unwrap_or_default()
+        }
+
+        pub(crate) fn set_context(&mut self, ident: Ident) {
+            self.args.entry(ident).or_default().context = true;
+        }
+
+        pub(crate) fn set_contexts(&mut self, contexts: impl Iterator<Item = Ident>) {
+            contexts.for_each(|ident| self.set_context(ident));
+        }
+
+        pub(crate) fn contexts(&self) -> impl Iterator<Item = &Ident> {
+            self.args
+                .iter()
+                .filter(|(_, arg)| arg.context)
+                .map(|(ident, _)| ident)
+        }
+
+        pub(crate) fn set_lazy(&mut self, ident: Ident) {
+            self.args.entry(ident).or_default().lazy = true;
+        }
+
+        pub(crate) fn set_lazies(&mut self, lazies: impl Iterator<Item = Ident>) {
+            lazies.for_each(|ident| self.set_lazy(ident));
+        }
+
+        pub(crate) fn is_lazy(&self, id: &Ident) -> bool {
+            self.args.get(id).map(|arg| arg.lazy).unwrap_or_default()
+        }
+
+        pub(crate) fn set_by_type(&mut self, ident: Ident) {
+            self.args.entry(ident).or_default().by_type = true;
+        }
+
+        pub(crate) fn set_by_types(&mut self, by_types: impl Iterator<Item = Ident>) {
+            by_types.for_each(|ident| self.set_by_type(ident));
+        }
+
+        pub(crate) fn is_by_type(&self, id: &Ident) -> bool {
+            self.args.get(id).map(|arg| arg.by_type).unwrap_or_default()
+        }
+
+        pub(crate) fn set_by_ref(&mut self, ident: Ident) {
+            self.args.entry(ident).or_default().by_ref = true;
+        }
+
+        pub(crate) fn set_by_refs(&mut self, by_refs: impl Iterator<Item = Ident>) {
+            by_refs.for_each(|ident| self.set_by_ref(ident));
+        }
+
+        pub(crate) fn is_by_ref(&self, id: &Ident) -> bool {
+            self.args.get(id).map(|arg| arg.by_ref).unwrap_or_default()
+        }
+
         pub(crate) fn set_once(&mut self, once: Option<Ident>) {
             self.once = once
         }
@@ -720,6 +975,43 @@ pub(crate) mod arguments {
         pub(crate) fn is_once(&self) -> bool {
             self.get_once().is_some()
         }
+
+        pub(crate) fn set_teardown(&mut self, teardown: Option<syn::Expr>) {
+            self.teardown = teardown
+        }
+
+        pub(crate) fn get_teardown(&self) -> Option<&syn::Expr> {
+            self.teardown.as_ref()
+        }
+
+        pub(crate) fn set_timeout(&mut self, timeout: Option<syn::Expr>) {
+            self.timeout = timeout
+        }
+
+        pub(crate) fn get_timeout(&self) -> Option<&syn::Expr> {
+            self.timeout.as_ref()
+        }
+
+        pub(crate) fn set_fallible(&mut self, fallible: Option<Ident>) {
+            self.fallible = fallible
+        }
+
+        pub(crate) fn get_fallible(&self) -> Option<&Ident> {
+            self.fallible.as_ref()
+        }
+
+        pub(crate) fn is_fallible(&self) -> bool {
+            self.get_fallible().is_some()
+        }
+
+        pub(crate) fn set_concurrent(&mut self, concurrent: Option<Ident>) {
+            self.concurrent = concurrent
+        }
+
+        /// Whether the awaited future arguments should be awaited concurrently.
+        pub(crate) fn is_concurrent(&self) -> bool {
+            self.concurrent.is_some()
+        }
     }
 
     #[cfg(test)]
@@ -3,25 +3,38 @@ use syn::{
     Ident, ItemFn, Token,
 };
 
-use self::files::{extract_files, ValueListFromFiles};
+use self::{
+    cases_from::{extract_cases_from, CasesFromFiles},
+    files::{extract_files, ValueListFromFiles},
+};
 
 use super::{
     arguments::ArgumentsInfo,
-    check_timeout_attrs, extract_case_args, extract_cases, extract_excluded_trace,
-    extract_fixtures, extract_value_list,
+    autouse::check_autouse_attrs,
+    by_ref::extract_by_refs,
+    by_type::extract_by_types,
+    context::extract_contexts,
+    check_timeout_attrs, extract_case_args, extract_cases, extract_concurrent,
+    extract_excluded_trace, extract_fixtures, extract_value_list,
     future::{extract_futures, extract_global_awt},
+    lazy::extract_lazies,
     parse_vector_trailing_till_double_comma,
+    scope::extract_groups,
     testcase::TestCase,
+    variants::{extract_fixture_variants, FixtureVariants},
     Attribute, Attributes, ExtendWithFunctionAttrs, Fixture,
 };
 use crate::parse::vlist::ValueList;
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
+    utils::fn_args_idents,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, ToTokens};
+use std::path::PathBuf;
 
+pub(crate) mod cases_from;
 pub(crate) mod files;
 
 #[derive(PartialEq, Debug, Default)]
@@ -50,16 +63,42 @@ impl Parse for RsTestInfo {
 
 impl ExtendWithFunctionAttrs for RsTestInfo {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(_inner, excluded, _timeout, futures, global_awt) = merge_errors!(
+        let composed_tuple!(
+            _inner,
+            excluded,
+            _timeout,
+            _autouse,
+            futures,
+            global_awt,
+            concurrent,
+            groups,
+            contexts,
+            lazies,
+            by_types,
+            by_refs
+        ) = merge_errors!(
             self.data.extend_with_function_attrs(item_fn),
             extract_excluded_trace(item_fn),
             check_timeout_attrs(item_fn),
+            check_autouse_attrs(item_fn),
             extract_futures(item_fn),
-            extract_global_awt(item_fn)
+            extract_global_awt(item_fn),
+            extract_concurrent(item_fn),
+            extract_groups(item_fn),
+            extract_contexts(item_fn),
+            extract_lazies(item_fn),
+            extract_by_types(item_fn),
+            extract_by_refs(item_fn)
         )?;
         self.attributes.add_notraces(excluded);
         self.arguments.set_global_await(global_awt);
+        self.arguments.set_concurrent(concurrent);
         self.arguments.set_futures(futures.into_iter());
+        self.arguments.set_groups(groups.into_iter());
+        self.arguments.set_contexts(contexts.into_iter());
+        self.arguments.set_lazies(lazies.into_iter());
+        self.arguments.set_by_types(by_types.into_iter());
+        self.arguments.set_by_refs(by_refs.into_iter());
         Ok(())
     }
 }
@@ -67,6 +106,10 @@ impl ExtendWithFunctionAttrs for RsTestInfo {
 #[derive(PartialEq, Debug, Default)]
 pub(crate) struct RsTestData {
     pub(crate) items: Vec<RsTestItem>,
+    /// The data files read to build the test: it should be compiled again when they change.
+    pub(crate) tracked_files: Vec<PathBuf>,
+    /// The arguments that take the variants of a parametrized fixture
+    pub(crate) fixture_variants: Vec<FixtureVariants>,
 }
 
 impl RsTestData {
@@ -115,6 +158,14 @@ impl RsTestData {
     pub(crate) fn has_list_values(&self) -> bool {
         self.list_values().next().is_some()
     }
+
+    /// The first argument whose fixture's variants are not known yet: the fixture's
+    /// callback macro should give them.
+    pub(crate) fn unresolved_variants(&self) -> Option<&FixtureVariants> {
+        self.fixture_variants
+            .iter()
+            .find(|variants| variants.descriptions.is_none())
+    }
 }
 
 impl Parse for RsTestData {
@@ -124,6 +175,7 @@ impl Parse for RsTestData {
         } else {
             Ok(Self {
                 items: parse_vector_trailing_till_double_comma::<_, Token![,]>(input)?,
+                ..Default::default()
             })
         }
     }
@@ -131,18 +183,47 @@ impl Parse for RsTestData {
 
 impl ExtendWithFunctionAttrs for RsTestData {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(fixtures, case_args, cases, value_list, files) = merge_errors!(
+        let composed_tuple!(
+            fixtures,
+            fixture_variants,
+            case_args,
+            cases_from,
+            cases,
+            value_list,
+            files
+        ) = merge_errors!(
             extract_fixtures(item_fn),
+            // Before the cases: they would take the variants as their attributes
+            extract_fixture_variants(item_fn),
             extract_case_args(item_fn),
+            extract_cases_from(item_fn),
             extract_cases(item_fn),
             extract_value_list(item_fn),
             extract_files(item_fn)
         )?;
+        let (cases_from, tracked_files) =
+            CasesFromFiles::default().to_test_cases(&case_args, cases_from)?;
+        let mut fixtures = fixtures;
+        let mut fixture_variants = fixture_variants;
+        let errors = fixture_variants
+            .iter_mut()
+            .filter_map(|variants| variants.take_fixture(&mut fixtures).err())
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(errors.into());
+        }
 
         self.items.extend(fixtures.into_iter().map(|f| f.into()));
         self.items.extend(case_args.into_iter().map(|f| f.into()));
         self.items.extend(cases.into_iter().map(|f| f.into()));
+        self.items.extend(cases_from.into_iter().map(|f| f.into()));
+        self.tracked_files.extend(tracked_files);
+        // The variants of the fixtures are values lists too: keep the arguments' order
+        let mut value_list = value_list;
+        value_list.extend(fixture_variants.iter().filter_map(FixtureVariants::value_list));
+        value_list.sort_by_key(|list| fn_args_idents(item_fn).position(|arg| arg == &list.arg));
         self.items.extend(value_list.into_iter().map(|f| f.into()));
+        self.fixture_variants.extend(fixture_variants);
         self.items.extend(
             ValueListFromFiles::default()
                 .to_value_list(files)?
@@ -281,6 +362,7 @@ impl Parse for RsTestAttributes {
 mod test {
     use super::*;
     use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
 
     mod parse_rstest_data {
         use super::assert_eq;
@@ -296,6 +378,7 @@ mod test {
 
             let expected = RsTestData {
                 items: vec![fixture("my_fixture", &["42"]).into()],
+                ..Default::default()
             };
 
             assert_eq!(expected, fixtures);
@@ -321,6 +404,41 @@ mod test {
         assert_eq!(2, errors.len());
     }
 
+    #[test]
+    fn should_extract_concurrent() {
+        let mut item_fn = r#"
+            #[concurrent]
+            #[awt]
+            async fn test_fn(#[future] a: u32, #[future] b: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        assert!(info.arguments.is_concurrent());
+        assert!(item_fn.attrs.is_empty());
+    }
+
+    #[test]
+    fn should_return_error_for_more_than_one_concurrent() {
+        let mut item_fn = r#"
+            #[concurrent]
+            #[concurrent]
+            async fn test_fn(#[future(awt)] a: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), "more than once");
+    }
+
     #[cfg(feature = "async-timeout")]
     #[test]
     fn should_parse_async_timeout() {
@@ -706,6 +824,62 @@ mod test {
                 );
             }
 
+            #[test]
+            fn should_collect_case_fixtures() {
+                let mut item_fn = r#"
+                    #[with(user("admin"), db(1))]
+                    #[should_panic]
+                    #[case(42)]
+                    #[case(24)]
+                    #[with(user(role = "guest"))]
+                    #[case(0)]
+                    fn test_fn(#[case] arg: u32, user: User, db: Db) {
+                    }
+                "#
+                .ast();
+
+                let mut info = RsTestInfo::default();
+
+                info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+                let cases = info.data.cases().cloned().collect::<Vec<_>>();
+
+                assert_eq!(
+                    vec![
+                        TestCase::from_iter(["42"].iter())
+                            .with_attrs(attrs("#[should_panic]"))
+                            .with_fixtures(&[r#"user("admin")"#, "db(1)"]),
+                        TestCase::from_iter(["24"].iter()),
+                        TestCase::from_iter(["0"].iter())
+                            .with_fixtures(&[r#"user(role = "guest")"#]),
+                    ],
+                    cases
+                );
+                assert!(item_fn.attrs.is_empty());
+            }
+
+            #[rstest]
+            #[case::not_followed_by_a_case(
+                r#"#[case(42)] #[with(user("admin"))] fn test_fn(#[case] arg: u32, user: User) {}"#,
+                "should be followed by the #[case(...)]"
+            )]
+            #[case::fixture_without_arguments(
+                r#"#[with(user)] #[case(42)] fn test_fn(#[case] arg: u32, user: User) {}"#,
+                "fixture need arguments"
+            )]
+            fn should_raise_error_on_invalid_case_fixtures(
+                #[case] item_fn: &str,
+                #[case] message: &str,
+            ) {
+                let mut item_fn = item_fn.ast();
+
+                let err = RsTestInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_in!(format!("{:?}", err), message);
+            }
+
             #[test]
             fn should_consume_all_used_attributes() {
                 let mut item_fn = r#"
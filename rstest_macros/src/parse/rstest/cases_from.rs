@@ -0,0 +1,466 @@
+use std::path::{Path, PathBuf};
+
+use relative_path::RelativePath;
+use syn::{parse_quote, Ident, ItemFn, LitStr};
+
+use super::files::{BaseDir, DefaultBaseDir, LitStrAttr, RaiseError};
+use crate::{error::ErrorsVec, parse::testcase::TestCase, utils::attr_is};
+
+/// Entry point function to extract the `#[cases_from("path")]` attributes
+pub(crate) fn extract_cases_from(item_fn: &mut ItemFn) -> Result<Vec<LitStrAttr>, ErrorsVec> {
+    let attrs = std::mem::take(&mut item_fn.attrs);
+    let (cases_from, remain): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr_is(attr, "cases_from"));
+    item_fn.attrs = remain;
+
+    let mut refs = vec![];
+    let mut errors = vec![];
+    for attr in cases_from {
+        match LitStrAttr::try_from(attr) {
+            Ok(r) => refs.push(r),
+            Err(e) => errors.push(e),
+        }
+    }
+    if errors.is_empty() {
+        Ok(refs)
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// A data file's row: the `(column, cell)` pairs.
+type Row = Vec<(String, String)>;
+
+#[cfg(any(feature = "cases-json", feature = "cases-toml"))]
+fn cell_text<V: ToString>(value: &V, as_str: Option<&str>) -> String {
+    as_str
+        .map(str::to_owned)
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// A CSV file with a header line that names the columns.
+#[cfg(feature = "cases-csv")]
+fn csv_rows(content: &str) -> Result<Vec<Row>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map(|record| {
+                    headers
+                        .iter()
+                        .zip(record.iter())
+                        .map(|(column, cell)| (column.to_owned(), cell.to_owned()))
+                        .collect()
+                })
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// A JSON array of objects.
+#[cfg(feature = "cases-json")]
+fn json_rows(content: &str) -> Result<Vec<Row>, String> {
+    let value = serde_json::from_str::<serde_json::Value>(content).map_err(|e| e.to_string())?;
+    value
+        .as_array()
+        .ok_or_else(|| "should contain an array of objects".to_owned())?
+        .iter()
+        .enumerate()
+        .map(|(n, row)| {
+            row.as_object()
+                .ok_or_else(|| format!("case {} should be an object", n + 1))
+                .map(|row| {
+                    row.iter()
+                        .map(|(column, cell)| (column.clone(), cell_text(cell, cell.as_str())))
+                        .collect()
+                })
+        })
+        .collect()
+}
+
+/// The tables of a TOML `[[case]]` array.
+#[cfg(feature = "cases-toml")]
+fn toml_rows(content: &str) -> Result<Vec<Row>, String> {
+    let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+    table
+        .get("case")
+        .and_then(toml::Value::as_array)
+        .ok_or_else(|| "should contain a [[case]] array of tables".to_owned())?
+        .iter()
+        .enumerate()
+        .map(|(n, row)| {
+            row.as_table()
+                .ok_or_else(|| format!("case {} should be a table", n + 1))
+                .map(|row| {
+                    row.iter()
+                        .map(|(column, cell)| (column.clone(), cell_text(cell, cell.as_str())))
+                        .collect()
+                })
+        })
+        .collect()
+}
+
+/// A plain text file: every not empty line is a case for the only `#[case]` argument.
+fn lines_rows(content: &str, case_args: &[Ident]) -> Result<Vec<Row>, String> {
+    let arg = match case_args {
+        [arg] => arg.to_string(),
+        _ => {
+            return Err(format!(
+                "a one case per line file needs exactly one #[case] argument but the test has {}",
+                case_args.len()
+            ))
+        }
+    };
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| vec![(arg.clone(), line.to_owned())])
+        .collect())
+}
+
+type RowsParser = fn(&str, &[Ident]) -> Result<Vec<Row>, String>;
+
+/// Choose the parser by the file's extension: the text with a case per line is used
+/// just for `.txt` files and files without extension.
+fn rows_parser(path: &Path) -> Result<RowsParser, String> {
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        #[cfg(feature = "cases-csv")]
+        Some("csv") => Ok(|content, _| csv_rows(content)),
+        #[cfg(feature = "cases-json")]
+        Some("json") => Ok(|content, _| json_rows(content)),
+        #[cfg(feature = "cases-toml")]
+        Some("toml") => Ok(|content, _| toml_rows(content)),
+        #[allow(unreachable_patterns)]
+        Some(format @ ("csv" | "json" | "toml")) => Err(format!(
+            "enable the `cases-{format}` feature of rstest to read {} files",
+            format.to_uppercase()
+        )),
+        Some("txt") | None => Ok(lines_rows),
+        Some(format) => Err(format!(
+            "unsupported `{format}` file: use CSV (`cases-csv` feature of rstest), JSON \
+            (`cases-json` feature), TOML (`cases-toml` feature) or a text file with a case \
+            per line (`.txt` or no extension)"
+        )),
+    }
+}
+
+/// Map the row's cells to the `#[case]` arguments by the column names.
+fn case_cells(row: Row, case_args: &[Ident]) -> Result<Vec<String>, String> {
+    if let Some((column, _)) = row
+        .iter()
+        .find(|(column, _)| !case_args.iter().any(|arg| arg == column))
+    {
+        return Err(format!("`{column}` is not a #[case] argument"));
+    }
+    case_args
+        .iter()
+        .map(|arg| {
+            row.iter()
+                .find(|(column, _)| arg == column)
+                .map(|(_, cell)| cell.clone())
+                .ok_or_else(|| format!("missed `{arg}` column"))
+        })
+        .collect()
+}
+
+trait FileReader {
+    fn read(&self, path: &Path) -> Result<String, String> {
+        std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))
+    }
+}
+
+struct DefaultFileReader;
+
+impl FileReader for DefaultFileReader {}
+
+/// The struct used to get the test cases from the `#[cases_from]` attributes. You can
+/// inject the base dir resolver and file reader implementation.
+pub(crate) struct CasesFromFiles<'a> {
+    base_dir: Box<dyn BaseDir + 'a>,
+    reader: Box<dyn FileReader + 'a>,
+}
+
+impl<'a> Default for CasesFromFiles<'a> {
+    fn default() -> Self {
+        Self {
+            base_dir: Box::new(DefaultBaseDir),
+            reader: Box::new(DefaultFileReader),
+        }
+    }
+}
+
+impl<'a> CasesFromFiles<'a> {
+    /// Return the test cases and the files' absolute paths: the test should be compiled
+    /// again when they change.
+    pub fn to_test_cases(
+        &self,
+        case_args: &[Ident],
+        refs: Vec<LitStrAttr>,
+    ) -> Result<(Vec<TestCase>, Vec<PathBuf>), syn::Error> {
+        let mut cases = vec![];
+        let mut paths = vec![];
+        for attr in refs {
+            let path = self.path(&attr)?;
+            cases.extend(self.file_cases(&attr, &path, case_args)?);
+            paths.push(path);
+        }
+        Ok((cases, paths))
+    }
+
+    fn path(&self, attr: &LitStrAttr) -> Result<PathBuf, syn::Error> {
+        let base_dir = self.base_dir.base_dir().map_err(|msg| attr.error(&msg))?;
+        RelativePath::from_path(&attr.value())
+            .map_err(|e| attr.error(&format!("Invalid cases file path: {e}")))
+            .map(|p| p.to_logical_path(base_dir))
+    }
+
+    fn file_cases(
+        &self,
+        attr: &LitStrAttr,
+        path: &Path,
+        case_args: &[Ident],
+    ) -> Result<Vec<TestCase>, syn::Error> {
+        let file = attr.value();
+        let error = |msg: String| attr.error(&format!("{file}: {msg}"));
+        if case_args.is_empty() {
+            return Err(error(
+                "#[cases_from] needs some #[case] arguments".to_owned(),
+            ));
+        }
+        let parser = rows_parser(path).map_err(error)?;
+        let content = self.reader.read(path).map_err(|msg| attr.error(&msg))?;
+        let rows = parser(&content, case_args).map_err(error)?;
+        if rows.is_empty() {
+            return Err(error("No case found".to_owned()));
+        }
+        let span = attr.value.span();
+        rows.into_iter()
+            .enumerate()
+            .map(|(n, row)| {
+                case_cells(row, case_args)
+                    .map_err(|msg| error(format!("case {}: {msg}", n + 1)))
+                    .map(|cells| TestCase {
+                        args: cells
+                            .iter()
+                            .map(|cell| {
+                                let lit = LitStr::new(cell, span);
+                                parse_quote! { #lit }
+                            })
+                            .collect(),
+                        attrs: Default::default(),
+                        description: None,
+                        fixtures: Default::default(),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn cases_from_attr(path: &str) -> LitStrAttr {
+        attrs(format!(r#"#[cases_from("{path}")]"#))
+            .into_iter()
+            .next()
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    struct FakeBaseDir;
+
+    impl BaseDir for FakeBaseDir {
+        fn base_dir(&self) -> Result<PathBuf, String> {
+            Ok(PathBuf::from("/base"))
+        }
+    }
+
+    struct FakeFiles(HashMap<PathBuf, String>);
+
+    impl FileReader for FakeFiles {
+        fn read(&self, path: &Path) -> Result<String, String> {
+            self.0
+                .get(path)
+                .cloned()
+                .ok_or_else(|| format!("Cannot read {}", path.display()))
+        }
+    }
+
+    fn cases_from_files(files: &[(&str, &str)]) -> CasesFromFiles<'static> {
+        CasesFromFiles {
+            base_dir: Box::new(FakeBaseDir),
+            reader: Box::new(FakeFiles(
+                files
+                    .iter()
+                    .map(|(path, content)| (PathBuf::from("/base").join(path), content.to_string()))
+                    .collect(),
+            )),
+        }
+    }
+
+    fn case_args(names: &[&str]) -> Vec<Ident> {
+        names.iter().copied().map(ident).collect()
+    }
+
+    #[test]
+    fn extract_the_attributes() {
+        let mut item_fn: ItemFn = r#"
+            #[cases_from("a.csv")]
+            #[other]
+            #[cases_from("b.json")]
+            fn test(#[case] a: u32) {}
+        "#
+        .ast();
+
+        let refs = extract_cases_from(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec!["a.csv", "b.json"],
+            refs.iter().map(|r| r.value()).collect::<Vec<_>>()
+        );
+        assert_eq!(attrs("#[other]"), item_fn.attrs);
+    }
+
+    #[test]
+    fn raise_error_if_the_path_is_not_a_string() {
+        let mut item_fn: ItemFn = r#"#[cases_from(a.csv)] fn test(#[case] a: u32) {}"#.ast();
+
+        assert!(extract_cases_from(&mut item_fn).is_err());
+    }
+
+    #[cfg(all(feature = "cases-csv", feature = "cases-json", feature = "cases-toml"))]
+    #[rstest]
+    #[case::csv("data.csv", "input, expected\n1, 2\n\"a,b\", 42\n")]
+    #[case::csv_other_columns_order("data.csv", "expected,input\n2,1\n42,\"a,b\"\n")]
+    #[case::json(
+        "data.json",
+        r#"[{"input": "1", "expected": 2}, {"expected": "42", "input": "a,b"}]"#
+    )]
+    #[case::toml(
+        "data.toml",
+        "[[case]]\ninput = \"1\"\nexpected = 2\n\n[[case]]\ninput = \"a,b\"\nexpected = 42\n"
+    )]
+    fn map_the_columns_to_the_case_arguments(#[case] path: &str, #[case] content: &str) {
+        let (cases, paths) = cases_from_files(&[(path, content)])
+            .to_test_cases(
+                &case_args(&["input", "expected"]),
+                vec![cases_from_attr(path)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                to_args!([r#""1""#, r#""2""#]),
+                to_args!([r#""a,b""#, r#""42""#])
+            ],
+            cases.iter().map(|c| c.args.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![PathBuf::from("/base").join(path)], paths);
+    }
+
+    #[test]
+    fn use_a_case_for_each_line_of_plain_text_files() {
+        let (cases, _) = cases_from_files(&[("data/words.txt", "first\n\n  second  \n")])
+            .to_test_cases(
+                &case_args(&["word"]),
+                vec![cases_from_attr("data/words.txt")],
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![to_args!([r#""first""#]), to_args!([r#""second""#])],
+            cases.iter().map(|c| c.args.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::csv("data.csv", cfg!(feature = "cases-csv"))]
+    #[case::json("data.json", cfg!(feature = "cases-json"))]
+    #[case::toml("data.toml", cfg!(feature = "cases-toml"))]
+    fn need_the_format_feature(#[case] path: &str, #[case] enabled: bool) {
+        let err = cases_from_files(&[(path, "")])
+            .to_test_cases(&case_args(&["a"]), vec![cases_from_attr(path)])
+            .unwrap_err();
+
+        assert_eq!(!enabled, format!("{:?}", err).contains("feature of rstest"));
+    }
+
+    #[rstest]
+    #[case::yaml("data.yaml")]
+    #[case::upper_case("DATA.YML")]
+    #[case::missed_file("other.ini")]
+    fn raise_error_on_unsupported_formats(#[case] path: &str) {
+        let err = cases_from_files(&[("data.yaml", "- a: 1\n"), ("DATA.YML", "- a: 1\n")])
+            .to_test_cases(&case_args(&["a"]), vec![cases_from_attr(path)])
+            .unwrap_err();
+
+        let message = format!("{:?}", err);
+        rstest_test::assert_in!(message, "unsupported `");
+        for feature in ["cases-csv", "cases-json", "cases-toml"] {
+            rstest_test::assert_in!(message, feature);
+        }
+    }
+
+    #[test]
+    fn use_a_case_for_each_line_of_files_without_extension() {
+        let (cases, _) = cases_from_files(&[("data/words", "first\nsecond\n")])
+            .to_test_cases(&case_args(&["word"]), vec![cases_from_attr("data/words")])
+            .unwrap();
+
+        assert_eq!(2, cases.len());
+    }
+
+    #[cfg(all(feature = "cases-csv", feature = "cases-json", feature = "cases-toml"))]
+    #[test]
+    fn append_the_cases_of_all_files() {
+        let (cases, paths) = cases_from_files(&[("a.txt", "a\nb"), ("b.csv", "x\nc\n")])
+            .to_test_cases(
+                &case_args(&["x"]),
+                vec![cases_from_attr("a.txt"), cases_from_attr("b.csv")],
+            )
+            .unwrap();
+
+        assert_eq!(3, cases.len());
+        assert_eq!(2, paths.len());
+    }
+
+    #[cfg(all(feature = "cases-csv", feature = "cases-json", feature = "cases-toml"))]
+    #[rstest]
+    #[case::missed_file("other.csv", &["a"], "Cannot read")]
+    #[case::no_case_args("data.csv", &[], "needs some #[case] arguments")]
+    #[case::unknown_column("data.csv", &["a"], "case 1: `b` is not a #[case] argument")]
+    #[case::missed_column("data.csv", &["a", "b", "c"], "case 1: missed `c` column")]
+    #[case::more_case_args_for_lines("data.txt", &["a", "b"], "needs exactly one #[case] argument")]
+    #[case::no_cases("empty.csv", &["a"], "No case found")]
+    #[case::wrong_csv("wrong.csv", &["a", "b"], "wrong.csv: ")]
+    #[case::json_not_array("object.json", &["a"], "should contain an array of objects")]
+    #[case::json_not_object("numbers.json", &["a"], "case 1 should be an object")]
+    #[case::toml_without_cases("other.toml", &["a"], "should contain a [[case]] array of tables")]
+    fn raise_error(#[case] path: &str, #[case] args: &[&str], #[case] message: &str) {
+        let err = cases_from_files(&[
+            ("data.csv", "a,b\n1,2\n"),
+            ("data.txt", "a\nb\n"),
+            ("empty.csv", "a\n"),
+            ("wrong.csv", "a,b\n1,2,3\n"),
+            ("object.json", r#"{"a": 1}"#),
+            ("numbers.json", "[1, 2]"),
+            ("other.toml", "[[row]]\na = 1\n"),
+        ])
+        .to_test_cases(&case_args(args), vec![cases_from_attr(path)])
+        .unwrap_err();
+
+        rstest_test::assert_in!(format!("{:?}", err), message);
+    }
+}
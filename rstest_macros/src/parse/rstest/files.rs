@@ -1,4 +1,7 @@
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use glob::glob;
 use quote::ToTokens;
@@ -16,11 +19,75 @@ use crate::{
     utils::attr_is,
 };
 
+/// What `#[files(...)]` injects for each file, as given by `#[mode = ...]`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum FilesMode {
+    /// The file's absolute `PathBuf`
+    #[default]
+    Path,
+    /// The file's `PathBuf` joined at runtime to the crate root or to `RSTEST_FILES_BASE`
+    Relocatable,
+    /// The file's contents as `&'static str` by `include_str!()`
+    Str,
+    /// The file's contents as `&'static [u8]` by `include_bytes!()`
+    Bytes,
+}
+
+impl TryFrom<&Attribute> for FilesMode {
+    type Error = syn::Error;
+
+    fn try_from(attr: &Attribute) -> Result<Self, Self::Error> {
+        let error = || {
+            attr.error(
+                "Use #[mode = path], #[mode = relocatable], #[mode = str] or #[mode = bytes]",
+            )
+        };
+        let value = &attr.meta.require_name_value().map_err(|_| error())?.value;
+        let mode = match value {
+            Expr::Path(p) => p.path.get_ident().map(ToString::to_string),
+            _ => None,
+        };
+        match mode.as_deref() {
+            Some("path") => Ok(Self::Path),
+            Some("relocatable") => Ok(Self::Relocatable),
+            Some("str") => Ok(Self::Str),
+            Some("bytes") => Ok(Self::Bytes),
+            _ => Err(error()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct FilesGlobReferences {
     glob: Vec<LitStrAttr>,
     exclude: Vec<Exclude>,
     ignore_dot_files: bool,
+    mode: FilesMode,
+    siblings: Vec<Sibling>,
+}
+
+/// An argument with `#[sibling("{stem}.expected")]` that gets, for each file matched by the
+/// previous `#[files(...)]` argument, the file with the given name in the same folder
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Sibling {
+    arg: Ident,
+    name: LitStrAttr,
+    mode: FilesMode,
+}
+
+impl Sibling {
+    /// The sibling's name of `file`: `{stem}` and `{name}` are replaced by the file's stem
+    /// and name
+    fn name_of(&self, file: &Path) -> String {
+        let part = |p: Option<&std::ffi::OsStr>| {
+            p.map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        self.name
+            .value()
+            .replace("{stem}", &part(file.file_stem()))
+            .replace("{name}", &part(file.file_name()))
+    }
 }
 
 impl FilesGlobReferences {
@@ -38,7 +105,7 @@ impl FilesGlobReferences {
     }
 }
 
-trait RaiseError: ToTokens {
+pub(super) trait RaiseError: ToTokens {
     fn error(&self, msg: &str) -> syn::Error {
         syn::Error::new_spanned(self, msg)
     }
@@ -58,9 +125,16 @@ impl FilesGlobReferences {
             glob,
             exclude,
             ignore_dot_files,
+            mode: Default::default(),
+            siblings: Default::default(),
         }
     }
 
+    fn with_mode(mut self, mode: FilesMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     fn is_valid(&self, p: &RelativePath) -> bool {
         if self.ignore_dot_files
             && p.components()
@@ -74,13 +148,13 @@ impl FilesGlobReferences {
 
 /// An attribute in the form `#[name("some string")]`
 #[derive(Debug, Clone, PartialEq)]
-struct LitStrAttr {
+pub(crate) struct LitStrAttr {
     attr: Attribute,
-    value: LitStr,
+    pub(super) value: LitStr,
 }
 
 impl LitStrAttr {
-    fn value(&self) -> String {
+    pub(super) fn value(&self) -> String {
         self.value.value()
     }
 }
@@ -187,6 +261,18 @@ impl ValueFilesExtractor {
         )
     }
 
+    fn extract_mode(&mut self, node: &mut FnArg) -> Vec<(Attribute, FilesMode)> {
+        self.extract_argument_attrs(
+            node,
+            |a| attr_is(a, "mode"),
+            |attr, _| FilesMode::try_from(&attr).map(|mode| (attr, mode)),
+        )
+    }
+
+    fn extract_sibling(&mut self, node: &mut FnArg) -> Vec<LitStrAttr> {
+        self.extract_argument_attrs(node, |a| attr_is(a, "sibling"), |attr, _| attr.try_into())
+    }
+
     fn extract_include_dot_files(&mut self, node: &mut FnArg) -> Vec<Attribute> {
         self.extract_argument_attrs(
             node,
@@ -211,33 +297,71 @@ impl VisitMut for ValueFilesExtractor {
         let files = self.extract_files(node);
         let excludes = self.extract_exclude(node);
         let include_dot_files = self.extract_include_dot_files(node);
+        let modes = self.extract_mode(node);
+        let siblings = self.extract_sibling(node);
         if !include_dot_files.is_empty() {
             include_dot_files.iter().skip(1).for_each(|attr| {
                 self.errors
                     .push(attr.error("Cannot use #[include_dot_files] more than once"))
             })
         }
+        modes.iter().skip(1).for_each(|(attr, _)| {
+            self.errors
+                .push(attr.error("Cannot use #[mode = ...] more than once"))
+        });
+        siblings.iter().skip(1).for_each(|attr| {
+            self.errors
+                .push(attr.error("Cannot use #[sibling(...)] more than once"))
+        });
+        let mode = modes.first().map(|(_, mode)| *mode).unwrap_or_default();
+        let sibling = siblings.into_iter().next();
         if !files.is_empty() {
+            if let Some(sibling) = sibling {
+                self.errors
+                    .push(sibling.error(
+                        "Cannot use #[sibling(...)] and #[files(...)] on the same argument",
+                    ));
+            }
             self.files.push((
                 name,
-                FilesGlobReferences::new(files, excludes, include_dot_files.is_empty()),
-            ))
-        } else {
-            excludes.into_iter().for_each(|e| {
-                self.errors.push(
-                    e.attr
-                        .error("You cannot use #[exclude(...)] without #[files(...)]"),
-                )
-            });
-            include_dot_files.into_iter().for_each(|attr| {
+                FilesGlobReferences::new(files, excludes, include_dot_files.is_empty())
+                    .with_mode(mode),
+            ));
+            return;
+        }
+        let is_sibling = sibling.is_some();
+        if let Some(sibling) = sibling {
+            match self.files.last_mut() {
+                Some((_, refs)) => refs.siblings.push(Sibling {
+                    arg: name,
+                    name: sibling,
+                    mode,
+                }),
+                None => self
+                    .errors
+                    .push(sibling.error("#[sibling(...)] should follow a #[files(...)] argument")),
+            }
+        }
+        excludes.into_iter().for_each(|e| {
+            self.errors.push(
+                e.attr
+                    .error("You cannot use #[exclude(...)] without #[files(...)]"),
+            )
+        });
+        include_dot_files.into_iter().for_each(|attr| {
+            self.errors
+                .push(attr.error("You cannot use #[include_dot_files] without #[files(...)]"))
+        });
+        if !is_sibling {
+            modes.into_iter().for_each(|(attr, _)| {
                 self.errors
-                    .push(attr.error("You cannot use #[include_dot_files] without #[files(...)]"))
+                    .push(attr.error("You cannot use #[mode = ...] without #[files(...)]"))
             });
         }
     }
 }
 
-trait BaseDir {
+pub(super) trait BaseDir {
     fn base_dir(&self) -> Result<PathBuf, String> {
         env::var("CARGO_MANIFEST_DIR")
             .map(PathBuf::from)
@@ -247,7 +371,7 @@ trait BaseDir {
     }
 }
 
-struct DefaultBaseDir;
+pub(super) struct DefaultBaseDir;
 
 impl BaseDir for DefaultBaseDir {}
 
@@ -314,38 +438,69 @@ impl<'a> ValueListFromFiles<'a> {
             .into_string()
             .map_err(|p| refs.glob[0].error(&format!("Cannot get a valid string from {p:?}")))?;
 
-        let mut values: Vec<(Expr, String)> = vec![];
-        for (attr, abs_path) in self.all_files_path(resolved_paths)? {
-            let relative_path = abs_path
-                .clone()
-                .into_os_string()
-                .into_string()
+        let relative = |attr: &LitStrAttr, abs_path: &Path| {
+            abs_path
+                .to_str()
                 .map(|inner| RelativePath::new(base_dir.as_str()).relative(inner))
-                .map_err(|e| {
-                    attr.error(&format!("Invalid absolute path {}", e.to_string_lossy()))
-                })?;
+                .ok_or_else(|| attr.error(&format!("Invalid absolute path {}", abs_path.display())))
+        };
+
+        let mut values = vec![];
+        for (attr, abs_path) in self.all_files_path(resolved_paths)? {
+            let relative_path = relative(attr, &abs_path)?;
 
             if !refs.is_valid(&relative_path) {
                 continue;
             }
 
-            let path_str = abs_path.to_string_lossy();
-            values.push((
-                parse_quote! {
-                    <PathBuf as std::str::FromStr>::from_str(#path_str).unwrap()
-                },
-                render_file_description(&relative_path),
-            ));
+            let value = file_value(refs.mode, &abs_path, &relative_path);
+            if refs.siblings.is_empty() {
+                values.push(Value::new(
+                    value,
+                    Some(render_file_description(&relative_path)),
+                ));
+                continue;
+            }
+            let mut siblings = vec![];
+            for sibling in &refs.siblings {
+                let path = abs_path
+                    .parent()
+                    .unwrap_or(&abs_path)
+                    .join(sibling.name_of(&abs_path));
+                let sibling_path = self
+                    .g_resolver
+                    .glob(&glob::Pattern::escape(&path.to_string_lossy()))
+                    .map_err(|msg| sibling.name.error(&msg))?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        sibling.name.error(&format!(
+                            "Missing sibling `{}` of `{}`",
+                            RelativePath::new(base_dir.as_str()).relative(&*path.to_string_lossy()),
+                            relative_path
+                        ))
+                    })?;
+                let sibling_relative_path = relative(&sibling.name, &sibling_path)?;
+                siblings.push((
+                    sibling.arg.clone(),
+                    file_value(sibling.mode, &sibling_path, &sibling_relative_path),
+                ));
+            }
+            // The file and its siblings share the stem, so it names the test
+            values.push(
+                Value::new(
+                    value,
+                    Some(render_file_description(&relative_path.with_extension(""))),
+                )
+                .with_siblings(siblings),
+            );
         }
 
         if values.is_empty() {
             Err(refs.glob[0].error("No file found"))?;
         }
 
-        Ok(values
-            .into_iter()
-            .map(|(e, desc)| Value::new(e, Some(desc)))
-            .collect())
+        Ok(values)
     }
 
     /// Return the tuples of attribute, file path resolved via glob resolver, sorted by path and without duplications.
@@ -371,6 +526,25 @@ impl<'a> ValueListFromFiles<'a> {
     }
 }
 
+/// The expression that gives the file as asked by `mode`
+fn file_value(mode: FilesMode, abs_path: &Path, relative_path: &RelativePath) -> Expr {
+    let path_str = abs_path.to_string_lossy();
+    // The compiler tracks the included files and builds the test again when they change
+    match mode {
+        FilesMode::Path => parse_quote! {
+            <PathBuf as std::str::FromStr>::from_str(#path_str).unwrap()
+        },
+        FilesMode::Relocatable => {
+            let relative_str = relative_path.as_str();
+            parse_quote! {
+                rstest::files::relocatable_path(#relative_str, env!("CARGO_MANIFEST_DIR"))
+            }
+        }
+        FilesMode::Str => parse_quote! { include_str!(#path_str) },
+        FilesMode::Bytes => parse_quote! { include_bytes!(#path_str) },
+    }
+}
+
 fn render_file_description(file: &RelativePath) -> String {
     let mut description = String::new();
     for c in file.components() {
@@ -478,6 +652,77 @@ mod should {
         );
     }
 
+    #[rstest]
+    #[case::default(r#"fn f(#[files("some")] a: PathBuf) {}"#, FilesMode::Path)]
+    #[case::path(
+        r#"fn f(#[files("some")] #[mode = path] a: PathBuf) {}"#,
+        FilesMode::Path
+    )]
+    #[case::relocatable(
+        r#"fn f(#[files("some")] #[mode = relocatable] a: PathBuf) {}"#,
+        FilesMode::Relocatable
+    )]
+    #[case::str(r#"fn f(#[files("some")] #[mode = str] a: &str) {}"#, FilesMode::Str)]
+    #[case::bytes(
+        r#"fn f(#[mode = bytes] #[files("some")] a: &[u8]) {}"#,
+        FilesMode::Bytes
+    )]
+    fn extract_mode(#[case] item_fn: &str, #[case] expected: FilesMode) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert!(attrs_of_first_arg(&item_fn).is_empty());
+        assert_eq!(expected, files[0].1.mode);
+    }
+
+    #[test]
+    fn extract_siblings() {
+        let mut item_fn: ItemFn = r#"
+            fn f(
+                #[files("cases/*.input")] input: PathBuf,
+                #[sibling("{stem}.expected")] #[mode = str] expected: &str,
+                #[sibling("{name}.args")] args: PathBuf,
+                #[files("other/*")] other: PathBuf
+            ) {}
+        "#
+        .ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(2, files.len());
+        let siblings = files[0]
+            .1
+            .siblings
+            .iter()
+            .map(|s| (s.arg.to_string(), s.name.value(), s.mode))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (
+                    "expected".to_owned(),
+                    "{stem}.expected".to_owned(),
+                    FilesMode::Str
+                ),
+                ("args".to_owned(), "{name}.args".to_owned(), FilesMode::Path),
+            ],
+            siblings
+        );
+        assert!(files[1].1.siblings.is_empty());
+        assert_eq!(
+            "fn f(input: PathBuf, expected: &str, args: PathBuf, other: PathBuf) {}"
+                .ast::<ItemFn>(),
+            item_fn
+        );
+    }
+
+    fn attrs_of_first_arg(item_fn: &ItemFn) -> &[Attribute] {
+        match &item_fn.sig.inputs[0] {
+            FnArg::Typed(arg) => &arg.attrs,
+            _ => &[],
+        }
+    }
+
     #[rstest]
     #[case::no_files_arg("fn f(#[files] a: PathBuf) {}", "#[files(...)]")]
     #[case::invalid_files_inner("fn f(#[files(a::b::c)] a: PathBuf) {}", "string literal")]
@@ -509,6 +754,38 @@ mod should {
         r#"fn f(#[files("some")] #[include_dot_files] #[include_dot_files] a: PathBuf) {}"#,
         "more than once"
     )]
+    #[case::invalid_mode(
+        r#"fn f(#[files("some")] #[mode = string] a: &str) {}"#,
+        "#[mode = str]"
+    )]
+    #[case::mode_without_value(
+        r#"fn f(#[files("some")] #[mode(str)] a: &str) {}"#,
+        "#[mode = str]"
+    )]
+    #[case::mode_more_than_once(
+        r#"fn f(#[files("some")] #[mode = str] #[mode = bytes] a: &str) {}"#,
+        "#[mode = ...] more than once"
+    )]
+    #[case::mode_without_files(
+        r#"fn f(#[mode = str] a: &str) {}"#,
+        "#[mode = ...] without #[files(...)]"
+    )]
+    #[case::sibling_without_files(
+        r#"fn f(#[sibling("{stem}.out")] a: PathBuf) {}"#,
+        "#[sibling(...)] should follow a #[files(...)] argument"
+    )]
+    #[case::sibling_on_files(
+        r#"fn f(#[files("some")] #[sibling("{stem}.out")] a: PathBuf) {}"#,
+        "#[sibling(...)] and #[files(...)] on the same argument"
+    )]
+    #[case::sibling_more_than_once(
+        r#"fn f(#[files("some")] a: PathBuf, #[sibling("a")] #[sibling("b")] b: PathBuf) {}"#,
+        "#[sibling(...)] more than once"
+    )]
+    #[case::invalid_sibling_inner(
+        r#"fn f(#[files("some")] a: PathBuf, #[sibling(a::b)] b: PathBuf) {}"#,
+        "string literal"
+    )]
     fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
         let mut item_fn: ItemFn = item_fn.ast();
 
@@ -661,6 +938,165 @@ mod should {
         assert_eq!(vec![v_list], values);
     }
 
+    #[rstest]
+    #[case::str(FilesMode::Str, r#"include_str!("/base/first")"#)]
+    #[case::bytes(FilesMode::Bytes, r#"include_bytes!("/base/first")"#)]
+    fn generate_a_variable_with_the_file_contents(#[case] mode: FilesMode, #[case] expected: &str) {
+        let values = ValueListFromFiles::new(
+            FakeBaseDir::from("/base"),
+            FakeResolver::from(["/base/first"].as_slice()),
+        )
+        .to_value_list(vec![(
+            ident("a"),
+            FilesGlobReferences::new(vec![files_attr("no_mater")], Default::default(), true)
+                .with_mode(mode),
+        )])
+        .unwrap();
+
+        assert_eq!(expected.ast::<Expr>(), values[0].values[0].expr);
+        assert_eq!("first", values[0].values[0].description());
+    }
+
+    /// Resolve just the known patterns
+    struct FakeGlobs(HashMap<String, Vec<PathBuf>>);
+
+    impl From<&[(&str, &[&str])]> for FakeGlobs {
+        fn from(value: &[(&str, &[&str])]) -> Self {
+            Self(
+                value
+                    .iter()
+                    .map(|(pattern, paths)| {
+                        (
+                            pattern.to_string(),
+                            paths.iter().map(PathBuf::from).collect(),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    impl GlobResolver for FakeGlobs {
+        fn glob(&self, pattern: &str) -> Result<Vec<PathBuf>, String> {
+            Ok(self.0.get(pattern).cloned().unwrap_or_default())
+        }
+    }
+
+    fn with_siblings(files: &str, siblings: &[(&str, &str, FilesMode)]) -> FilesGlobReferences {
+        let mut refs = FilesGlobReferences::new(vec![files_attr(files)], Default::default(), true);
+        refs.siblings = siblings
+            .iter()
+            .map(|&(arg, name, mode)| Sibling {
+                arg: ident(arg),
+                name: lit_str_attr("sibling", name),
+                mode,
+            })
+            .collect();
+        refs
+    }
+
+    #[test]
+    fn generate_the_siblings_values() {
+        let values = ValueListFromFiles::new(
+            FakeBaseDir::from("/base"),
+            FakeGlobs::from(
+                [
+                    (
+                        "/base/cases/*.input",
+                        ["/base/cases/a.input", "/base/cases/b.input"].as_slice(),
+                    ),
+                    ("/base/cases/a.expected", &["/base/cases/a.expected"]),
+                    ("/base/cases/b.expected", &["/base/cases/b.expected"]),
+                    ("/base/cases/a.input.args", &["/base/cases/a.input.args"]),
+                    ("/base/cases/b.input.args", &["/base/cases/b.input.args"]),
+                ]
+                .as_slice(),
+            ),
+        )
+        .to_value_list(vec![(
+            ident("input"),
+            with_siblings(
+                "cases/*.input",
+                &[
+                    ("expected", "{stem}.expected", FilesMode::Str),
+                    ("args", "{name}.args", FilesMode::Path),
+                ],
+            ),
+        )])
+        .unwrap();
+
+        let values = &values[0].values;
+        assert_eq!(2, values.len());
+        assert_eq!("cases/a", values[0].description());
+        assert_eq!("cases/b", values[1].description());
+        assert_eq!(
+            vec![
+                (
+                    ident("expected"),
+                    r#"include_str!("/base/cases/b.expected")"#.ast::<Expr>()
+                ),
+                (
+                    ident("args"),
+                    r#"<PathBuf as std::str::FromStr>::from_str("/base/cases/b.input.args").unwrap()"#
+                        .ast::<Expr>()
+                ),
+            ],
+            values[1].siblings
+        );
+    }
+
+    #[test]
+    fn raise_error_if_a_sibling_is_missing() {
+        let err = ValueListFromFiles::new(
+            FakeBaseDir::from("/base"),
+            FakeGlobs::from(
+                [
+                    (
+                        "/base/cases/*.input",
+                        ["/base/cases/a.input", "/base/cases/b.input"].as_slice(),
+                    ),
+                    ("/base/cases/a.expected", &["/base/cases/a.expected"]),
+                ]
+                .as_slice(),
+            ),
+        )
+        .to_value_list(vec![(
+            ident("input"),
+            with_siblings(
+                "cases/*.input",
+                &[("expected", "{stem}.expected", FilesMode::Path)],
+            ),
+        )])
+        .unwrap_err();
+
+        assert_in!(
+            err.to_string(),
+            "Missing sibling `cases/b.expected` of `cases/b.input`"
+        );
+    }
+
+    #[rstest]
+    #[case::in_base_dir("/base/some/first", "some/first")]
+    #[case::outside_base_dir("/shared/first", "../shared/first")]
+    fn generate_a_relocatable_path(#[case] file: &str, #[case] relative: &str) {
+        let values = ValueListFromFiles::new(
+            FakeBaseDir::from("/base"),
+            FakeResolver::from([file].as_slice()),
+        )
+        .to_value_list(vec![(
+            ident("a"),
+            FilesGlobReferences::new(vec![files_attr("no_mater")], Default::default(), true)
+                .with_mode(FilesMode::Relocatable),
+        )])
+        .unwrap();
+
+        assert_eq!(
+            format!(r#"rstest::files::relocatable_path("{relative}", env!("CARGO_MANIFEST_DIR"))"#)
+                .ast::<Expr>(),
+            values[0].values[0].expr
+        );
+    }
+
     #[rstest]
     #[case::file("name.txt", "name.txt")]
     #[case::in_folder("some/folder/name.txt", "some/folder/name.txt")]
@@ -0,0 +1,123 @@
+use quote::{format_ident, ToTokens};
+use syn::{visit_mut::VisitMut, FnArg, Ident, ItemFn, Type};
+
+use crate::{error::ErrorsVec, refident::MaybeType, utils::attr_is};
+
+use super::extract_argument_attrs;
+
+/// Extract the arguments annotated by `#[scope(group)]`.
+pub(crate) fn extract_groups(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = GroupsFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the group scoped ones.
+#[derive(Default)]
+struct GroupsFunctionExtractor {
+    groups: Vec<Ident>,
+    errors: Vec<syn::Error>,
+}
+
+impl GroupsFunctionExtractor {
+    pub(crate) fn take(self) -> Result<Vec<Ident>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.groups)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for GroupsFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        match extract_argument_attrs(
+            node,
+            |a| attr_is(a, "scope"),
+            |arg, name| {
+                let scope = arg.parse_args::<Ident>()?;
+                if scope != format_ident!("group") {
+                    return Err(syn::Error::new(
+                        scope.span(),
+                        format!("Invalid '{scope}' #[scope(...)] arg: just 'group' is supported."),
+                    ));
+                }
+                Ok((arg, name.clone()))
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(scopes) => match scopes.len().cmp(&1) {
+                std::cmp::Ordering::Equal => match node.maybe_type() {
+                    Some(Type::Reference(_)) => self.groups.push(scopes[0].1.clone()),
+                    _ => self.errors.push(syn::Error::new_spanned(
+                        node.maybe_type().unwrap().into_token_stream(),
+                        "Group scoped values are shared: use a reference like `&T` as type."
+                            .to_owned(),
+                    )),
+                },
+                std::cmp::Ordering::Greater => {
+                    self.errors
+                        .extend(scopes.iter().skip(1).map(|(attr, _ident)| {
+                            syn::Error::new_spanned(
+                                attr.into_token_stream(),
+                                "Cannot use #[scope] more than once.".to_owned(),
+                            )
+                        }));
+                }
+                std::cmp::Ordering::Less => {}
+            },
+            Err(e) => {
+                self.errors.push(e);
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[scope(group)] a: &u32) {}", "fn f(a: &u32) {}", &["a"])]
+    #[case::more_than_one(
+        "fn f(#[scope(group)] a: &u32, b: u32, #[scope(group)] c: &'static str) {}",
+        "fn f(a: &u32, b: u32, c: &'static str) {}",
+        &["a", "c"]
+    )]
+    #[case::no_one("fn f(a: &u32) {}", "fn f(a: &u32) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_groups: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let groups = extract_groups(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            groups,
+            expected_groups
+                .iter()
+                .copied()
+                .map(ident)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[scope(group)] #[scope(group)] a: &u32) {}", "more than once")]
+    #[case::invalid_scope("fn f(#[scope(test)] a: &u32) {}", "Invalid 'test'")]
+    #[case::no_scope("fn f(#[scope] a: &u32) {}", "expected attribute arguments")]
+    #[case::no_reference("fn f(#[scope(group)] a: u32) {}", "use a reference")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_groups(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -4,6 +4,8 @@ use syn::{
     Attribute, Expr, Ident, Token,
 };
 
+use super::Fixture;
+
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
@@ -14,6 +16,8 @@ pub(crate) struct TestCase {
     pub(crate) args: Vec<Expr>,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) description: Option<Ident>,
+    /// The fixtures resolved just for this case by a `#[with(...)]` before the `#[case]`.
+    pub(crate) fixtures: Vec<Fixture>,
 }
 
 impl Parse for TestCase {
@@ -35,6 +39,7 @@ impl Parse for TestCase {
                 args,
                 attrs,
                 description,
+                fixtures: Default::default(),
             })
         } else {
             Err(Error::new(case.span(), "expected a test case"))
@@ -0,0 +1,259 @@
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::VisitMut,
+    FnArg, Ident, ItemFn, LitStr, Token,
+};
+
+use crate::{error::ErrorsVec, utils::attr_is};
+
+use super::{
+    extract_argument_attrs,
+    vlist::{Value, ValueList},
+    Fixture,
+};
+
+/// The function attribute that the callback macro of a parametrized fixture adds to the
+/// test: it gives the descriptions of the fixture's variants.
+pub(crate) const VARIANTS_ATTR: &str = "__rstest_variants";
+
+/// A test's argument that takes every variant of a parametrized fixture: it's marked by
+/// a bare `#[values]`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct FixtureVariants {
+    pub(crate) arg: Ident,
+    /// The fixture that gives the variants (`#[from(fixture)]` or the argument's name)
+    pub(crate) fixture: Ident,
+    /// Filled when the fixture's callback macro already gave them
+    pub(crate) descriptions: Option<Vec<String>>,
+}
+
+impl FixtureVariants {
+    /// Move the fixture that resolves the argument (if any) in the variants: the argument
+    /// cannot take any other value.
+    pub(crate) fn take_fixture(&mut self, fixtures: &mut Vec<Fixture>) -> Result<(), syn::Error> {
+        let position = match fixtures.iter().position(|f| f.name == self.arg) {
+            Some(position) => position,
+            None => return Ok(()),
+        };
+        let fixture = fixtures.remove(position);
+        if !fixture.positional.0.is_empty() || !fixture.named.is_empty() {
+            return Err(syn::Error::new(
+                self.arg.span(),
+                "Cannot use #[with] on the variants of a parametrized fixture: \
+                set the arguments in the fixture's #[values] instead.",
+            ));
+        }
+        if let Some(resolve) = fixture.resolve {
+            self.fixture = resolve;
+        }
+        Ok(())
+    }
+
+    /// The values list that calls each fixture's variant.
+    pub(crate) fn value_list(&self) -> Option<ValueList> {
+        let fixture = &self.fixture;
+        self.descriptions.as_ref().map(|descriptions| ValueList {
+            arg: self.arg.clone(),
+            values: descriptions
+                .iter()
+                .enumerate()
+                .map(|(i, description)| {
+                    let variant = quote::format_ident!("variant_{}", i + 1);
+                    Value::new(
+                        parse_quote! { #fixture::#variant() },
+                        Some(description.clone()),
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
+/// The content of `#[__rstest_variants(arg, "description", ...)]`
+struct VariantsInfo {
+    arg: Ident,
+    descriptions: Vec<String>,
+}
+
+impl Parse for VariantsInfo {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let arg = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let descriptions = Punctuated::<LitStr, Token![,]>::parse_terminated(input)?
+            .iter()
+            .map(LitStr::value)
+            .collect();
+        Ok(Self { arg, descriptions })
+    }
+}
+
+/// Extract the arguments marked by a bare `#[values]` and the variants that the fixtures'
+/// callback macros already gave for them.
+pub(crate) fn extract_fixture_variants(
+    item_fn: &mut ItemFn,
+) -> Result<Vec<FixtureVariants>, ErrorsVec> {
+    let mut extractor = VariantsFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    let (infos, attrs): (Vec<_>, Vec<_>) = std::mem::take(&mut item_fn.attrs)
+        .into_iter()
+        .partition(|attr| attr_is(attr, VARIANTS_ATTR));
+    item_fn.attrs = attrs;
+    for info in infos {
+        match info.parse_args::<VariantsInfo>() {
+            Ok(info) => match extractor.variants.iter_mut().find(|v| v.arg == info.arg) {
+                Some(variants) => variants.descriptions = Some(info.descriptions),
+                None => extractor.errors.push(syn::Error::new(
+                    info.arg.span(),
+                    format!("Missed the #[values] marker on argument '{}'", info.arg),
+                )),
+            },
+            Err(err) => extractor.errors.push(err),
+        }
+    }
+    extractor.take()
+}
+
+/// Simple struct used to visit function arguments and extract the ones marked by a bare
+/// `#[values]`.
+#[derive(Default)]
+struct VariantsFunctionExtractor {
+    variants: Vec<FixtureVariants>,
+    errors: Vec<syn::Error>,
+}
+
+impl VariantsFunctionExtractor {
+    fn take(self) -> Result<Vec<FixtureVariants>, ErrorsVec> {
+        if self.errors.is_empty() {
+            Ok(self.variants)
+        } else {
+            Err(self.errors.into())
+        }
+    }
+}
+
+impl VisitMut for VariantsFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        if matches!(node, FnArg::Receiver(_)) {
+            return;
+        }
+        let markers = extract_argument_attrs(
+            node,
+            |a| attr_is(a, "values") && matches!(a.meta, syn::Meta::Path(_)),
+            |attr, name| Ok((attr, name.clone())),
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_default();
+        if let Some((_, name)) = markers.first() {
+            self.variants.push(FixtureVariants {
+                arg: name.clone(),
+                fixture: name.clone(),
+                descriptions: None,
+            });
+        }
+        self.errors.extend(markers.iter().skip(1).map(|(attr, _)| {
+            syn::Error::new_spanned(
+                attr.into_token_stream(),
+                "Cannot use #[values] more than once.",
+            )
+        }));
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::simple("fn f(#[values] store: Store) {}", "fn f(store: Store) {}", &["store"])]
+    #[case::among_others(
+        "fn f(a: u32, #[values] store: Store, #[values(1, 2)] b: u32) {}",
+        "fn f(a: u32, store: Store, #[values(1, 2)] b: u32) {}",
+        &["store"]
+    )]
+    #[case::no_one("fn f(#[values(1, 2)] b: u32) {}", "fn f(#[values(1, 2)] b: u32) {}", &[])]
+    fn extract(#[case] item_fn: &str, #[case] expected: &str, #[case] expected_args: &[&str]) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let variants = extract_fixture_variants(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            variants.iter().map(|v| v.arg.clone()).collect::<Vec<_>>(),
+            expected_args.iter().copied().map(ident).collect::<Vec<_>>()
+        );
+        assert!(variants.iter().all(|v| v.descriptions.is_none()));
+    }
+
+    #[test]
+    fn take_the_descriptions_given_by_the_fixture() {
+        let mut item_fn: ItemFn = r#"
+            #[__rstest_variants(store, "Backend::Mem", "Backend::Disk")]
+            fn f(#[values] store: Store) {}
+            "#
+        .ast();
+
+        let variants = extract_fixture_variants(&mut item_fn).unwrap();
+
+        assert!(item_fn.attrs.is_empty());
+        assert_eq!(
+            Some(vec!["Backend::Mem".to_owned(), "Backend::Disk".to_owned()]),
+            variants[0].descriptions
+        );
+        let values = variants[0].value_list().unwrap().values;
+        assert_eq!(
+            vec![
+                "store :: variant_1 ()".to_owned(),
+                "store :: variant_2 ()".to_owned()
+            ],
+            values
+                .iter()
+                .map(|v| v.expr.to_token_stream().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn call_the_variants_of_the_fixture_given_by_from() {
+        let mut variants = FixtureVariants {
+            arg: ident("store"),
+            fixture: ident("store"),
+            descriptions: Some(vec!["Mem".to_owned()]),
+        };
+        let mut fixtures = vec![Fixture::new(
+            ident("store"),
+            Some(ident("backend_store")),
+            Default::default(),
+        )];
+
+        variants.take_fixture(&mut fixtures).unwrap();
+
+        assert!(fixtures.is_empty());
+        assert_eq!(
+            "backend_store :: variant_1 ()",
+            variants.value_list().unwrap().values[0]
+                .expr
+                .to_token_stream()
+                .to_string()
+        );
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("fn f(#[values] #[values] store: Store) {}", "more than once")]
+    #[case::no_marker(
+        r#"#[__rstest_variants(store, "Mem")] fn f(store: Store) {}"#,
+        "Missed the #[values] marker"
+    )]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_fixture_variants(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -13,11 +13,22 @@ use super::expressions::Expressions;
 pub(crate) struct Value {
     pub(crate) expr: Expr,
     pub(crate) description: Option<String>,
+    /// The other arguments that take their value together with this one
+    pub(crate) siblings: Vec<(Ident, Expr)>,
 }
 
 impl Value {
     pub(crate) fn new(expr: Expr, description: Option<String>) -> Self {
-        Self { expr, description }
+        Self {
+            expr,
+            description,
+            siblings: Default::default(),
+        }
+    }
+
+    pub(crate) fn with_siblings(mut self, siblings: Vec<(Ident, Expr)>) -> Self {
+        self.siblings = siblings;
+        self
     }
 
     pub(crate) fn description(&self) -> String {
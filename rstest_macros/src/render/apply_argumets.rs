@@ -65,12 +65,22 @@ impl ApplyArgumets for ItemFn {
             .iter()
             .filter_map(|a| a.maybe_ident())
             .filter(|&a| arguments.is_future_await(a))
-            .cloned();
+            .cloned()
+            .collect::<Vec<_>>();
         let orig_block_impl = self.block.clone();
-        self.block = parse_quote! {
-            {
-                #(let #awaited_args = #awaited_args.await;)*
-                #orig_block_impl
+        self.block = if arguments.is_concurrent() && awaited_args.len() > 1 {
+            parse_quote! {
+                {
+                    let (#(#awaited_args,)*) = rstest::concurrent::join!(#(#awaited_args),*);
+                    #orig_block_impl
+                }
+            }
+        } else {
+            parse_quote! {
+                {
+                    #(let #awaited_args = #awaited_args.await;)*
+                    #orig_block_impl
+                }
             }
         };
         self.sig.apply_argumets(arguments);
@@ -245,5 +255,41 @@ mod should {
             assert_in!(code, await_argument_code_string("b"));
             assert_not_in!(code, await_argument_code_string("c"));
         }
+
+        #[test]
+        fn concurrently() {
+            let mut item_fn: ItemFn = r#"fn test(a: i32, b:i32, c:i32) {} "#.ast();
+            let mut arguments: ArgumentsInfo = Default::default();
+            arguments.set_concurrent(Some(ident("concurrent")));
+            arguments.set_future(ident("a"), FutureArg::Await);
+            arguments.set_future(ident("b"), FutureArg::Await);
+            arguments.set_future(ident("c"), FutureArg::Define);
+
+            item_fn.apply_argumets(&arguments);
+
+            let code = item_fn.block.display_code();
+
+            assert_in!(
+                code,
+                "let (a , b ,) = rstest :: concurrent :: join ! (a , b)"
+            );
+            assert_not_in!(code, await_argument_code_string("a"));
+            assert_not_in!(code, await_argument_code_string("c"));
+        }
+
+        #[test]
+        fn just_one_concurrently() {
+            let mut item_fn: ItemFn = r#"fn test(a: i32, b:i32) {} "#.ast();
+            let mut arguments: ArgumentsInfo = Default::default();
+            arguments.set_concurrent(Some(ident("concurrent")));
+            arguments.set_future(ident("a"), FutureArg::Await);
+
+            item_fn.apply_argumets(&arguments);
+
+            let code = item_fn.block.display_code();
+
+            assert_in!(code, await_argument_code_string("a"));
+            assert_not_in!(code, "join !");
+        }
     }
 }
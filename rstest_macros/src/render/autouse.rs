@@ -0,0 +1,277 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_quote, spanned::Spanned, visit_mut::VisitMut, Attribute, Ident, Item, ItemFn, ItemMod,
+};
+
+use crate::{parse::autouse::Autouse, utils::attr_ends_with};
+
+/// Resolve the fixtures listed by the test's `#[autouse(...)]` attributes, in order
+/// and just once, but not the ones that the test already takes as arguments. Their
+/// values live till the end of the test. Async tests await the async fixtures, while
+/// sync tests don't compile if they list one of them.
+pub(crate) fn resolve(autouses: &[Attribute], args: &[Ident], is_async: bool) -> TokenStream {
+    let mut resolved: Vec<&Ident> = Vec::new();
+    let mut errors = Vec::new();
+    let autouses = autouses
+        .iter()
+        .filter_map(|attr| {
+            attr.parse_args::<Autouse>()
+                .map_err(|e| errors.push(e.to_compile_error()))
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    let statements = autouses
+        .iter()
+        .flat_map(Autouse::fixtures)
+        .filter(|(_, name)| !args.contains(name))
+        .filter_map(|(path, name)| {
+            if resolved.contains(&name) {
+                return None;
+            }
+            resolved.push(name);
+            let var = format_ident!("__rstest_autouse_{}", name);
+            Some(if is_async {
+                quote_spanned! { path.span() =>
+                    let #var = {
+                        use rstest::autouse::*;
+                        let autoused = Autoused::new(#path::default());
+                        let resolved = (&&autoused).resolve();
+                        resolved
+                    }.await;
+                }
+            } else {
+                quote_spanned! { path.span() =>
+                    let rstest::autouse::Resolved(#var) = {
+                        use rstest::autouse::*;
+                        let autoused = Autoused::new(#path::default());
+                        let resolved = (&&autoused).resolve_sync();
+                        resolved
+                    };
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    quote! { #(#errors)* #(#statements)* }
+}
+
+/// Add `#[autouse(...)]` to the given `#[rstest]` test or to all the `#[rstest]` tests
+/// in the given module, also in its nested modules.
+pub(crate) fn apply(autouse: Autouse, mut item: Item) -> TokenStream {
+    let mut adder = AutouseAdder::new(autouse);
+    let error = match &mut item {
+        Item::Mod(ItemMod {
+            content: Some(_), ..
+        }) => {
+            adder.visit_item_mut(&mut item);
+            None
+        }
+        Item::Fn(item_fn) => match adder.add(item_fn) {
+            true => None,
+            false => Some(syn::Error::new_spanned(
+                item_fn,
+                "#[autouse] should be used on a module or on a #[rstest] test",
+            )),
+        },
+        Item::Mod(m) => Some(syn::Error::new_spanned(
+            m,
+            "#[autouse] cannot see the tests of a module in another file: \
+            use it on the module's content instead",
+        )),
+        other => Some(syn::Error::new_spanned(
+            other,
+            "#[autouse] should be used on a module or on a #[rstest] test",
+        )),
+    };
+    if let Some(error) = error {
+        return error.to_compile_error();
+    }
+    quote! { #item }
+}
+
+struct AutouseAdder {
+    attr: Attribute,
+}
+
+impl AutouseAdder {
+    fn new(autouse: Autouse) -> Self {
+        Self {
+            attr: parse_quote! { #[autouse(#autouse)] },
+        }
+    }
+
+    /// `#[rstest]` should expand the `#[autouse]` attribute and the attributes before
+    /// a `#[case]` apply just to that case: put it last, after the outer modules' ones.
+    fn add(&self, item_fn: &mut ItemFn) -> bool {
+        if !item_fn
+            .attrs
+            .iter()
+            .any(|attr| attr_ends_with(attr, &parse_quote! { rstest }))
+        {
+            return false;
+        }
+        item_fn.attrs.push(self.attr.clone());
+        true
+    }
+}
+
+impl VisitMut for AutouseAdder {
+    fn visit_item_fn_mut(&mut self, item_fn: &mut ItemFn) {
+        self.add(item_fn);
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn autouse(code: &str) -> Autouse {
+        syn::parse_str(code).unwrap()
+    }
+
+    fn autouse_attrs(autouses: &[&str]) -> Vec<Attribute> {
+        autouses
+            .iter()
+            .map(|a| -> Attribute {
+                let a = autouse(a);
+                parse_quote! { #[autouse(#a)] }
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::one(&["logging"], &[], &[("logging", "logging")])]
+    #[case::path(
+        &["crate::setup::logging"],
+        &[],
+        &[("logging", "crate::setup::logging")]
+    )]
+    #[case::in_order(
+        &["logging, locale"],
+        &[],
+        &[("logging", "logging"), ("locale", "locale")]
+    )]
+    #[case::just_once(
+        &["logging", "locale, logging"],
+        &[],
+        &[("logging", "logging"), ("locale", "locale")]
+    )]
+    #[case::not_arguments(&["logging, locale"], &["locale"], &[("logging", "logging")])]
+    fn resolve_autouse_fixtures(
+        #[case] autouses: &[&str],
+        #[case] args: &[&str],
+        #[case] expected: &[(&str, &str)],
+    ) {
+        let attrs = autouse_attrs(autouses);
+        let args = args.iter().copied().map(ident).collect::<Vec<_>>();
+
+        let code = resolve(&attrs, &args, false);
+
+        let expected = expected
+            .iter()
+            .map(|(name, path)| {
+                let var = format_ident!("__rstest_autouse_{}", name);
+                let path: syn::Path = path.ast();
+                quote! {
+                    let rstest::autouse::Resolved(#var) = {
+                        use rstest::autouse::*;
+                        let autoused = Autoused::new(#path::default());
+                        let resolved = (&&autoused).resolve_sync();
+                        resolved
+                    };
+                }
+            })
+            .collect::<TokenStream>();
+        assert_eq!(code.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn await_autouse_fixtures_in_async_tests() {
+        let attrs = autouse_attrs(&["logging"]);
+
+        let code = resolve(&attrs, &[], true).to_string();
+
+        assert_in!(code, "let __rstest_autouse_logging = {");
+        assert_in!(code, "(&& autoused) . resolve () ;");
+        assert_in!(code, "} . await ;");
+    }
+
+    #[test]
+    fn report_malformed_attributes() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote! { #[autouse(logging())] },
+            parse_quote! { #[autouse(locale)] },
+        ];
+
+        let code = resolve(&attrs, &[], false).to_string();
+
+        assert_in!(code, "compile_error");
+        assert_in!(code, "__rstest_autouse_locale");
+    }
+
+    #[test]
+    fn add_autouse_to_all_the_module_tests() {
+        let item: Item = r#"
+            mod tests {
+                #[rstest]
+                fn first() {}
+
+                fn not_a_test() {}
+
+                mod nested {
+                    #[rstest::rstest]
+                    #[case(1)]
+                    #[autouse(nested)]
+                    fn second(#[case] v: u32) {}
+                }
+            }
+        "#
+        .ast();
+        let expected: Item = r#"
+            mod tests {
+                #[rstest]
+                #[autouse(logging)]
+                fn first() {}
+
+                fn not_a_test() {}
+
+                mod nested {
+                    #[rstest::rstest]
+                    #[case(1)]
+                    #[autouse(nested)]
+                    #[autouse(logging)]
+                    fn second(#[case] v: u32) {}
+                }
+            }
+        "#
+        .ast();
+
+        let out: Item = syn::parse2(apply(autouse("logging"), item)).unwrap();
+
+        assert_eq!(expected, out);
+    }
+
+    #[test]
+    fn add_autouse_to_a_test() {
+        let item: Item = "#[rstest] fn first() {}".ast();
+        let expected: Item = "#[rstest] #[autouse(logging)] fn first() {}".ast();
+
+        let out: Item = syn::parse2(apply(autouse("logging"), item)).unwrap();
+
+        assert_eq!(expected, out);
+    }
+
+    #[rstest]
+    #[case::not_a_test("fn first() {}", "on a module or on a #[rstest] test")]
+    #[case::struct_item("struct S;", "on a module or on a #[rstest] test")]
+    #[case::file_module("mod tests;", "in another file")]
+    fn raise_error(#[case] item: &str, #[case] message: &str) {
+        let item: Item = item.ast();
+
+        let out = apply(autouse("logging"), item);
+
+        assert_in!(out.to_string(), message);
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Expr, FnArg, GenericParam, Generics, Ident, ItemFn, Lifetime, ParenthesizedGenericArguments,
+    ReturnType, Token, TraitBoundModifier, Type, TypeBareFn, TypeParamBound, TypeReference,
+    WherePredicate,
+};
+
+use super::{inject, render_exec_call};
+use crate::{
+    refident::{MaybeIdent, MaybeType},
+    resolver::Resolver,
+    utils::{fn_args, generics_clean_up},
+};
+
+/// The fixture's builder: `method` is the `builder()` method of the fixture's struct
+/// and `items` are the builder's struct and its implementation.
+pub(crate) struct Builder {
+    pub(crate) method: TokenStream,
+    pub(crate) items: TokenStream,
+}
+
+pub(crate) fn builder_ident(fixture: &Ident) -> Ident {
+    format_ident!("__rstest_{}_builder", fixture)
+}
+
+/// Render a builder that takes any subset of the fixture's arguments by name and
+/// resolves the other ones as `default()` does, but just when `build()` is called.
+pub(crate) fn render(
+    fixture: &ItemFn,
+    resolver: &impl Resolver,
+    generic_types: &[Ident],
+    output: &ReturnType,
+) -> Builder {
+    let name = &fixture.sig.ident;
+    let builder = builder_ident(name);
+    let visibility = &fixture.vis;
+    let asyncness = &fixture.sig.asyncness;
+    let generics = &fixture.sig.generics;
+    let args = fn_args(fixture)
+        .filter_map(|arg| {
+            Some((
+                arg,
+                arg.maybe_ident()?,
+                arg.maybe_type()?,
+                inject::resolve_argument(arg, resolver, generic_types)?,
+            ))
+        })
+        .collect::<Vec<_>>();
+    let idents = args
+        .iter()
+        .map(|(_, ident, _, _)| (*ident).clone())
+        .collect::<Vec<_>>();
+    let params = (0..args.len())
+        .map(|i| format_ident!("__Arg{}", i))
+        .collect::<Vec<_>>();
+
+    let lifetimes = generics.lifetimes();
+    let defaults_types = args
+        .iter()
+        .map(|(_, _, ty, _)| default_type(ty, generics))
+        .collect::<Vec<_>>();
+    let defaults = args.iter().zip(defaults_types.iter()).map(
+        |((arg, ident, ty, resolved), default_type)| {
+            // Give the type where we can: the value is coerced as in `get()` and a
+            // wrong type is reported on the argument
+            if is_concrete(arg, ty, generics) {
+                quote_spanned! { ident.span() =>
+                    rstest::builder::Lazy(|| -> #default_type { let #ident = #resolved; #ident })
+                }
+            } else {
+                quote! { rstest::builder::Lazy(|| #resolved) }
+            }
+        },
+    );
+    let builder_doc = format!(
+        "Start building the `{name}` fixture: set just the arguments you need by name and call `build()`."
+    );
+    let method = quote! {
+        #[doc = #builder_doc]
+        pub fn builder<#(#lifetimes),*>() -> #builder<#(impl rstest::builder::Arg<Value = #defaults_types>),*> {
+            #builder { #(#idents: #defaults),* }
+        }
+    };
+
+    let setters = args.iter().enumerate().map(|(i, (arg, ident, ty, _))| {
+        let (setter_generics, ty) = setter_type(arg, ty, generics);
+        let mut types = params.iter().map(|p| quote! { #p }).collect::<Vec<_>>();
+        types[i] = quote! { rstest::builder::Given<#ty> };
+        let others = idents.iter().filter(|&other| other != *ident);
+        let doc = format!("Set the `{ident}` argument.");
+        quote! {
+            #[doc = #doc]
+            pub fn #ident<#(#setter_generics),*>(self, #ident: #ty) -> #builder<#(#types),*> {
+                #builder { #ident: rstest::builder::Given(#ident), #(#others: self.#others),* }
+            }
+        }
+    });
+
+    let mut generalize = Generalize::default();
+    let values_bounds = args
+        .iter()
+        .zip(params.iter())
+        .map(|((_, _, ty, _), param)| -> WherePredicate {
+            let mut ty = (*ty).clone();
+            generalize.visit_type_mut(&mut ty);
+            parse_quote! { #param: rstest::builder::Arg<Value = #ty> }
+        })
+        .collect::<Vec<_>>();
+    let build_generics = generics
+        .lifetimes()
+        .map(|lt| quote! { #lt })
+        .chain(generalize.lifetimes.iter().map(|lt| quote! { #lt }))
+        .chain(
+            generics
+                .params
+                .iter()
+                .filter(|p| !matches!(p, GenericParam::Lifetime(_)))
+                .map(|p| quote! { #p }),
+        )
+        .chain(
+            generalize
+                .types
+                .iter()
+                .map(|(ident, bounds)| quote! { #ident: #bounds }),
+        );
+    let where_predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|wc| wc.predicates.iter())
+        .chain(values_bounds.iter());
+    let call_get = render_exec_call(parse_quote! { #name::get }, &idents, asyncness.is_some());
+
+    let struct_doc = format!(
+        "Builder of the `{name}` fixture: the arguments not given are resolved by injection or by their `#[default(...)]` values."
+    );
+    let items = quote! {
+        #[doc = #struct_doc]
+        #[allow(non_camel_case_types)]
+        #visibility struct #builder<#(#params),*> {
+            #(#idents: #params),*
+        }
+
+        impl<#(#params),*> #builder<#(#params),*> {
+            #(#setters)*
+
+            /// Resolve the arguments not given and build the fixture.
+            pub #asyncness fn build<#(#build_generics),*>(self) #output
+            where #(#where_predicates),*
+            {
+                #(let #idents = rstest::builder::Arg::value(self.#idents);)*
+                #call_get
+            }
+        }
+    };
+    Builder { method, items }
+}
+
+/// Resolve the arguments that the builder checks by its defaults: `default()` and the
+/// partials use them instead of resolving the arguments again.
+pub(crate) fn defaults_resolver(fixture: &ItemFn) -> HashMap<String, Expr> {
+    let generics = &fixture.sig.generics;
+    fn_args(fixture)
+        .filter_map(|arg| Some((arg, arg.maybe_ident()?, arg.maybe_type()?)))
+        .filter(|(arg, _, ty)| is_concrete(arg, ty, generics))
+        .map(|(_, ident, _)| {
+            let value: Expr = parse_quote! { rstest::builder::Arg::value(Self::builder().#ident) };
+            (ident.to_string(), value)
+        })
+        .collect()
+}
+
+/// Arguments whose type doesn't depend on the fixture's generics take values of just
+/// this type, so they can be coerced (i.e. `&String` to `&str`). The other ones take
+/// any value and `build()` checks it.
+fn setter_type(arg: &FnArg, ty: &Type, generics: &Generics) -> (Vec<TokenStream>, TokenStream) {
+    if !is_concrete(arg, ty, generics) {
+        return (vec![quote! { __V }], quote! { __V });
+    }
+    let mut generalize = Generalize::default();
+    let mut ty = ty.clone();
+    generalize.visit_type_mut(&mut ty);
+    (
+        generalize
+            .lifetimes
+            .iter()
+            .map(|lt| quote! { #lt })
+            .collect(),
+        quote! { #ty },
+    )
+}
+
+/// The argument's type doesn't contain `impl` types or the fixture's generics.
+fn is_concrete(arg: &FnArg, ty: &Type, generics: &Generics) -> bool {
+    let mut generalize = Generalize::default();
+    generalize.visit_type_mut(&mut ty.clone());
+    generalize.types.is_empty()
+        && generics_clean_up(generics, std::iter::once(arg), &ReturnType::Default)
+            .params
+            .is_empty()
+}
+
+/// The type of the default value of an argument of type `ty`: generic types are
+/// replaced by `impl` their bounds and elided lifetimes by `'static`.
+fn default_type(ty: &Type, generics: &Generics) -> Type {
+    let mut bounds = generics
+        .type_params()
+        .map(|tp| (tp.ident.clone(), tp.bounds.clone()))
+        .collect::<HashMap<_, _>>();
+    for predicate in generics.where_clause.iter().flat_map(|wc| &wc.predicates) {
+        if let WherePredicate::Type(pt) = predicate {
+            if let Some(b) = pt
+                .bounded_ty
+                .maybe_ident()
+                .and_then(|id| bounds.get_mut(id))
+            {
+                b.extend(pt.bounds.iter().cloned());
+            }
+        }
+    }
+    let mut ty = ty.clone();
+    DefaultType { bounds }.visit_type_mut(&mut ty);
+    ty
+}
+
+struct DefaultType {
+    bounds: HashMap<Ident, Punctuated<TypeParamBound, Token![+]>>,
+}
+
+impl VisitMut for DefaultType {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Some(bounds) = ty.maybe_ident().and_then(|id| self.bounds.get(id)) {
+            let mut bounds = bounds
+                .iter()
+                .filter(|b| match b {
+                    TypeParamBound::Trait(t) => !matches!(t.modifier, TraitBoundModifier::Maybe(_)),
+                    _ => true,
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if !bounds.iter().any(|b| matches!(b, TypeParamBound::Trait(_))) {
+                bounds.insert(0, parse_quote! { Sized });
+            }
+            *ty = parse_quote! { impl #(#bounds)+* };
+            return;
+        }
+        visit_mut::visit_type_mut(self, ty)
+    }
+
+    fn visit_type_reference_mut(&mut self, r: &mut TypeReference) {
+        if r.lifetime.is_none() {
+            r.lifetime = Some(parse_quote! { 'static });
+        }
+        visit_mut::visit_type_reference_mut(self, r)
+    }
+
+    fn visit_lifetime_mut(&mut self, lt: &mut Lifetime) {
+        if lt.ident == "_" {
+            *lt = parse_quote! { 'static };
+        }
+    }
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+}
+
+/// Replace `impl` types and elided lifetimes by new generic parameters: `build()`
+/// should accept the types of any given value.
+#[derive(Default)]
+struct Generalize {
+    lifetimes: Vec<Lifetime>,
+    types: Vec<(Ident, Punctuated<TypeParamBound, Token![+]>)>,
+}
+
+impl Generalize {
+    fn lifetime(&mut self) -> Lifetime {
+        let lt = Lifetime::new(
+            &format!("'__rstest_{}", self.lifetimes.len()),
+            proc_macro2::Span::call_site(),
+        );
+        self.lifetimes.push(lt.clone());
+        lt
+    }
+}
+
+impl VisitMut for Generalize {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_mut::visit_type_mut(self, ty);
+        if let Type::ImplTrait(it) = ty {
+            let ident = format_ident!("__Impl{}", self.types.len());
+            self.types.push((ident.clone(), it.bounds.clone()));
+            *ty = parse_quote! { #ident };
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, r: &mut TypeReference) {
+        if r.lifetime.is_none() {
+            r.lifetime = Some(self.lifetime());
+        }
+        visit_mut::visit_type_reference_mut(self, r)
+    }
+
+    fn visit_lifetime_mut(&mut self, lt: &mut Lifetime) {
+        if lt.ident == "_" {
+            *lt = self.lifetime();
+        }
+    }
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+}
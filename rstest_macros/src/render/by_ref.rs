@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use syn::{parse_quote, Expr, ItemFn};
+
+use super::inject;
+use crate::{parse::arguments::ArgumentsInfo, resolver::Resolver, utils::fn_args_idents};
+
+/// Resolve `#[by_ref]` arguments by a reference to the fixture's value: the value is a
+/// temporary that the test wrapper keeps alive till the end of the test, so the next
+/// arguments can borrow it too (i.e. by `#[with(...)]`).
+pub(crate) fn resolver(
+    test: &ItemFn,
+    arguments: &ArgumentsInfo,
+    fixtures: &impl Resolver,
+) -> impl Resolver {
+    fn_args_idents(test)
+        .filter(|ident| arguments.is_by_ref(ident))
+        .map(|ident| {
+            let fixture = inject::resolve_fixture(fixtures, ident);
+            let expr: Expr = parse_quote! { &#fixture };
+            (ident.to_string(), expr)
+        })
+        .collect::<HashMap<_, _>>()
+}
+
+/// The fixture's arguments before the last `#[by_ref]` one: they cannot be resolved
+/// by the fixture because their values belong to the caller.
+pub(crate) fn borrowed_arguments(fixture: &ItemFn, arguments: &ArgumentsInfo) -> usize {
+    fn_args_idents(fixture)
+        .enumerate()
+        .filter(|(_, ident)| arguments.is_by_ref(ident))
+        .map(|(pos, _)| pos + 1)
+        .last()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        parse::{fixture::FixtureInfo, rstest::RsTestInfo, ExtendWithFunctionAttrs},
+        test::{assert_eq, *},
+    };
+
+    #[rstest]
+    #[case::default_fixture("fn f(#[by_ref] a: &A) {}", "a", "&a::default()")]
+    #[case::without_underscore("fn f(#[by_ref] _a: &A) {}", "_a", "&a::default()")]
+    #[case::given_fixture("fn f(#[by_ref] #[with(42)] a: &A) {}", "a", "&a::partial_1(42)")]
+    fn resolve_by_ref_arguments_by_a_reference(
+        #[case] test: &str,
+        #[case] arg: &str,
+        #[case] expected: &str,
+    ) {
+        let mut test: ItemFn = test.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut test).unwrap();
+        let fixtures = crate::resolver::fixtures::get(info.data.fixtures());
+
+        let resolver = resolver(&test, &info.arguments, &fixtures);
+
+        assert_eq!(
+            resolver.resolve(&ident(arg)).unwrap().into_owned(),
+            expected.ast()
+        );
+        assert!(resolver.resolve(&ident("b")).is_none());
+    }
+
+    #[rstest]
+    #[case::none("fn f(a: A, b: B) {}", 0)]
+    #[case::first("fn f(#[by_ref] a: &A, b: B) {}", 1)]
+    #[case::last("fn f(#[by_ref] a: &A, b: B, #[by_ref] c: &C) {}", 3)]
+    fn count_borrowed_arguments(#[case] fixture: &str, #[case] expected: usize) {
+        let mut fixture: ItemFn = fixture.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut fixture).unwrap();
+
+        assert_eq!(expected, borrowed_arguments(&fixture, &info.arguments));
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use syn::{parse_quote_spanned, spanned::Spanned, Expr, ItemFn};
+
+use crate::{
+    parse::arguments::ArgumentsInfo,
+    refident::{MaybeIdent, MaybeType},
+    resolver::Resolver,
+    utils::fn_args,
+};
+
+/// Resolve `#[by_type]` arguments by the `rstest::Provide` implementation of their type.
+pub(crate) fn resolver(item_fn: &ItemFn, arguments: &ArgumentsInfo) -> impl Resolver {
+    fn_args(item_fn)
+        .filter_map(|arg| Some((arg.maybe_ident()?, arg.maybe_type()?)))
+        .filter(|(ident, _)| arguments.is_by_type(ident))
+        .map(|(ident, ty)| {
+            let expr: Expr = parse_quote_spanned! { ty.span() =>
+                <#ty as rstest::Provide>::provide()
+            };
+            (ident.to_string(), expr)
+        })
+        .collect::<HashMap<_, _>>()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        parse::{rstest::RsTestInfo, ExtendWithFunctionAttrs},
+        test::{assert_eq, *},
+    };
+
+    #[rstest]
+    #[case::simple("fn f(#[by_type] a: A) {}", "a", "A")]
+    #[case::any_name("fn f(#[by_type] other: A) {}", "other", "A")]
+    #[case::reference("fn f(#[by_type] a: &'static A) {}", "a", "&'static A")]
+    fn resolve_arguments_by_their_provider(
+        #[case] test: &str,
+        #[case] arg: &str,
+        #[case] ty: &str,
+    ) {
+        let mut test: ItemFn = test.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut test).unwrap();
+
+        let resolver = resolver(&test, &info.arguments);
+
+        assert_eq!(
+            resolver.resolve(&ident(arg)).unwrap().into_owned(),
+            format!("<{ty} as rstest::Provide>::provide()").ast()
+        );
+        assert!(resolver.resolve(&ident("b")).is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use quote::quote;
+use syn::{parse_quote, Expr, Ident};
+
+use crate::{parse::arguments::ArgumentsInfo, resolver::Resolver};
+
+/// What the `#[context]` arguments of a rendered test should know: its case and the
+/// labels of its values.
+#[derive(Clone, Default)]
+pub(crate) struct TestContext {
+    args: Vec<Ident>,
+    case: Option<(usize, Option<Ident>)>,
+    values: Vec<String>,
+}
+
+impl TestContext {
+    pub(crate) fn new(arguments: &ArgumentsInfo) -> Self {
+        Self {
+            args: arguments.contexts().cloned().collect(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_case(&self, index: usize, description: Option<&Ident>) -> Self {
+        Self {
+            case: Some((index, description.cloned())),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn with_value(&self, label: &str) -> Self {
+        let mut context = self.clone();
+        context.values.push(label.to_owned());
+        context
+    }
+
+    /// Resolve the `#[context]` arguments of the test `name`.
+    pub(crate) fn resolver(&self, name: &Ident) -> impl Resolver {
+        let case = match &self.case {
+            Some((index, _)) => quote! { Some(#index) },
+            None => quote! { None },
+        };
+        let description = match &self.case {
+            Some((_, Some(description))) => {
+                let description = description.to_string();
+                quote! { Some(#description) }
+            }
+            _ => quote! { None },
+        };
+        let values = &self.values;
+        let expr: Expr = parse_quote! {
+            rstest::context::Context::new(&__rstest_test, stringify!(#name), #case, #description, &[#(#values),*])
+        };
+        self.args
+            .iter()
+            .map(|arg| (arg.to_string(), expr.clone()))
+            .collect::<HashMap<_, _>>()
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn context() -> TestContext {
+        let mut arguments = ArgumentsInfo::default();
+        arguments.set_context(ident("ctx"));
+        TestContext::new(&arguments)
+    }
+
+    #[rstest]
+    #[case::single(
+        context(),
+        "rstest::context::Context::new(&__rstest_test, stringify!(the_test), None, None, &[])"
+    )]
+    #[case::case(
+        context().with_case(2, None),
+        "rstest::context::Context::new(&__rstest_test, stringify!(the_test), Some(2usize), None, &[])"
+    )]
+    #[case::case_description(
+        context().with_case(2, Some(&ident("two"))),
+        r#"rstest::context::Context::new(&__rstest_test, stringify!(the_test), Some(2usize), Some("two"), &[])"#
+    )]
+    #[case::values(
+        context().with_value("x_1_42").with_value("y_2_3"),
+        r#"rstest::context::Context::new(&__rstest_test, stringify!(the_test), None, None, &["x_1_42", "y_2_3"])"#
+    )]
+    fn resolve_context_arguments(#[case] context: TestContext, #[case] expected: &str) {
+        let resolver = context.resolver(&ident("the_test"));
+
+        assert_eq!(
+            resolver.resolve(&ident("ctx")).unwrap().into_owned(),
+            expected.ast()
+        );
+        assert!(resolver.resolve(&ident("other")).is_none());
+    }
+}
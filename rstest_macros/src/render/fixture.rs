@@ -1,12 +1,16 @@
 use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Ident, ItemFn, ReturnType};
+use syn::{
+    parse_quote, spanned::Spanned, Expr, Ident, ItemFn, ReturnType, Signature, WherePredicate,
+};
 
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
+
+use std::collections::HashMap;
 
 use super::apply_argumets::ApplyArgumets;
-use super::{inject, render_exec_call};
+use super::{builder, by_ref, by_type, inject, render_exec_call, variants};
 use crate::resolver::{self, Resolver};
-use crate::utils::{fn_args, fn_args_idents};
+use crate::utils::{fn_args, fn_args_idents, result_ok_type};
 use crate::{parse::fixture::FixtureInfo, utils::generics_clean_up};
 
 fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
@@ -18,23 +22,202 @@ fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
     }
 }
 
-fn wrap_call_impl_with_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -> TokenStream {
-    match rt {
-        syn::ReturnType::Type(_, t) => parse_quote! {
-            static mut S: Option<#t> = None;
-            static CELL: std::sync::Once = std::sync::Once::new();
-            CELL.call_once(|| unsafe { S = Some(#call_impl) });
-            unsafe { S.as_ref().unwrap() }
-        },
-        _ => parse_quote! {
-            static CELL: std::sync::Once = std::sync::Once::new();
-            CELL.call_once(|| #call_impl );
+fn once_cell(name: &Ident) -> Ident {
+    format_ident!("__rstest_once_{}", name)
+}
+
+// The cell that stores the value of a `#[once]` fixture: shared by `get()` and the
+// methods that resolve its dependencies.
+fn render_once_cell(
+    rt: &ReturnType,
+    name: &Ident,
+    generics_idents: &[Ident],
+    is_async: bool,
+) -> TokenStream {
+    let t = match rt {
+        syn::ReturnType::Type(_, t) => quote! { #t },
+        _ => quote! { () },
+    };
+    let cell_type = match (!generics_idents.is_empty(), is_async) {
+        (true, _) => quote! { rstest::once::OnceMap },
+        (false, true) => quote! { rstest::once::AsyncOnce<#t> },
+        (false, false) => quote! { rstest::once::SyncOnce<#t> },
+    };
+    let cell = once_cell(name);
+    quote! {
+        #[allow(non_upper_case_globals)]
+        static #cell: #cell_type = <#cell_type>::new();
+    }
+}
+
+// When `resolve` is true `call_impl` resolves the fixture's dependencies too, and the
+// cell keeps their teardowns till the process exits.
+fn wrap_call_impl_with_call_once_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+    name: &Ident,
+    generics_idents: &[Ident],
+    teardown: Option<&Expr>,
+    is_async: bool,
+    resolve: bool,
+) -> TokenStream {
+    let t = match rt {
+        syn::ReturnType::Type(_, t) => quote! { #t },
+        _ => quote! { () },
+    };
+    let generic = !generics_idents.is_empty();
+    let mut method = match resolve {
+        true => "get_or_resolve",
+        false => "get_or_init",
+    }
+    .to_owned();
+    if generic && is_async {
+        method.push_str("_async");
+    }
+    if teardown.is_some() {
+        method.push_str("_with_teardown");
+    }
+    let method = Ident::new(&method, Span::call_site());
+    let turbofish = match (generic, teardown.is_some()) {
+        (true, false) => quote! { ::<(#(#generics_idents,)*), #t, _> },
+        (true, true) => quote! { ::<(#(#generics_idents,)*), #t, _, _, _> },
+        (false, _) => quote! {},
+    };
+    let init = match (is_async, resolve) {
+        (true, _) => quote! { async move { #call_impl } },
+        (false, true) => quote! { || { #call_impl } },
+        (false, false) => quote! { || #call_impl },
+    };
+    let args = match teardown {
+        Some(teardown) => quote! {
+            stringify!(#name), #init, |#[allow(unused_variables)] #name| #teardown
         },
+        None => quote! { stringify!(#name), #init },
+    };
+    let await_ = if is_async {
+        quote! { .await }
+    } else {
+        quote! {}
+    };
+    let end = match rt {
+        syn::ReturnType::Type(_, _) => quote! {},
+        _ => quote! { ; },
+    };
+    let cell = once_cell(name);
+    quote! {
+        #cell.#method #turbofish (#args) #await_ #end
+    }
+}
+
+// The value of a generic `#[once]` fixture is stored by its concrete types and
+// shared among all test threads.
+fn add_generic_once_bounds(sig: &mut Signature) {
+    let type_params = sig
+        .generics
+        .type_params()
+        .map(|tp| tp.ident.clone())
+        .collect::<Vec<_>>();
+    if type_params.is_empty() {
+        return;
+    }
+    let output: Option<WherePredicate> = match &sig.output {
+        ReturnType::Type(_, t) => Some(parse_quote! { #t: Send + Sync + 'static }),
+        ReturnType::Default => None,
+    };
+    let where_clause = sig.generics.make_where_clause();
+    where_clause.predicates.extend(
+        type_params
+            .iter()
+            .map(|tp| -> WherePredicate {
+                parse_quote! { #tp: 'static }
+            })
+            .chain(output),
+    );
+}
+
+fn wrap_call_impl_with_teardown(
+    call_impl: TokenStream,
+    name: &Ident,
+    teardown: &Expr,
+) -> TokenStream {
+    quote! {
+        let __rstest_fixture_value = #call_impl;
+        {
+            #[allow(unused_variables)]
+            let #name = &__rstest_fixture_value;
+            rstest::teardown::register(stringify!(#name), #teardown);
+        }
+        __rstest_fixture_value
+    }
+}
+
+fn wrap_call_impl_with_timeout(
+    name: &Ident,
+    args: &[Ident],
+    timeout: &Expr,
+    is_async: bool,
+) -> TokenStream {
+    if is_async && !cfg!(feature = "async-timeout") {
+        // Already reported by the parser: just don't use the missed async implementation
+        quote_spanned! { timeout.span() =>
+            compile_error!("Enable async-timeout feature to use timeout in async fixtures")
+        }
+    } else if is_async {
+        quote! {
+            rstest::timeout::fixture_with_timeout_async(
+                stringify!(#name), move || #name(#(#args),*), #timeout
+            ).await
+        }
+    } else {
+        quote! {
+            rstest::timeout::fixture_with_timeout_sync(
+                stringify!(#name), move || #name(#(#args),*), #timeout
+            )
+        }
+    }
+}
+
+// Resolving a fixture by `default()` or `partial_N()` is timed when the test binary runs
+// with `RSTEST_PROFILE` set.
+fn wrap_resolve_with_profile(body: TokenStream, name: &Ident, is_async: bool) -> TokenStream {
+    if is_async {
+        quote! {
+            rstest::profile::fixture_async(stringify!(#name), async move { #body }).await
+        }
+    } else {
+        quote! {
+            rstest::profile::fixture(stringify!(#name), move || { #body })
+        }
+    }
+}
+
+fn wrap_call_impl_with_fallible(call_impl: TokenStream, name: &Ident) -> TokenStream {
+    quote! {
+        match #call_impl {
+            std::result::Result::Ok(value) => value,
+            std::result::Result::Err(error) => {
+                use rstest::fallible::*;
+                panic!("{}", (&&&Failure::new(error)).message(stringify!(#name)))
+            }
+        }
     }
 }
 
 pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     fixture.apply_argumets(&info.arguments);
+    // A fallible fixture's function returns the `Result` but its struct returns the value
+    let fallible_fn = match result_ok_type(&fixture.sig.output) {
+        Some(ok_output) if info.arguments.is_fallible() => {
+            let fallible_fn = fixture.clone();
+            fixture.sig.output = ok_output;
+            Some(fallible_fn)
+        }
+        _ => None,
+    };
+    if info.arguments.is_once() {
+        add_generic_once_bounds(&mut fixture.sig);
+    }
+    let fixture_fn = fallible_fn.as_ref().unwrap_or(&fixture);
     let name = &fixture.sig.ident;
     let asyncness = &fixture.sig.asyncness.clone();
     let vargs = fn_args_idents(&fixture).cloned().collect::<Vec<_>>();
@@ -53,27 +236,135 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     let mut output = fixture.sig.output.clone();
     let visibility = &fixture.vis;
     let resolver = (
-        resolver::fixtures::get(info.data.fixtures()),
-        resolver::values::get(info.data.values()),
+        by_type::resolver(&fixture, &info.arguments),
+        (
+            resolver::fixtures::get(info.data.fixtures()),
+            resolver::values::get(info.data.values()),
+        ),
     );
     let generics_idents = generics
         .type_params()
         .map(|tp| &tp.ident)
         .cloned()
         .collect::<Vec<_>>();
-    let inject = inject::resolve_aruments(fixture.sig.inputs.iter(), &resolver, &generics_idents);
-
-    let partials =
-        (1..=orig_args.len()).map(|n| render_partial_impl(&fixture, n, &resolver, &info));
+    // The caller should give the `#[by_ref]` arguments: the fixture cannot own them
+    let borrowed = by_ref::borrowed_arguments(&fixture, &info.arguments);
+    // A parametrized fixture can be resolved just by its variants
+    let parametrized = info.data.has_list_values();
+    // Take the builder's defaults where it checks their types: so a wrong type is
+    // reported just once
+    let defaults_resolver = (
+        if borrowed == 0 && !parametrized {
+            builder::defaults_resolver(&fixture)
+        } else {
+            Default::default()
+        },
+        &resolver,
+    );
+    let inject = inject::resolve_aruments(
+        fixture.sig.inputs.iter(),
+        &defaults_resolver,
+        &generics_idents,
+    );
+    let partials = (!parametrized)
+        .then(|| borrowed.max(1)..=orig_args.len())
+        .into_iter()
+        .flatten()
+        .map(|n| render_partial_impl(&fixture, n, &defaults_resolver, &info));
 
     let call_get = render_exec_call(parse_quote! { Self::get }, args, asyncness.is_some());
     let mut call_impl = render_exec_call(parse_quote! { #name }, args, asyncness.is_some());
+    if let Some(timeout) = info.arguments.get_timeout() {
+        call_impl = wrap_call_impl_with_timeout(name, args, timeout, asyncness.is_some());
+    }
+    if fallible_fn.is_some() {
+        call_impl = wrap_call_impl_with_fallible(call_impl, name);
+    }
 
+    let mut default_body = quote! {
+        #inject
+        #call_get
+    };
+    let mut once_cell = None;
     if info.arguments.is_once() {
-        call_impl = wrap_call_impl_with_call_once_impl(call_impl, &output);
+        once_cell = Some(render_once_cell(
+            &output,
+            name,
+            &generics_idents,
+            asyncness.is_some(),
+        ));
+        // `default()` resolves the dependencies just once, and it keeps them
+        // alive as long as the value
+        if !args.is_empty() {
+            default_body = wrap_call_impl_with_call_once_impl(
+                quote! {
+                    #inject
+                    #call_impl
+                },
+                &output,
+                name,
+                &generics_idents,
+                info.arguments.get_teardown(),
+                asyncness.is_some(),
+                true,
+            );
+        }
+        call_impl = wrap_call_impl_with_call_once_impl(
+            call_impl,
+            &output,
+            name,
+            &generics_idents,
+            info.arguments.get_teardown(),
+            asyncness.is_some(),
+            false,
+        );
         output = wrap_return_type_as_static_ref(output);
         default_output = wrap_return_type_as_static_ref(default_output);
+    } else if let Some(teardown) = info.arguments.get_teardown() {
+        call_impl = wrap_call_impl_with_teardown(call_impl, name, teardown);
     }
+    let default = (borrowed == 0 && !parametrized).then(|| {
+        let body = wrap_resolve_with_profile(default_body, name, asyncness.is_some());
+        quote! {
+            pub #asyncness fn default #default_generics () #default_output #default_where_clause {
+                #body
+            }
+        }
+    });
+    let builder = (borrowed == 0 && !parametrized)
+        .then(|| builder::render(&fixture, &resolver, &generics_idents, &output));
+    let variants = fixture_variants(&info);
+    let variants_impls = variants.iter().enumerate().map(|(i, (_, values))| {
+        let inject = inject::resolve_aruments(
+            fixture.sig.inputs.iter(),
+            &(values, &defaults_resolver),
+            &generics_idents,
+        );
+        let body = wrap_resolve_with_profile(
+            quote! {
+                #inject
+                #call_get
+            },
+            name,
+            asyncness.is_some(),
+        );
+        let variant = Ident::new(&format!("variant_{}", i + 1), Span::call_site());
+        quote! {
+            pub #asyncness fn #variant #default_generics () #default_output #default_where_clause {
+                #body
+            }
+        }
+    });
+    let callback = parametrized.then(|| {
+        let descriptions = variants.iter().map(|(d, _)| d.clone()).collect::<Vec<_>>();
+        variants::callback_macro(&fixture, &descriptions)
+    });
+    let builder_method = builder.as_ref().map(|b| &b.method);
+    let builder_items = builder.as_ref().map(|b| &b.items);
+    let provide = info
+        .attributes
+        .provides()
+        .map(|provides| render_provide(name, &output, provides));
 
     quote! {
         #[allow(non_camel_case_types)]
@@ -86,16 +377,64 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
                 #call_impl
             }
 
-            pub #asyncness fn default #default_generics () #default_output #default_where_clause {
-                #inject
-                #call_get
-            }
+            #default
 
             #(#partials)*
+
+            #(#variants_impls)*
+
+            #builder_method
         }
 
         #[allow(dead_code)]
-        #fixture
+        #fixture_fn
+
+        #builder_items
+
+        #provide
+
+        #callback
+
+        #once_cell
+    }
+}
+
+/// The variants of a parametrized fixture, one for each combination of its values lists:
+/// their descriptions and the values of the parametrized arguments.
+fn fixture_variants(info: &FixtureInfo) -> Vec<(String, HashMap<String, Expr>)> {
+    info.data
+        .list_values()
+        .fold(vec![(Vec::new(), HashMap::new())], |variants, list| {
+            variants
+                .into_iter()
+                .flat_map(|(descriptions, values)| {
+                    list.values.iter().map(move |value| {
+                        let mut descriptions = descriptions.clone();
+                        descriptions.push(value.description());
+                        let mut values = values.clone();
+                        values.insert(list.arg.to_string(), value.expr.clone());
+                        (descriptions, values)
+                    })
+                })
+                .collect()
+        })
+        .into_iter()
+        .filter(|(descriptions, _)| !descriptions.is_empty())
+        .map(|(descriptions, values)| (descriptions.join(", "), values))
+        .collect()
+}
+
+fn render_provide(name: &Ident, output: &ReturnType, provides: &Ident) -> TokenStream {
+    let t = match output {
+        ReturnType::Type(_, t) => quote! { #t },
+        ReturnType::Default => quote! { () },
+    };
+    quote_spanned! { provides.span() =>
+        impl rstest::Provide for #t {
+            fn provide() -> Self {
+                #name::default()
+            }
+        }
     }
 }
 
@@ -136,11 +475,19 @@ fn render_partial_impl(
         asyncness.is_some(),
     );
 
+    let body = wrap_resolve_with_profile(
+        quote! {
+            #inject
+            #call_get
+        },
+        &fixture.sig.ident,
+        asyncness.is_some(),
+    );
+
     quote! {
         #[allow(unused_mut)]
         pub #asyncness fn #name #generics (#(#sign_args),*) #output #where_clause {
-            #inject
-            #call_get
+            #body
         }
     }
 }
@@ -150,12 +497,12 @@ mod should {
     use rstest_test::{assert_in, assert_not_in};
     use syn::{
         parse::{Parse, ParseStream},
-        parse2, parse_str, ItemFn, ItemImpl, ItemStruct, Result,
+        parse2, parse_str, ItemFn, ItemImpl, ItemStatic, ItemStruct, Result, Stmt,
     };
 
     use crate::parse::{
         arguments::{ArgumentsInfo, FutureArg},
-        Attribute, Attributes,
+        Attribute, Attributes, ExtendWithFunctionAttrs,
     };
 
     use super::*;
@@ -167,6 +514,9 @@ mod should {
         orig: ItemFn,
         fixture: ItemStruct,
         core_impl: ItemImpl,
+        builder: ItemStruct,
+        builder_impl: ItemImpl,
+        once_cell: Option<ItemStatic>,
     }
 
     impl Parse for FixtureOutput {
@@ -175,6 +525,12 @@ mod should {
                 fixture: input.parse()?,
                 core_impl: input.parse()?,
                 orig: input.parse()?,
+                builder: input.parse()?,
+                builder_impl: input.parse()?,
+                once_cell: match input.is_empty() {
+                    true => None,
+                    false => Some(input.parse()?),
+                },
             })
         }
     }
@@ -247,6 +603,406 @@ mod should {
         assert_eq!(signature.output, "-> &'static (u32, B, String, &str)".ast())
     }
 
+    #[test]
+    fn share_async_once_value_through_async_once_cell() {
+        let item_fn: ItemFn = r#"async fn test(a: u32) -> u32 { a }"#.ast();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let code = get.block.display_code();
+
+        assert_eq!(get.sig.output, "-> &'static u32".ast());
+        assert_in!(
+            out.once_cell.unwrap().display_code(),
+            "rstest :: once :: AsyncOnce < u32 >"
+        );
+        assert_in!(
+            code,
+            "__rstest_once_test . get_or_init (stringify ! (test) , async move { test (a) . await }) . await"
+        );
+    }
+
+    #[test]
+    fn store_generic_once_value_by_generic_types() {
+        let item_fn: ItemFn =
+            r#"fn test<A: Default, B>(b: B) -> (A, B) { (A::default(), b) }"#.ast();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let code = get.block.display_code();
+        let where_clause = get.sig.generics.where_clause.unwrap().display_code();
+
+        assert_eq!(get.sig.output, "-> &'static (A, B)".ast());
+        assert_in!(
+            out.once_cell.unwrap().display_code(),
+            "rstest :: once :: OnceMap"
+        );
+        assert_in!(
+            code,
+            "__rstest_once_test . get_or_init :: < (A , B ,) , (A , B) , _ >"
+        );
+        assert_in!(where_clause, "A : 'static");
+        assert_in!(where_clause, "B : 'static");
+        assert_in!(where_clause, "(A , B) : Send + Sync + 'static");
+    }
+
+    #[test]
+    fn store_once_value_in_a_safe_cell() {
+        let item_fn: ItemFn = r#"fn test(a: u32) -> u32 { a }"#.ast();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let code = select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(
+            out.once_cell.unwrap().display_code(),
+            "static __rstest_once_test : rstest :: once :: SyncOnce < u32 >"
+        );
+        assert_in!(
+            code,
+            "__rstest_once_test . get_or_init (stringify ! (test) , | | test (a))"
+        );
+        assert_not_in!(code, "unsafe");
+    }
+
+    #[rstest]
+    #[case::sync_fixture(
+        "fn test(a: u32) -> u32 { a }",
+        "__rstest_once_test . get_or_resolve (stringify ! (test) , | | { let a = rstest :: builder :: Arg :: value (Self :: builder () . a) ; test (a) })"
+    )]
+    #[case::async_fixture(
+        "async fn test(a: u32) -> u32 { a }",
+        "__rstest_once_test . get_or_resolve (stringify ! (test) , async move { let a = rstest :: builder :: Arg :: value (Self :: builder () . a) ; test (a) . await }) . await"
+    )]
+    fn resolve_once_dependencies_just_when_initialize_the_value(
+        #[case] item_fn: &str,
+        #[case] resolve: &str,
+    ) {
+        let item_fn: ItemFn = item_fn.ast();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let code = select_method(out.core_impl, "default")
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(code, resolve);
+        assert_not_in!(code, "Self :: get");
+    }
+
+    #[test]
+    fn not_resolve_by_ref_arguments() {
+        let item_fn: ItemFn =
+            r#"fn tx<'a>(a: u32, conn: &'a Conn, b: u32) -> Tx<'a> { conn.tx(a, b) }"#.ast();
+        let mut info = FixtureInfo::default();
+        info.arguments.set_by_ref(ident("conn"));
+
+        let tokens = render(item_fn, info);
+        let core_impl: ItemImpl = syn::parse2::<syn::File>(tokens)
+            .unwrap()
+            .items
+            .into_iter()
+            .find_map(|item| match item {
+                syn::Item::Impl(i) => Some(i),
+                _ => None,
+            })
+            .unwrap();
+
+        assert!(select_method(core_impl.clone(), "get").is_some());
+        assert!(select_method(core_impl.clone(), "default").is_none());
+        assert!(select_method(core_impl.clone(), "builder").is_none());
+        assert!(select_method(core_impl.clone(), "partial_1").is_none());
+        assert!(select_method(core_impl.clone(), "partial_2").is_some());
+        assert!(select_method(core_impl, "partial_3").is_some());
+    }
+
+    #[test]
+    fn resolve_parametrized_fixture_just_by_its_variants() {
+        let mut item_fn: ItemFn =
+            r#"fn store(#[values(1, 2)] a: u32, #[values(3, 4)] b: u32, c: u32) -> u32 { a }"#
+                .ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = render(item_fn, info);
+        let file = syn::parse2::<syn::File>(tokens).unwrap();
+        let core_impl: ItemImpl = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Impl(i) => Some(i.clone()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert!(select_method(core_impl.clone(), "default").is_none());
+        assert!(select_method(core_impl.clone(), "builder").is_none());
+        assert!(select_method(core_impl.clone(), "partial_1").is_none());
+        assert!(select_method(core_impl.clone(), "variant_5").is_none());
+        let code = select_method(core_impl, "variant_3")
+            .unwrap()
+            .block
+            .display_code();
+        assert_in!(code, "let a = 2 ;");
+        assert_in!(code, "let b = 3 ;");
+        assert_in!(code, "let c = c :: default () ;");
+        assert!(file
+            .items
+            .iter()
+            .any(|item| matches!(item, syn::Item::Macro(m) if m.mac.path.is_ident("macro_rules"))));
+    }
+
+    #[rstest]
+    #[case::sync_fixture("fn test(a: u32) -> u32 { a }", "get_or_init_with_teardown")]
+    #[case::async_fixture("async fn test(a: u32) -> u32 { a }", "get_or_init_with_teardown")]
+    #[case::generic_fixture(
+        "fn test<T: Default>() -> T { T::default() }",
+        "get_or_init_with_teardown :: < (T ,) , T , _ , _ , _ >"
+    )]
+    #[case::async_generic_fixture(
+        "async fn test<T: Default>() -> T { T::default() }",
+        "get_or_init_async_with_teardown :: < (T ,) , T , _ , _ , _ >"
+    )]
+    fn pass_teardown_to_once_cell(#[case] item_fn: &str, #[case] method: &str) {
+        let item_fn: ItemFn = item_fn.ast();
+        let mut info = FixtureInfo::default().with_once();
+        info.arguments.set_teardown(Some(expr("|| release()")));
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let code = select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(
+            code,
+            format!("__rstest_once_test . {method} (stringify ! (test) ,")
+        );
+        assert_in!(code, "| # [allow (unused_variables)] test | | | release ()");
+        assert_not_in!(code, "rstest :: teardown :: register");
+    }
+
+    #[test]
+    fn register_teardown_in_get_method() {
+        let item_fn: ItemFn = r#"fn test(a: u32) -> u32 { a }"#.ast();
+        let mut info = FixtureInfo::default();
+        info.arguments.set_teardown(Some(expr("|| release()")));
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let code = select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code();
+        let register: Stmt = parse_quote! {
+            rstest::teardown::register(stringify!(test), || release());
+        };
+
+        assert_in!(code, register.display_code());
+    }
+
+    fn get_with_timeout(item_fn: &str) -> String {
+        let item_fn: ItemFn = item_fn.ast();
+        let mut info = FixtureInfo::default();
+        info.arguments.set_timeout(Some(expr("d")));
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        select_method(out.core_impl, "get")
+            .unwrap()
+            .block
+            .display_code()
+    }
+
+    #[test]
+    fn resolve_with_timeout() {
+        let code = get_with_timeout("fn test(a: u32) -> u32 { a }");
+
+        assert_in!(
+            code,
+            "rstest :: timeout :: fixture_with_timeout_sync (stringify ! (test) , move | | test (a) , d)"
+        );
+    }
+
+    #[cfg(feature = "async-timeout")]
+    #[test]
+    fn resolve_async_fixture_with_timeout() {
+        let code = get_with_timeout("async fn test(a: u32) -> u32 { a }");
+
+        assert_in!(
+            code,
+            "rstest :: timeout :: fixture_with_timeout_async (stringify ! (test) , move | | test (a) , d) . await"
+        );
+    }
+
+    #[cfg(not(feature = "async-timeout"))]
+    #[test]
+    fn not_resolve_async_fixture_with_timeout_without_async_timeout_feature() {
+        let code = get_with_timeout("async fn test(a: u32) -> u32 { a }");
+
+        assert_in!(code, "compile_error !");
+        assert_not_in!(code, "fixture_with_timeout_async");
+    }
+
+    #[rstest]
+    #[case::sync_default(
+        "fn test(a: u32) -> u32 { a }",
+        "default",
+        "rstest :: profile :: fixture (stringify ! (test) , move | |"
+    )]
+    #[case::sync_partial(
+        "fn test(a: u32) -> u32 { a }",
+        "partial_1",
+        "rstest :: profile :: fixture (stringify ! (test) , move | |"
+    )]
+    #[case::async_default(
+        "async fn test(a: u32) -> u32 { a }",
+        "default",
+        "rstest :: profile :: fixture_async (stringify ! (test) , async move"
+    )]
+    fn profile_the_resolution(#[case] item_fn: &str, #[case] method: &str, #[case] expected: &str) {
+        let item_fn: ItemFn = item_fn.ast();
+
+        let out: FixtureOutput = parse2(render(item_fn, Default::default())).unwrap();
+
+        let code = select_method(out.core_impl, method)
+            .unwrap()
+            .block
+            .display_code();
+
+        assert_in!(code, expected);
+    }
+
+    #[test]
+    fn unwrap_the_result_of_a_fallible_fixture() {
+        let item_fn: ItemFn = r#"fn db(url: &str) -> Result<Db, Error> { }"#.ast();
+        let mut info = FixtureInfo::default();
+        info.arguments.set_fallible(Some(ident("fallible")));
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let get = select_method(out.core_impl.clone(), "get").unwrap();
+        let default = select_method(out.core_impl, "default").unwrap();
+        let code = get.block.display_code();
+        assert_eq!(get.sig.output, "-> Db".ast());
+        assert_eq!(default.sig.output, "-> Db".ast());
+        assert_eq!(out.orig.sig.output, "-> Result<Db, Error>".ast());
+        assert_in!(code, "match db (url)");
+        assert_in!(code, "Failure :: new (error)) . message (stringify ! (db))");
+    }
+
+    #[test]
+    fn implement_a_builder_that_resolves_the_arguments_not_given() {
+        let item_fn: ItemFn =
+            r#"pub fn user(name: impl AsRef<str>, age: u8, country: &str) -> User { }"#.ast();
+        let info = FixtureInfo {
+            data: vec![arg_value("age", "22").into()].into(),
+            ..Default::default()
+        };
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let builder = select_method(out.core_impl, "builder").unwrap();
+        assert_eq!(
+            builder.sig.output,
+            "-> __rstest_user_builder<
+                impl rstest::builder::Arg<Value = impl AsRef<str>>,
+                impl rstest::builder::Arg<Value = u8>,
+                impl rstest::builder::Arg<Value = &'static str>
+            >"
+            .ast()
+        );
+        let code = builder.block.display_code();
+        assert_in!(
+            code,
+            "name : rstest :: builder :: Lazy (| | name :: default ())"
+        );
+        assert_in!(
+            code,
+            "age : rstest :: builder :: Lazy (| | -> u8 { let age = 22 ; age })"
+        );
+        assert_eq!(out.builder.vis, "pub".ast());
+        assert_eq!(out.builder.ident, "__rstest_user_builder");
+    }
+
+    #[test]
+    fn resolve_concrete_arguments_by_the_builder_defaults() {
+        let item_fn: ItemFn =
+            r#"pub fn user(name: impl AsRef<str>, age: u8) -> User { }"#.ast();
+
+        let out: FixtureOutput = parse2(render(item_fn, Default::default())).unwrap();
+
+        for method in ["default", "partial_1"] {
+            let code = select_method(out.core_impl.clone(), method)
+                .unwrap()
+                .block
+                .display_code();
+            assert_in!(
+                code,
+                "let age = rstest :: builder :: Arg :: value (Self :: builder () . age) ;"
+            );
+        }
+        let default = select_method(out.core_impl, "default")
+            .unwrap()
+            .block
+            .display_code();
+        assert_in!(default, "let name = name :: default () ;");
+    }
+
+    #[rstest]
+    #[case::concrete_type("age", "fn age (self , age : u8)")]
+    #[case::elided_lifetime(
+        "country",
+        "fn country < '__rstest_0 > (self , country : & '__rstest_0 str)"
+    )]
+    #[case::impl_type("name", "fn name < __V > (self , name : __V)")]
+    #[case::generic_type("value", "fn value < __V > (self , value : __V)")]
+    fn implement_a_builder_setter_for_each_argument(#[case] arg: &str, #[case] expected: &str) {
+        let item_fn: ItemFn =
+            r#"fn user<T>(name: impl AsRef<str>, age: u8, country: &str, value: T) -> User { }"#
+                .ast();
+
+        let out: FixtureOutput = parse2(render(item_fn, Default::default())).unwrap();
+
+        let setter = select_method(out.builder_impl, arg).unwrap();
+        assert_in!(setter.sig.display_code(), expected);
+    }
+
+    #[test]
+    fn implement_a_builder_that_builds_the_fixture_by_get() {
+        let item_fn: ItemFn =
+            r#"async fn user<T: Default>(name: impl AsRef<str>, country: &str, value: T) -> User<T> where T: Clone { }"#
+                .ast();
+
+        let out: FixtureOutput = parse2(render(item_fn, Default::default())).unwrap();
+
+        let build = select_method(out.builder_impl, "build").unwrap();
+        let expected: Signature = parse_quote! {
+            async fn build<'__rstest_0, T: Default, __Impl0: AsRef<str>>(self) -> User<T>
+            where
+                T: Clone,
+                __Arg0: rstest::builder::Arg<Value = __Impl0>,
+                __Arg1: rstest::builder::Arg<Value = &'__rstest_0 str>,
+                __Arg2: rstest::builder::Arg<Value = T>
+        };
+        assert_eq!(build.sig, expected);
+        assert_in!(
+            build.block.display_code(),
+            "user :: get (name , country , value) . await"
+        );
+    }
+
     #[template]
     #[rstest(
         method => ["default", "get", "partial_1", "partial_2", "partial_3"])
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Expr, FnArg, Ident, ItemFn, PatType, Type};
+
+use super::inject;
+use crate::{parse::arguments::ArgumentsInfo, refident::MaybeIdent, resolver::Resolver};
+
+fn static_ident(ident: &Ident) -> Ident {
+    format_ident!("__rstest_group_{}", ident)
+}
+
+fn group_args<'a>(
+    test: &'a ItemFn,
+    arguments: &'a ArgumentsInfo,
+) -> impl Iterator<Item = (&'a Ident, &'a Type)> + 'a {
+    test.sig.inputs.iter().filter_map(move |arg| match arg {
+        FnArg::Typed(PatType { ty, .. }) => match (arg.maybe_ident(), ty.as_ref()) {
+            (Some(ident), Type::Reference(r)) if arguments.is_group(ident) => {
+                Some((ident, r.elem.as_ref()))
+            }
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Render the statics that hold the values of group scoped arguments shared among
+/// `size` tests.
+pub(crate) fn statics(test: &ItemFn, arguments: &ArgumentsInfo, size: usize) -> TokenStream {
+    let statics = group_args(test, arguments).map(|(ident, ty)| {
+        let name = static_ident(ident);
+        quote! {
+            #[allow(non_upper_case_globals)]
+            static #name: rstest::group::Group<#ty> = rstest::group::Group::new(#size);
+        }
+    });
+    quote! { #(#statics)* }
+}
+
+/// Resolve group scoped arguments by the value shared in their group's static: the
+/// fixture is resolved by `fixtures` just once for the whole group.
+pub(crate) fn resolver(
+    test: &ItemFn,
+    arguments: &ArgumentsInfo,
+    fixtures: &impl Resolver,
+) -> impl Resolver {
+    group_args(test, arguments)
+        .map(|(ident, _)| {
+            let name = static_ident(ident);
+            let fixture = inject::resolve_fixture(fixtures, ident);
+            let expr: Expr = parse_quote! {
+                &*#name.get(stringify!(#ident), || #fixture)
+            };
+            (ident.to_string(), expr)
+        })
+        .collect::<HashMap<_, _>>()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        parse::{rstest::RsTestInfo, ExtendWithFunctionAttrs},
+        test::{assert_eq, *},
+    };
+
+    fn parse(test: &str) -> (ItemFn, ArgumentsInfo) {
+        let mut test: ItemFn = test.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut test).unwrap();
+        (test, info.arguments)
+    }
+
+    #[test]
+    fn render_a_static_for_each_group_argument() {
+        let (test, arguments) =
+            parse("fn f(#[scope(group)] a: &A, b: &B, #[scope(group)] c: &C) {}");
+
+        let statics = statics(&test, &arguments, 3);
+
+        let expected = quote! {
+            #[allow(non_upper_case_globals)]
+            static __rstest_group_a: rstest::group::Group<A> = rstest::group::Group::new(3usize);
+            #[allow(non_upper_case_globals)]
+            static __rstest_group_c: rstest::group::Group<C> = rstest::group::Group::new(3usize);
+        };
+        assert_eq!(statics.to_string(), expected.to_string());
+    }
+
+    #[rstest]
+    #[case::default_fixture("fn f(#[scope(group)] a: &A) {}", "a", "a::default()")]
+    #[case::without_underscore("fn f(#[scope(group)] _a: &A) {}", "_a", "a::default()")]
+    fn resolve_group_arguments_by_their_static(
+        #[case] test: &str,
+        #[case] arg: &str,
+        #[case] fixture: &str,
+    ) {
+        let (test, arguments) = parse(test);
+        let fixtures = HashMap::<String, Expr>::new();
+
+        let resolver = resolver(&test, &arguments, &fixtures);
+
+        assert_eq!(
+            resolver.resolve(&ident(arg)).unwrap().into_owned(),
+            format!("&*__rstest_group_{arg}.get(stringify!({arg}), || {fixture})").ast()
+        );
+    }
+
+    #[test]
+    fn not_resolve_other_arguments() {
+        let (test, arguments) = parse("fn f(#[scope(group)] a: &A, b: &B) {}");
+        let fixtures = HashMap::<String, Expr>::new();
+
+        let resolver = resolver(&test, &arguments, &fixtures);
+
+        assert!(resolver.resolve(&ident("b")).is_none());
+    }
+}
@@ -48,31 +48,22 @@ where
         let unused_mut: Option<syn::Attribute> = mutability
             .as_ref()
             .map(|_| parse_quote! {#[allow(unused_mut)]});
-        let arg_type = arg.maybe_type()?;
-        let fixture_name = self.fixture_name(ident);
-
-        let mut fixture = self
-            .resolver
-            .resolve(ident)
-            .or_else(|| self.resolver.resolve(&fixture_name))
-            .unwrap_or_else(|| default_fixture_resolve(&fixture_name));
-
-        if fixture.is_literal() && self.type_can_be_get_from_literal_str(arg_type) {
-            fixture = Cow::Owned((self.magic_conversion)(fixture, arg_type));
-        }
+        let fixture = self.resolve_expr(arg)?;
         Some(parse_quote! {
             #unused_mut
             let #mutability #ident = #fixture;
         })
     }
 
-    fn fixture_name<'a>(&self, ident: &'a Ident) -> Cow<'a, Ident> {
-        let id_str = ident.to_string();
-        if id_str.starts_with('_') && !id_str.starts_with("__") {
-            Cow::Owned(Ident::new(&id_str[1..], ident.span()))
-        } else {
-            Cow::Borrowed(ident)
+    fn resolve_expr(&self, arg: &FnArg) -> Option<Cow<'resolver, Expr>> {
+        let ident = arg.maybe_ident()?;
+        let arg_type = arg.maybe_type()?;
+        let mut fixture = resolve_fixture(self.resolver, ident);
+
+        if fixture.is_literal() && self.type_can_be_get_from_literal_str(arg_type) {
+            fixture = Cow::Owned((self.magic_conversion)(fixture, arg_type));
         }
+        Some(fixture)
     }
 
     fn type_can_be_get_from_literal_str(&self, t: &Type) -> bool {
@@ -96,7 +87,35 @@ where
     }
 }
 
-fn default_fixture_resolve(ident: &Ident) -> Cow<Expr> {
+/// Resolve the value of the single argument `arg` as `resolve_aruments()` does.
+pub(crate) fn resolve_argument<'a>(
+    arg: &FnArg,
+    resolver: &'a impl Resolver,
+    generic_types: &[Ident],
+) -> Option<Cow<'a, Expr>> {
+    ArgumentResolver::new(resolver, generic_types).resolve_expr(arg)
+}
+
+/// Resolve the argument `ident` by `resolver`, or by the fixture with the same name
+/// (without the leading underscore, if any).
+pub(crate) fn resolve_fixture<'a>(resolver: &'a impl Resolver, ident: &Ident) -> Cow<'a, Expr> {
+    let fixture_name = fixture_name(ident);
+    resolver
+        .resolve(ident)
+        .or_else(|| resolver.resolve(&fixture_name))
+        .unwrap_or_else(|| default_fixture_resolve(&fixture_name))
+}
+
+fn fixture_name(ident: &Ident) -> Cow<'_, Ident> {
+    let id_str = ident.to_string();
+    if id_str.starts_with('_') && !id_str.starts_with("__") {
+        Cow::Owned(Ident::new(&id_str[1..], ident.span()))
+    } else {
+        Cow::Borrowed(ident)
+    }
+}
+
+fn default_fixture_resolve<'a>(ident: &Ident) -> Cow<'a, Expr> {
     Cow::Owned(parse_quote! { #ident::default() })
 }
 
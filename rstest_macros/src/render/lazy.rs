@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use quote::{format_ident, quote};
+use syn::{parse_quote, Expr, ItemFn};
+
+use super::inject;
+use crate::{parse::arguments::ArgumentsInfo, resolver::Resolver, utils::fn_args_idents};
+
+/// Resolve `#[lazy]` arguments by a closure that resolves the fixture by `fixtures` the
+/// first time the test uses its value. The fixture's arguments (i.e. the `#[with(...)]`
+/// ones) are evaluated before the closure, like the other arguments' fixtures, so the
+/// closure moves their values and not the test's variables that they use.
+pub(crate) fn resolver(
+    test: &ItemFn,
+    arguments: &ArgumentsInfo,
+    fixtures: &impl Resolver,
+) -> impl Resolver {
+    fn_args_idents(test)
+        .filter(|ident| arguments.is_lazy(ident))
+        .map(|ident| {
+            let mut fixture = inject::resolve_fixture(fixtures, ident).into_owned();
+            let arguments = match &mut fixture {
+                Expr::Call(call) => call
+                    .args
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, arg)| {
+                        let name = format_ident!("__rstest_lazy_arg_{}", i);
+                        let value = std::mem::replace(arg, parse_quote! { #name });
+                        quote! { let #name = #value; }
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            let expr: Expr = parse_quote! {
+                {
+                    #(#arguments)*
+                    rstest::lazy::lazy(move || #fixture)
+                }
+            };
+            (ident.to_string(), expr)
+        })
+        .collect::<HashMap<_, _>>()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        parse::{rstest::RsTestInfo, ExtendWithFunctionAttrs},
+        test::{assert_eq, *},
+    };
+
+    fn parse(test: &str) -> (ItemFn, RsTestInfo) {
+        let mut test: ItemFn = test.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut test).unwrap();
+        (test, info)
+    }
+
+    #[rstest]
+    #[case::default_fixture("fn f(#[lazy] a: Lazy<A>) {}", "a", "", "a::default()")]
+    #[case::without_underscore("fn f(#[lazy] _a: Lazy<A>) {}", "_a", "", "a::default()")]
+    #[case::given_fixture(
+        "fn f(#[lazy] #[with(42, b.clone())] a: Lazy<A>) {}",
+        "a",
+        "let __rstest_lazy_arg_0 = 42; let __rstest_lazy_arg_1 = b.clone();",
+        "a::partial_2(__rstest_lazy_arg_0, __rstest_lazy_arg_1)"
+    )]
+    fn resolve_lazy_arguments_by_a_closure(
+        #[case] test: &str,
+        #[case] arg: &str,
+        #[case] arguments: &str,
+        #[case] fixture: &str,
+    ) {
+        let (test, info) = parse(test);
+        let fixtures = crate::resolver::fixtures::get(info.data.fixtures());
+
+        let resolver = resolver(&test, &info.arguments, &fixtures);
+
+        assert_eq!(
+            resolver.resolve(&ident(arg)).unwrap().into_owned(),
+            format!("{{ {arguments} rstest::lazy::lazy(move || {fixture}) }}").ast()
+        );
+    }
+
+    #[test]
+    fn not_resolve_other_arguments() {
+        let (test, info) = parse("fn f(#[lazy] a: Lazy<A>, b: B) {}");
+        let fixtures = HashMap::<String, Expr>::new();
+
+        let resolver = resolver(&test, &info.arguments, &fixtures);
+
+        assert!(resolver.resolve(&ident("b")).is_none());
+    }
+}
@@ -1,12 +1,20 @@
+pub(crate) mod autouse;
+mod builder;
+mod by_ref;
+mod by_type;
+mod context;
 pub(crate) mod fixture;
+mod group;
+mod lazy;
 mod test;
+pub(crate) mod variants;
 mod wrapper;
 
 use std::collections::HashMap;
 
 use syn::token::Async;
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::TokenStream;
 use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, Path, ReturnType, Stmt};
 
 use quote::{format_ident, quote};
@@ -14,9 +22,11 @@ use quote::{format_ident, quote};
 use crate::utils::{attr_ends_with, sanitize_ident};
 use crate::{
     parse::{
+        arguments::ArgumentsInfo,
         rstest::{RsTestAttributes, RsTestData, RsTestInfo},
         testcase::TestCase,
         vlist::ValueList,
+        Fixture,
     },
     utils::attr_is,
 };
@@ -24,6 +34,7 @@ use crate::{
     refident::MaybeIdent,
     resolver::{self, Resolver},
 };
+use context::TestContext;
 use wrapper::WrapByModule;
 
 pub(crate) use fixture::render as fixture;
@@ -34,7 +45,8 @@ pub(crate) mod inject;
 
 pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
     test.apply_argumets(&info.arguments);
-    let resolver = resolver::fixtures::get(info.data.fixtures());
+    let resolver = fixtures_resolver(&test, info.data.fixtures(), &info.arguments);
+    let statics = group::statics(&test, &info.arguments, 1);
     let args = test.sig.inputs.iter().cloned().collect::<Vec<_>>();
     let attrs = std::mem::take(&mut test.attrs);
     let asyncness = test.sig.asyncness;
@@ -53,10 +65,11 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         &attrs,
         &test.sig.output,
         asyncness,
-        Some(&test),
+        Some(quote! { #statics #test }),
         resolver,
         &info.attributes,
         &generic_types,
+        &TestContext::new(&info.arguments),
     )
 }
 
@@ -67,16 +80,17 @@ pub(crate) fn parametrize(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         arguments,
     } = info;
     test.apply_argumets(&arguments);
-    let resolver_fixtures = resolver::fixtures::get(data.fixtures());
+    let statics = group::statics(&test, &arguments, data.cases().count());
+    let context = TestContext::new(&arguments);
 
-    let rendered_cases = cases_data(&data, test.sig.ident.span())
-        .map(|(name, attrs, resolver)| {
-            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures))
-        })
+    let rendered_cases = cases_data(&test, &data, &arguments, &context)
+        .map(|(name, attrs, resolver, context)| TestCaseRender::new(name, attrs, resolver, context))
         .map(|case| case.render(&test, &attributes))
-        .collect();
+        .collect::<TokenStream>();
+
+    let tracked = track_files(&data);
 
-    test_group(test, rendered_cases)
+    test_group(test, quote! { #tracked #statics #rendered_cases })
 }
 
 impl ValueList {
@@ -86,11 +100,15 @@ impl ValueList {
         resolver: &dyn Resolver,
         attrs: &[syn::Attribute],
         attributes: &RsTestAttributes,
+        context: &TestContext,
     ) -> TokenStream {
         let span = test.sig.ident.span();
         let test_cases = self
             .argument_data(resolver)
-            .map(|(name, r)| TestCaseRender::new(Ident::new(&name, span), attrs, r))
+            .map(|(name, r)| {
+                let context = context.with_value(&name);
+                TestCaseRender::new(Ident::new(&name, span), attrs, r, context)
+            })
             .map(|test_case| test_case.render(test, attributes));
 
         quote! { #(#test_cases)* }
@@ -99,7 +117,7 @@ impl ValueList {
     fn argument_data<'a>(
         &'a self,
         resolver: &'a dyn Resolver,
-    ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, (String, Expr))>)> + 'a {
+    ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, HashMap<String, Expr>)>)> + 'a {
         let max_len = self.values.len();
         self.values.iter().enumerate().map(move |(index, value)| {
             let description = sanitize_ident(&value.description());
@@ -109,7 +127,14 @@ impl ValueList {
                 index + 1,
                 len = max_len.display_len()
             );
-            let resolver_this = (self.arg.to_string(), value.expr.clone());
+            let resolver_this = std::iter::once((self.arg.to_string(), value.expr.clone()))
+                .chain(
+                    value
+                        .siblings
+                        .iter()
+                        .map(|(arg, expr)| (arg.to_string(), expr.clone())),
+                )
+                .collect::<HashMap<_, _>>();
             (name, Box::new((resolver, resolver_this)))
         })
     }
@@ -121,6 +146,7 @@ fn _matrix_recursive<'a>(
     resolver: &dyn Resolver,
     attrs: &'a [syn::Attribute],
     attributes: &RsTestAttributes,
+    context: &TestContext,
 ) -> TokenStream {
     if list_values.is_empty() {
         return Default::default();
@@ -133,11 +159,12 @@ fn _matrix_recursive<'a>(
         attrs.push(parse_quote!(
             #[allow(non_snake_case)]
         ));
-        vlist.render(test, resolver, &attrs, attributes)
+        vlist.render(test, resolver, &attrs, attributes, context)
     } else {
         let span = test.sig.ident.span();
         let modules = vlist.argument_data(resolver).map(move |(name, resolver)| {
-            _matrix_recursive(test, list_values, &resolver, attrs, attributes)
+            let context = context.with_value(&name);
+            _matrix_recursive(test, list_values, &resolver, attrs, attributes, &context)
                 .wrap_by_mod(&Ident::new(&name, span))
         });
 
@@ -155,32 +182,63 @@ pub(crate) fn matrix(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         arguments,
     } = info;
     test.apply_argumets(&arguments);
-    let span = test.sig.ident.span();
-
-    let cases = cases_data(&data, span).collect::<Vec<_>>();
-
-    let resolver = resolver::fixtures::get(data.fixtures());
-    let rendered_cases = if cases.is_empty() {
+    let context = TestContext::new(&arguments);
+
+    // Each case module is a group
+    let statics = group::statics(
+        &test,
+        &arguments,
+        data.list_values().map(|v| v.values.len()).product(),
+    );
+    let rendered_cases = if !data.has_cases() {
+        let resolver = fixtures_resolver(&test, data.fixtures(), &arguments);
         let list_values = data.list_values().collect::<Vec<_>>();
-        _matrix_recursive(&test, &list_values, &resolver, &[], &attributes)
+        let rendered =
+            _matrix_recursive(&test, &list_values, &resolver, &[], &attributes, &context);
+        quote! { #statics #rendered }
     } else {
-        cases
-            .into_iter()
-            .map(|(case_name, attrs, case_resolver)| {
+        cases_data(&test, &data, &arguments, &context)
+            .map(|(case_name, attrs, case_resolver, context)| {
                 let list_values = data.list_values().collect::<Vec<_>>();
-                _matrix_recursive(
+                let rendered = _matrix_recursive(
                     &test,
                     &list_values,
-                    &(case_resolver, &resolver),
+                    &case_resolver,
                     attrs,
                     &attributes,
-                )
-                .wrap_by_mod(&case_name)
+                    &context,
+                );
+                quote! { #statics #rendered }.wrap_by_mod(&case_name)
             })
             .collect()
     };
+    let tracked = track_files(&data);
+
+    test_group(test, quote! { #tracked #rendered_cases })
+}
 
-    test_group(test, rendered_cases)
+/// Resolve the given fixtures and the arguments resolved by type, sharing the group
+/// scoped ones, wrapping the lazy ones in a closure and borrowing the `#[by_ref]` ones.
+/// When a fixture is given more than once the last one wins.
+fn fixtures_resolver<'a>(
+    test: &ItemFn,
+    fixtures: impl Iterator<Item = &'a Fixture>,
+    arguments: &ArgumentsInfo,
+) -> impl Resolver + 'a {
+    let fixtures = (
+        by_type::resolver(test, arguments),
+        resolver::fixtures::get(fixtures),
+    );
+    (
+        (
+            group::resolver(test, arguments, &fixtures),
+            (
+                lazy::resolver(test, arguments, &fixtures),
+                by_ref::resolver(test, arguments, &fixtures),
+            ),
+        ),
+        fixtures,
+    )
 }
 
 fn resolve_default_test_attr(is_async: bool) -> TokenStream {
@@ -199,12 +257,9 @@ fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStrea
     }
 }
 
-fn render_test_call(
-    fn_path: Path,
-    args: &[Ident],
-    timeout: Option<Expr>,
-    is_async: bool,
-) -> TokenStream {
+/// Render the test's body: when a timeout is given the whole body, fixtures resolution
+/// included, is executed in the timeout's budget.
+fn render_test_call(body: TokenStream, timeout: Option<Expr>, is_async: bool) -> TokenStream {
     let timeout = timeout.map(|x| quote! {#x}).or_else(|| {
         std::env::var("RSTEST_TIMEOUT")
             .ok().map(|to| quote! { std::time::Duration::from_secs( (#to).parse().unwrap()) })
@@ -212,13 +267,13 @@ fn render_test_call(
     match (timeout, is_async) {
         (Some(to_expr), true) => quote! {
             use rstest::timeout::*;
-            execute_with_timeout_async(move || #fn_path(#(#args),*), #to_expr).await
+            execute_with_timeout_async(move || async move { #body }, #to_expr).await
         },
         (Some(to_expr), false) => quote! {
             use rstest::timeout::*;
-            execute_with_timeout_sync(move || #fn_path(#(#args),*), #to_expr)
+            execute_with_timeout_sync(move || { #body }, #to_expr)
         },
-        _ => render_exec_call(fn_path, args, is_async),
+        _ => body,
     }
 }
 
@@ -231,6 +286,7 @@ fn render_test_call(
 /// * `output` - The expected test return type
 /// * `asyncness` - The `async` fn token
 /// * `test_impl` - If you want embed test function (should be the one called by `testfn_name`)
+///   and the items it needs
 /// * `resolver` - The resolver used to resolve injected values
 /// * `attributes` - Test attributes to select test behaviour
 /// * `generic_types` - The genrics type used in signature
@@ -244,10 +300,11 @@ fn single_test_case(
     attrs: &[Attribute],
     output: &ReturnType,
     asyncness: Option<Async>,
-    test_impl: Option<&ItemFn>,
+    test_impl: Option<TokenStream>,
     resolver: impl Resolver,
     attributes: &RsTestAttributes,
     generic_types: &[Ident],
+    context: &TestContext,
 ) -> TokenStream {
     let (attrs, trace_me): (Vec<_>, Vec<_>) =
         attrs.iter().cloned().partition(|a| !attr_is(a, "trace"));
@@ -255,6 +312,7 @@ fn single_test_case(
     if !trace_me.is_empty() {
         attributes.add_trace(format_ident!("trace"));
     }
+    let resolver = (context.resolver(testfn_name), resolver);
     let inject = inject::resolve_aruments(args.iter(), &resolver, generic_types);
     let args = args
         .iter()
@@ -266,6 +324,9 @@ fn single_test_case(
     let is_async = asyncness.is_some();
     let (attrs, timeouts): (Vec<_>, Vec<_>) =
         attrs.iter().cloned().partition(|a| !attr_is(a, "timeout"));
+    let (attrs, autouses): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|a| !attr_is(a, "autouse"));
+    let autouse = autouse::resolve(&autouses, &args, is_async);
 
     let timeout = timeouts
         .into_iter()
@@ -281,15 +342,35 @@ fn single_test_case(
     } else {
         Some(resolve_default_test_attr(is_async))
     };
-    let execute = render_test_call(testfn_name.clone().into(), &args, timeout, is_async);
+    let call = render_exec_call(testfn_name.clone().into(), &args, is_async);
+    let body = quote! {
+        #autouse
+        #inject
+        #trace_args
+        #call
+    };
+    // The async test's teardowns are collected by its future and the test is marked as
+    // running just while the future is polled, whatever thread polls it
+    let body = if is_async {
+        quote! {
+            let __rstest_test = rstest::context::test(concat!(module_path!(), "::", stringify!(#name)));
+            rstest::teardown::scoped(__rstest_test.run(async move { #body })).await
+        }
+    } else {
+        quote! {
+            let __rstest_teardown = rstest::teardown::Scope::new();
+            let __rstest_test = rstest::context::test(concat!(module_path!(), "::", stringify!(#name)));
+            let __rstest_running = __rstest_test.enter();
+            #body
+        }
+    };
+    let execute = render_test_call(body, timeout, is_async);
 
     quote! {
         #test_attr
         #(#attrs)*
         #asyncness fn #name() #output {
             #test_impl
-            #inject
-            #trace_args
             #execute
         }
     }
@@ -323,14 +404,21 @@ struct TestCaseRender<'a> {
     name: Ident,
     attrs: &'a [syn::Attribute],
     resolver: Box<dyn Resolver + 'a>,
+    context: TestContext,
 }
 
 impl<'a> TestCaseRender<'a> {
-    pub fn new<R: Resolver + 'a>(name: Ident, attrs: &'a [syn::Attribute], resolver: R) -> Self {
+    pub fn new<R: Resolver + 'a>(
+        name: Ident,
+        attrs: &'a [syn::Attribute],
+        resolver: R,
+        context: TestContext,
+    ) -> Self {
         TestCaseRender {
             name,
             attrs,
             resolver: Box::new(resolver),
+            context,
         }
     }
 
@@ -358,10 +446,23 @@ impl<'a> TestCaseRender<'a> {
             self.resolver,
             attributes,
             &generic_types,
+            &self.context,
         )
     }
 }
 
+/// Include the data files the test is built from: the compiler tracks them and builds
+/// the test again when they change.
+fn track_files(data: &RsTestData) -> TokenStream {
+    let paths = data
+        .tracked_files
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned());
+    quote! {
+        #(const _: &[u8] = include_bytes!(#paths);)*
+    }
+}
+
 fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
     let fname = &test.sig.ident;
     test.attrs = vec![];
@@ -398,10 +499,15 @@ fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String
     format!("case_{index:0display_len$}{description}")
 }
 
-fn cases_data(
-    data: &RsTestData,
-    name_span: Span,
-) -> impl Iterator<Item = (Ident, &[syn::Attribute], HashMap<String, &syn::Expr>)> {
+/// The data of every case: its name, attributes, resolver and context. The resolver
+/// resolves the case arguments and the fixtures, where the case's `#[with(...)]`
+/// fixtures take the place of the test's ones.
+fn cases_data<'a>(
+    test: &'a ItemFn,
+    data: &'a RsTestData,
+    arguments: &'a ArgumentsInfo,
+    context: &'a TestContext,
+) -> impl Iterator<Item = (Ident, &'a [syn::Attribute], impl Resolver + 'a, TestContext)> + 'a {
     let display_len = data.cases().count().display_len();
     data.cases().enumerate().map({
         move |(n, case)| {
@@ -410,10 +516,16 @@ fn cases_data(
                 .map(|a| a.to_string())
                 .zip(case.args.iter())
                 .collect::<HashMap<_, _>>();
+            let fixtures =
+                fixtures_resolver(test, data.fixtures().chain(case.fixtures.iter()), arguments);
             (
-                Ident::new(&format_case_name(case, n + 1, display_len), name_span),
+                Ident::new(
+                    &format_case_name(case, n + 1, display_len),
+                    test.sig.ident.span(),
+                ),
                 case.attrs.as_slice(),
-                resolver_case,
+                (resolver_case, fixtures),
+                context.with_case(n + 1, case.description.as_ref()),
             )
         }
     })
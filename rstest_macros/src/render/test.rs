@@ -130,6 +130,33 @@ mod single_test_should {
         assert_eq!(result.attrs, attributes);
     }
 
+    #[rstest]
+    #[case::sync(
+        false,
+        "execute_with_timeout_sync (move | | {",
+        "rstest :: teardown :: Scope :: new ()"
+    )]
+    #[case::async_fn(
+        true,
+        "execute_with_timeout_async (move | | async move {",
+        "rstest :: teardown :: scoped (__rstest_test . run (async move {"
+    )]
+    fn resolve_fixtures_in_the_timeout(
+        #[case] is_async: bool,
+        #[case] execute: &str,
+        #[case] scope: &str,
+    ) {
+        let mut input_fn: ItemFn = r#"#[timeout(d)] fn test(fix: u32) {} "#.ast();
+        input_fn.set_async(is_async);
+
+        let result: ItemFn = single(input_fn, Default::default()).ast();
+
+        let code = result.block.display_code();
+        let execute = code.find(execute).unwrap();
+        assert!(execute < code.find(scope).unwrap());
+        assert!(execute < code.find("let fix = fix :: default ()").unwrap());
+    }
+
     #[test]
     fn use_global_await() {
         let input_fn: ItemFn = r#"fn test(a: i32, b:i32, c:i32) {} "#.ast();
@@ -498,6 +525,7 @@ mod cases_should {
                 .cloned()
                 .map(RsTestItem::CaseArgName)
                 .collect(),
+            ..Default::default()
         }
     }
 
@@ -565,6 +593,18 @@ mod cases_should {
         assert_eq!(output.module.ident, "should_be_the_module_name");
     }
 
+    #[test]
+    fn include_the_tracked_files() {
+        let (item_fn, mut info) = one_simple_case();
+        info.data.tracked_files = vec!["/base/data.csv".into(), "/base/other.txt".into()];
+
+        let tokens = parametrize(item_fn, info);
+
+        let code = tokens.display_code();
+        assert_in!(code, r#"const _ : & [u8] = include_bytes ! ("/base/data.csv") ;"#);
+        assert_in!(code, r#"const _ : & [u8] = include_bytes ! ("/base/other.txt") ;"#);
+    }
+
     #[test]
     fn copy_user_function() {
         let (item_fn, info) = TestCaseBuilder::from(
@@ -1020,6 +1060,32 @@ mod cases_should {
         assert_in!(code, await_argument_code_string("b"));
         assert_not_in!(code, await_argument_code_string("c"));
     }
+
+    #[test]
+    fn resolve_case_fixtures_just_in_their_case() {
+        let item_fn: ItemFn = r#"fn test(a: u32, user: User) {}"#.ast();
+        let info: RsTestInfo = RsTestData {
+            items: vec![
+                ident("a").into(),
+                TestCase::from("1")
+                    .with_fixtures(&[r#"user("admin")"#])
+                    .into(),
+                TestCase::from("2").into(),
+            ],
+            ..Default::default()
+        }
+        .into();
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+
+        let first = tests[0].block.display_code();
+        let second = tests[1].block.display_code();
+        assert_in!(first, r#"user :: partial_1 ("admin")"#);
+        assert_not_in!(first, "user :: default ()");
+        assert_in!(second, "user :: default ()");
+    }
 }
 
 mod matrix_cases_should {
@@ -1046,6 +1112,7 @@ mod matrix_cases_should {
                     .into()
                 })
                 .collect(),
+            ..Default::default()
         }
     }
 
@@ -1088,6 +1155,7 @@ mod matrix_cases_should {
         let info = RsTestInfo {
             data: RsTestData {
                 items: vec![values_list("fix", &["1"]).into()].into(),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1108,6 +1176,7 @@ mod matrix_cases_should {
         let info = RsTestInfo {
             data: RsTestData {
                 items: vec![values_list("fix", &["1"]).into()].into(),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1126,6 +1195,7 @@ mod matrix_cases_should {
         let info = RsTestInfo {
             data: RsTestData {
                 items: vec![values_list("fix", &["1"]).into()].into(),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1148,6 +1218,7 @@ mod matrix_cases_should {
         let info = RsTestInfo {
             data: RsTestData {
                 items: vec![values_list("fix", &["1", "2", "3"]).into()].into(),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1207,6 +1278,7 @@ mod matrix_cases_should {
         let info = RsTestInfo {
             data: RsTestData {
                 items: vec![values_list(arg_name, &["1", "2", "3"]).into()].into(),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1240,6 +1312,7 @@ mod matrix_cases_should {
         let filter = attrs("#[allow(non_snake_case)]");
         let data = RsTestData {
             items: vec![values_list("v", &["1", "2", "3"]).into()].into(),
+            ..Default::default()
         };
         let mut item_fn: ItemFn = r#"fn test(v: u32) {{ println!("user code") }}"#.ast();
         item_fn.set_async(is_async);
@@ -1280,6 +1353,7 @@ mod matrix_cases_should {
         let attributes = attrs(attributes);
         let data = RsTestData {
             items: vec![values_list("v", &["1", "2", "3"]).into()].into(),
+            ..Default::default()
         };
 
         let mut item_fn: ItemFn = r#"fn test(v: u32) {{ println!("user code") }}"#.ast();
@@ -1345,6 +1419,7 @@ mod matrix_cases_should {
         let non_snake_case = &attrs("#[allow(non_snake_case)]")[0];
         let data = RsTestData {
             items: vec![values_list("v", &["1", "2", "3"]).into()].into(),
+            ..Default::default()
         };
 
         let mut item_fn: ItemFn = r#"fn test(v: u32) {{ println!("user code") }}"#.ast();
@@ -1369,6 +1444,7 @@ mod matrix_cases_should {
     fn use_await_for_async_test_function(#[case] is_async: bool, #[case] use_await: bool) {
         let data = RsTestData {
             items: vec![values_list("v", &["1", "2", "3"]).into()].into(),
+            ..Default::default()
         };
 
         let mut item_fn: ItemFn = r#"fn test(v: u32) {{ println!("user code") }}"#.ast();
@@ -1395,6 +1471,7 @@ mod matrix_cases_should {
                 values_list("b_trace_me", &["3", "4"]).into(),
             ]
             .into(),
+            ..Default::default()
         };
         let item_fn: ItemFn = r#"#[trace] fn test(a_trace_me: u32, b_trace_me: u32) {}"#.ast();
 
@@ -1420,6 +1497,7 @@ mod matrix_cases_should {
                 values_list("d_trace_me", &["7", "8"]).into(),
             ]
             .into(),
+            ..Default::default()
         };
         let mut attributes: RsTestAttributes = Default::default();
         attributes.add_notraces(vec![ident("b_no_trace_me"), ident("c_no_trace_me")]);
@@ -1463,6 +1541,7 @@ mod matrix_cases_should {
                 values_list("c", &["3"]).into(),
             ]
             .into(),
+            ..Default::default()
         };
         let mut info = RsTestInfo {
             data,
@@ -1494,6 +1573,7 @@ mod matrix_cases_should {
                 values_list("c", &["3"]).into(),
             ]
             .into(),
+            ..Default::default()
         };
         let mut info = RsTestInfo {
             data,
@@ -1535,6 +1615,7 @@ mod matrix_cases_should {
                             values_list(names[0], &["1", "2", "3"]).into(),
                             values_list(names[1], &["1", "2"]).into(),
                         ],
+                        ..Default::default()
                     },
                     ..Default::default()
                 },
@@ -1632,6 +1713,7 @@ mod matrix_cases_should {
                     values_list(second, &["1", "2", "3"]).into(),
                     values_list(third, &["1", "2"]).into(),
                 ],
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1675,6 +1757,7 @@ mod matrix_cases_should {
                     values_list("second", values[..10].as_ref()).into(),
                     values_list("third", values[..2].as_ref()).into(),
                 ],
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1732,6 +1815,7 @@ mod complete_should {
                 values_list("x", &["12", "-2"]).into(),
                 values_list("y", &["-3", "42"]).into(),
             ],
+            ..Default::default()
         };
 
         matrix(item_fn.clone(), data.into()).into()
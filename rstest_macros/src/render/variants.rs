@@ -0,0 +1,98 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ItemFn;
+
+use crate::parse::variants::{FixtureVariants, VARIANTS_ATTR};
+
+/// The callback macro of a parametrized fixture: a test that marks an argument by a bare
+/// `#[values]` calls it to know the fixture's variants. The macro gives them back in a
+/// `#[__rstest_variants(...)]` attribute and applies `#[rstest]` again.
+///
+/// The macro is imported by the fixture's name, so it can be used where the fixture's
+/// struct is visible in the same crate.
+pub(crate) fn callback_macro(fixture: &ItemFn, descriptions: &[String]) -> TokenStream {
+    let name = &fixture.sig.ident;
+    // Fixtures with the same name can live in nested modules: the macro's name
+    // should be unique
+    let mut hasher = DefaultHasher::new();
+    fixture.to_token_stream().to_string().hash(&mut hasher);
+    let macro_name = format_ident!("__rstest_fixture_{}_{:x}", name, hasher.finish());
+    let attr = format_ident!("{}", VARIANTS_ATTR);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($arg:ident [$($rstest:tt)*] $($test:tt)*) => {
+                $($rstest)*
+                #[#attr($arg, #(#descriptions),*)]
+                $($test)*
+            };
+        }
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name as #name;
+    }
+}
+
+/// Call the callback macro of the fixture that gives the argument's variants: it expands
+/// to the same test with the variants' descriptions.
+pub(crate) fn callback(
+    args: TokenStream,
+    test: TokenStream,
+    variants: &FixtureVariants,
+) -> TokenStream {
+    let FixtureVariants { arg, fixture, .. } = variants;
+    let args = (!args.is_empty()).then(|| quote! { (#args) });
+    quote_spanned! { fixture.span() =>
+        #fixture! { #arg [#[rstest::rstest #args]] #test }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    #[test]
+    fn call_the_fixture_macro_with_the_rstest_attribute() {
+        let variants = FixtureVariants {
+            arg: ident("store"),
+            fixture: ident("backend_store"),
+            descriptions: None,
+        };
+
+        let out = callback(
+            quote! { ::trace },
+            quote! { fn test(#[values] store: Store) {} },
+            &variants,
+        );
+
+        assert_eq!(
+            quote! {
+                backend_store! {
+                    store [#[rstest::rstest (::trace)]] fn test(#[values] store: Store) {}
+                }
+            }
+            .to_string(),
+            out.to_string()
+        );
+    }
+
+    #[test]
+    fn give_the_descriptions_of_the_variants() {
+        let fixture: ItemFn = "fn store(#[values(1, 2)] size: u32) -> Store {}".ast();
+
+        let out = callback_macro(&fixture, &["1".to_owned(), "2".to_owned()]).to_string();
+
+        assert!(
+            out.contains(r#"# [__rstest_variants ($ arg , "1" , "2")]"#),
+            "{out}"
+        );
+        assert!(out.contains("as store ;"), "{out}");
+    }
+}
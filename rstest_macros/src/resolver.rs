@@ -23,6 +23,11 @@ pub(crate) mod fixtures {
 
     fn extract_resolve_expression(fixture: &Fixture) -> syn::Expr {
         let resolve = fixture.resolve.as_ref().unwrap_or(&fixture.name);
+        if !fixture.named.is_empty() {
+            let names = fixture.named.iter().map(|n| &n.name);
+            let exprs = fixture.named.iter().map(|n| &n.expr);
+            return parse_quote! { #resolve::builder()#(.#names(#exprs))*.build() };
+        }
         let positional = &fixture.positional.0;
         let f_name = match positional.len() {
             0 => format_ident!("default"),
@@ -61,6 +66,22 @@ pub(crate) mod fixtures {
 
             assert_eq!(resolved, format!("pluto::{}", expected).ast());
         }
+
+        #[rstest]
+        #[case::simple("pippo(age = 42)", "pippo::builder().age(42).build()")]
+        #[case::more(
+            r#"pippo(age = 42, country = "IT")"#,
+            r#"pippo::builder().age(42).country("IT").build()"#
+        )]
+        #[case::resolve_field("pippo(age = 42) as other", "pippo::builder().age(42).build()")]
+        fn resolve_named_arguments_by_the_builder(#[case] fixture: &str, #[case] expected: &str) {
+            let data = [fixture.ast::<Fixture>()];
+            let resolver = get(data.iter());
+
+            let resolved = resolver.resolve(&data[0].name).unwrap().into_owned();
+
+            assert_eq!(resolved, expected.ast());
+        }
     }
 }
 
@@ -217,6 +217,14 @@ impl Fixture {
         self.resolve = Some(ident(resolve_ident));
         self
     }
+
+    pub fn with_named(mut self, named: &[(&str, &str)]) -> Self {
+        self.named = named
+            .iter()
+            .map(|(name, value)| arg_value(name, value))
+            .collect();
+        self
+    }
 }
 
 impl TestCase {
@@ -229,6 +237,11 @@ impl TestCase {
         self.attrs = attrs;
         self
     }
+
+    pub fn with_fixtures(mut self, fixtures: &[&str]) -> Self {
+        self.fixtures = fixtures.iter().map(|f| f.ast()).collect();
+        self
+    }
 }
 
 impl<A: AsRef<str>> FromIterator<A> for TestCase {
@@ -237,6 +250,7 @@ impl<A: AsRef<str>> FromIterator<A> for TestCase {
             args: iter.into_iter().map(expr).collect(),
             attrs: Default::default(),
             description: None,
+            fixtures: Default::default(),
         }
     }
 }
@@ -249,7 +263,10 @@ impl<'a> From<&'a str> for TestCase {
 
 impl From<Vec<RsTestItem>> for RsTestData {
     fn from(items: Vec<RsTestItem>) -> Self {
-        Self { items }
+        Self {
+            items,
+            ..Default::default()
+        }
     }
 }
 
@@ -271,6 +271,32 @@ pub(crate) fn fn_arg_mutability(arg: &FnArg) -> Option<syn::token::Mut> {
     }
 }
 
+/// The `T` of a `-> Result<T, E>` return type: the first type argument of the last
+/// path segment, so aliases like `io::Result<T>` work too.
+pub(crate) fn result_ok_type(rt: &ReturnType) -> Option<ReturnType> {
+    match rt {
+        ReturnType::Type(arrow, t) => match t.as_ref() {
+            Type::Path(tp) if tp.qself.is_none() => tp
+                .path
+                .segments
+                .last()
+                .filter(|ps| ps.ident == "Result")
+                .and_then(|ps| match &ps.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args.args.first(),
+                    _ => None,
+                })
+                .and_then(|arg| match arg {
+                    syn::GenericArgument::Type(t) => {
+                        Some(ReturnType::Type(*arrow, Box::new(t.clone())))
+                    }
+                    _ => None,
+                }),
+            _ => None,
+        },
+        ReturnType::Default => None,
+    }
+}
+
 pub(crate) fn sanitize_ident(name: &str) -> String {
     name.chars()
         .filter(|c| !c.is_whitespace())
@@ -404,6 +430,21 @@ mod test {
         assert_eq!(expected.sig.generics, cleaned);
     }
 
+    #[rstest]
+    #[case::result("fn f() -> Result<Db, Error> {}", Some("fn f() -> Db {}"))]
+    #[case::alias("fn f() -> std::io::Result<Db> {}", Some("fn f() -> Db {}"))]
+    #[case::lifetime("fn f<'a>() -> Result<&'a Db, E> {}", Some("fn f<'a>() -> &'a Db {}"))]
+    #[case::not_result("fn f() -> Option<Db> {}", None)]
+    #[case::no_type_arguments("fn f() -> Result {}", None)]
+    #[case::no_return_type("fn f() {}", None)]
+    fn result_ok_type_should(#[case] code: &str, #[case] expected: Option<&str>) {
+        let item_fn: ItemFn = code.ast();
+
+        let ok = result_ok_type(&item_fn.sig.output);
+
+        assert_eq!(expected.map(|e| e.ast::<ItemFn>().sig.output), ok);
+    }
+
     #[rstest]
     #[case("1", "1")]
     #[case(r#""1""#, "__1__")]
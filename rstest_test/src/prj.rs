@@ -50,6 +50,7 @@ pub struct Project {
     nocapture: bool,
     ws: Arc<std::sync::RwLock<()>>,
     default_timeout: Option<u64>,
+    envs: Vec<(OsString, OsString)>,
 }
 
 impl Project {
@@ -63,6 +64,7 @@ impl Project {
             nocapture: false,
             ws: Arc::new(std::sync::RwLock::new(())),
             default_timeout: Default::default(),
+            envs: Default::default(),
         }
         .create()
     }
@@ -86,6 +88,7 @@ impl Project {
             nocapture: self.nocapture,
             ws: self.ws.clone(),
             default_timeout: Default::default(),
+            envs: Default::default(),
         }
         .create()
     }
@@ -109,6 +112,7 @@ impl Project {
         if let Some(timeout) = self.default_timeout {
             cmd.env("RSTEST_TIMEOUT", timeout.to_string());
         }
+        cmd.envs(self.envs.iter().map(|(k, v)| (k, v)));
 
         cmd.current_dir(&self.path())
             .arg(&self.cargo_channel_arg())
@@ -190,6 +194,15 @@ impl Project {
         self.save_cargo_toml(&doc);
     }
 
+    pub fn add_build_dependency(&self, crate_name: &str, attrs: &str) {
+        let mut doc = self.read_cargo_toml();
+
+        doc["build-dependencies"].or_insert(Item::Table(Table::new()))[crate_name]
+            .or_insert(Item::Value(attrs.parse().unwrap()));
+
+        self.save_cargo_toml(&doc);
+    }
+
     pub fn add_path_dependency(&self, name: &str, path: &str) {
         self.add_dependency(name, format!(r#"{{path="{path}"}}"#).as_str());
     }
@@ -262,4 +275,9 @@ impl Project {
     pub fn set_default_timeout(&mut self, timeout: u64) {
         self.default_timeout = Some(timeout);
     }
+
+    pub fn set_env<K: AsRef<OsStr>, V: AsRef<OsStr>>(&mut self, key: K, value: V) {
+        self.envs
+            .push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+    }
 }